@@ -4,7 +4,10 @@
 //! - RSA STD
 //!
 //! Each cipher exposes the `sign` and `verify` static methods.
+//!
+//! The X25519 key agreement is also available (see [x25519]).
 
 mod common;
 
-pub mod rsa;
\ No newline at end of file
+pub mod rsa;
+pub mod x25519;
\ No newline at end of file
@@ -0,0 +1,270 @@
+//! X25519 key agreement (cf. RFC 7748)
+//!
+//! Field elements of GF(2^255 - 19) are stored as 5 limbs of 51 bits.
+//! The Montgomery ladder uses constant-time conditional swaps, so the execution does not depend on the
+//! secret scalar.
+
+type FieldElement = [u64; 5];
+
+const MASK_51: u64 = (1 << 51) - 1;
+
+// (A - 2) / 4, with A = 486662
+const A24: u64 = 121665;
+
+/// Base point (u = 9).
+pub const BASE_POINT: [u8; 32] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+fn le64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+// the most significant bit is ignored (cf. RFC 7748, 5)
+fn fe_from_bytes(bytes: &[u8; 32]) -> FieldElement {
+    [
+        le64(&bytes[0..]) & MASK_51,
+        (le64(&bytes[6..]) >> 3) & MASK_51,
+        (le64(&bytes[12..]) >> 6) & MASK_51,
+        (le64(&bytes[19..]) >> 1) & MASK_51,
+        (le64(&bytes[24..]) >> 12) & MASK_51,
+    ]
+}
+
+fn fe_carry(mut a: FieldElement) -> FieldElement {
+    for i in 0..4 {
+        a[i + 1] += a[i] >> 51;
+        a[i] &= MASK_51;
+    }
+    a[0] += (a[4] >> 51) * 19;
+    a[4] &= MASK_51;
+    a[1] += a[0] >> 51;
+    a[0] &= MASK_51;
+    a
+}
+
+fn fe_to_bytes(a: &FieldElement) -> [u8; 32] {
+    let mut h = fe_carry(fe_carry(*a));
+
+    // h < 2^255 + small, subtract p if h >= p
+    let mut q = (h[0] + 19) >> 51;
+    for limb in &h[1..] {
+        q = (limb + q) >> 51;
+    }
+    h[0] += 19 * q;
+    for i in 0..4 {
+        h[i + 1] += h[i] >> 51;
+        h[i] &= MASK_51;
+    }
+    h[4] &= MASK_51;
+
+    let mut bytes = [0; 32];
+    let mut acc: u128 = 0;
+    let mut acc_bits = 0;
+    let mut index = 0;
+    for limb in h {
+        acc |= (limb as u128) << acc_bits;
+        acc_bits += 51;
+        while acc_bits >= 8 && index < 32 {
+            bytes[index] = acc as u8;
+            acc >>= 8;
+            acc_bits -= 8;
+            index += 1;
+        }
+    }
+    bytes[31] = acc as u8;
+    bytes
+}
+
+fn fe_add(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    fe_carry(core::array::from_fn(|i| a[i] + b[i]))
+}
+
+// a + 2p - b
+fn fe_sub(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    const TWO_P: FieldElement = [0xFFFFFFFFFFFDA, 0xFFFFFFFFFFFFE, 0xFFFFFFFFFFFFE, 0xFFFFFFFFFFFFE, 0xFFFFFFFFFFFFE];
+    fe_carry(core::array::from_fn(|i| a[i] + TWO_P[i] - b[i]))
+}
+
+fn fe_mul(a: &FieldElement, b: &FieldElement) -> FieldElement {
+    let a = a.map(|x| x as u128);
+    let b = b.map(|x| x as u128);
+
+    // 2^255 = 19 mod p
+    let r = [
+        a[0] * b[0] + 19 * (a[1] * b[4] + a[2] * b[3] + a[3] * b[2] + a[4] * b[1]),
+        a[0] * b[1] + a[1] * b[0] + 19 * (a[2] * b[4] + a[3] * b[3] + a[4] * b[2]),
+        a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + 19 * (a[3] * b[4] + a[4] * b[3]),
+        a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + 19 * (a[4] * b[4]),
+        a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0],
+    ];
+
+    let mut out = [0; 5];
+    let mut carry = 0;
+    for i in 0..5 {
+        let t = r[i] + carry;
+        out[i] = (t as u64) & MASK_51;
+        carry = t >> 51;
+    }
+    out[0] += (carry as u64) * 19;
+    fe_carry(out)
+}
+
+fn fe_square(a: &FieldElement) -> FieldElement {
+    fe_mul(a, a)
+}
+
+fn fe_mul_small(a: &FieldElement, b: u64) -> FieldElement {
+    fe_mul(a, &[b, 0, 0, 0, 0])
+}
+
+// a^(p - 2), with p - 2 = 2^255 - 21
+fn fe_invert(a: &FieldElement) -> FieldElement {
+    let mut res = [1, 0, 0, 0, 0];
+    for bit in (0..255).rev() {
+        res = fe_square(&res);
+        // all bits are set, except bits 2 and 4
+        if bit != 2 && bit != 4 {
+            res = fe_mul(&res, a);
+        }
+    }
+    res
+}
+
+fn fe_cswap(a: &mut FieldElement, b: &mut FieldElement, swap: u64) {
+    let mask = 0u64.wrapping_sub(swap);
+    for i in 0..5 {
+        let t = mask & (a[i] ^ b[i]);
+        a[i] ^= t;
+        b[i] ^= t;
+    }
+}
+
+/// Computes the X25519 function: `scalar` (clamped) times the point of u-coordinate `u`.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+
+    // Montgomery ladder (cf. RFC 7748, 5)
+    let x_1 = fe_from_bytes(u);
+    let mut x_2 = [1, 0, 0, 0, 0];
+    let mut z_2 = [0; 5];
+    let mut x_3 = x_1;
+    let mut z_3 = [1, 0, 0, 0, 0];
+    let mut swap = 0;
+
+    for t in (0..255).rev() {
+        let k_t = ((k[t / 8] >> (t % 8)) & 1) as u64;
+        swap ^= k_t;
+        fe_cswap(&mut x_2, &mut x_3, swap);
+        fe_cswap(&mut z_2, &mut z_3, swap);
+        swap = k_t;
+
+        let a = fe_add(&x_2, &z_2);
+        let aa = fe_square(&a);
+        let b = fe_sub(&x_2, &z_2);
+        let bb = fe_square(&b);
+        let e = fe_sub(&aa, &bb);
+        let c = fe_add(&x_3, &z_3);
+        let d = fe_sub(&x_3, &z_3);
+        let da = fe_mul(&d, &a);
+        let cb = fe_mul(&c, &b);
+        x_3 = fe_square(&fe_add(&da, &cb));
+        z_3 = fe_mul(&x_1, &fe_square(&fe_sub(&da, &cb)));
+        x_2 = fe_mul(&aa, &bb);
+        z_2 = fe_mul(&e, &fe_add(&aa, &fe_mul_small(&e, A24)));
+    }
+    fe_cswap(&mut x_2, &mut x_3, swap);
+    fe_cswap(&mut z_2, &mut z_3, swap);
+
+    fe_to_bytes(&fe_mul(&x_2, &fe_invert(&z_2)))
+}
+
+/// Returns the public key associated to `secret_key`.
+pub fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    x25519(secret_key, &BASE_POINT)
+}
+
+/// Computes the shared secret between `secret_key` and the peer's `public_key`.
+///
+/// Fails if the result is all zeros (i.e. the peer's public key has a small order).
+pub fn shared_secret(secret_key: &[u8; 32], public_key: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    let shared = x25519(secret_key, public_key);
+    let zero = shared.iter().fold(0, |acc, x| acc | x);
+    if zero == 0 {
+        return Err("Shared secret is all zeros");
+    }
+    Ok(shared)
+}
+
+#[cfg(test)]
+mod tests_x25519 {
+    use super::*;
+
+    const ALICE_SECRET: [u8; 32] = [
+        0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+        0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+    ];
+    const BOB_SECRET: [u8; 32] = [
+        0x5D, 0xAB, 0x08, 0x7E, 0x62, 0x4A, 0x8A, 0x4B, 0x79, 0xE1, 0x7F, 0x8B, 0x83, 0x80, 0x0E, 0xE6,
+        0x6F, 0x3B, 0xB1, 0x29, 0x26, 0x18, 0xB6, 0xFD, 0x1C, 0x2F, 0x8B, 0x27, 0xFF, 0x88, 0xE0, 0xEB,
+    ];
+
+    #[test]
+    fn test_x25519_rfc7748() {
+        // RFC 7748, 5.2
+        let scalar = [
+            0xA5, 0x46, 0xE3, 0x6B, 0xF0, 0x52, 0x7C, 0x9D, 0x3B, 0x16, 0x15, 0x4B, 0x82, 0x46, 0x5E, 0xDD,
+            0x62, 0x14, 0x4C, 0x0A, 0xC1, 0xFC, 0x5A, 0x18, 0x50, 0x6A, 0x22, 0x44, 0xBA, 0x44, 0x9A, 0xC4,
+        ];
+        let u = [
+            0xE6, 0xDB, 0x68, 0x67, 0x58, 0x30, 0x30, 0xDB, 0x35, 0x94, 0xC1, 0xA4, 0x24, 0xB1, 0x5F, 0x7C,
+            0x72, 0x66, 0x24, 0xEC, 0x26, 0xB3, 0x35, 0x3B, 0x10, 0xA9, 0x03, 0xA6, 0xD0, 0xAB, 0x1C, 0x4C,
+        ];
+        let expected = [
+            0xC3, 0xDA, 0x55, 0x37, 0x9D, 0xE9, 0xC6, 0x90, 0x8E, 0x94, 0xEA, 0x4D, 0xF2, 0x8D, 0x08, 0x4F,
+            0x32, 0xEC, 0xCF, 0x03, 0x49, 0x1C, 0x71, 0xF7, 0x54, 0xB4, 0x07, 0x55, 0x77, 0xA2, 0x85, 0x52,
+        ];
+        assert_eq!(x25519(&scalar, &u), expected);
+    }
+
+    #[test]
+    fn test_x25519_iterated() {
+        // RFC 7748, 5.2, after one iteration
+        let expected = [
+            0x42, 0x2C, 0x8E, 0x7A, 0x62, 0x27, 0xD7, 0xBC, 0xA1, 0x35, 0x0B, 0x3E, 0x2B, 0xB7, 0x27, 0x9F,
+            0x78, 0x97, 0xB8, 0x7B, 0xB6, 0x85, 0x4B, 0x78, 0x3C, 0x60, 0xE8, 0x03, 0x11, 0xAE, 0x30, 0x79,
+        ];
+        assert_eq!(x25519(&BASE_POINT, &BASE_POINT), expected);
+    }
+
+    #[test]
+    fn test_x25519_key_agreement() {
+        // RFC 7748, 6.1
+        let alice_public = [
+            0x85, 0x20, 0xF0, 0x09, 0x89, 0x30, 0xA7, 0x54, 0x74, 0x8B, 0x7D, 0xDC, 0xB4, 0x3E, 0xF7, 0x5A,
+            0x0D, 0xBF, 0x3A, 0x0D, 0x26, 0x38, 0x1A, 0xF4, 0xEB, 0xA4, 0xA9, 0x8E, 0xAA, 0x9B, 0x4E, 0x6A,
+        ];
+        let bob_public = [
+            0xDE, 0x9E, 0xDB, 0x7D, 0x7B, 0x7D, 0xC1, 0xB4, 0xD3, 0x5B, 0x61, 0xC2, 0xEC, 0xE4, 0x35, 0x37,
+            0x3F, 0x83, 0x43, 0xC8, 0x5B, 0x78, 0x67, 0x4D, 0xAD, 0xFC, 0x7E, 0x14, 0x6F, 0x88, 0x2B, 0x4F,
+        ];
+        let expected = [
+            0x4A, 0x5D, 0x9D, 0x5B, 0xA4, 0xCE, 0x2D, 0xE1, 0x72, 0x8E, 0x3B, 0xF4, 0x80, 0x35, 0x0F, 0x25,
+            0xE0, 0x7E, 0x21, 0xC9, 0x47, 0xD1, 0x9E, 0x33, 0x76, 0xF0, 0x9B, 0x3C, 0x1E, 0x16, 0x17, 0x42,
+        ];
+
+        assert_eq!(public_key(&ALICE_SECRET), alice_public);
+        assert_eq!(public_key(&BOB_SECRET), bob_public);
+        assert_eq!(shared_secret(&ALICE_SECRET, &bob_public), Ok(expected));
+        assert_eq!(shared_secret(&BOB_SECRET, &alice_public), Ok(expected));
+    }
+
+    #[test]
+    fn test_x25519_small_order() {
+        assert!(shared_secret(&ALICE_SECRET, &[0; 32]).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Definition of the bcrypt password hash (Provos and Mazières, 1999)
+//!
+//!```text
+//!   state = EksBlowfishSetup(cost, salt, password)
+//!   ctext = "OrpheanBeholderScryDoubt"
+//!   repeat 64 times: ctext = EncryptECB(state, ctext)
+//!   hash  = ctext[..23]
+//!
+//!   EksBlowfishSetup(cost, salt, key):
+//!     state = InitState()
+//!     state = ExpandKey(state, salt, key)
+//!     repeat 2^cost times:
+//!       state = ExpandKey(state, 0, key)
+//!       state = ExpandKey(state, 0, salt)
+//! ```
+//! The password is NUL-terminated and truncated to 72 bytes (`$2b$` behaviour). Hashes are encoded in the
+//! modular crypt format: `$2b$<cost>$<salt><hash>`, with a specific base64 alphabet.
+
+use crate::symmetric::block_ciphers::blowfish::BlowfishState;
+use crate::utils::constant_time_eq;
+
+const ALPHABET: &[u8; 64] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+fn encode_base64(data: &[u8]) -> String {
+    let mut ret = String::new();
+    for chunk in data.chunks(3) {
+        let mut bytes = [0; 3];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..=chunk.len() {
+            ret.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+    ret
+}
+
+fn decode_base64<const N: usize>(s: &str) -> Result<[u8; N], &'static str> {
+    let mut bits = Vec::with_capacity(s.len() * 6);
+    for c in s.bytes() {
+        let v = ALPHABET.iter().position(|&a| a == c).ok_or("Invalid character in bcrypt hash")?;
+        bits.extend((0..6).rev().map(|i| (v >> i) & 1));
+    }
+    if bits.len() < 8 * N {
+        return Err("Invalid bcrypt hash length");
+    }
+    Ok(core::array::from_fn(|i| bits[8 * i..8 * i + 8].iter().fold(0, |acc, &b| acc << 1 | b as u8)))
+}
+
+/// Computes the raw bcrypt hash of the `password` with a `cost` between 4 and 31.
+pub fn bcrypt(password: &[u8], salt: &[u8; 16], cost: u32) -> Result<[u8; 23], &'static str> {
+    if !(4..=31).contains(&cost) {
+        return Err("bcrypt cost must be between 4 and 31");
+    }
+
+    let mut key = password.to_vec();
+    key.push(0);
+    key.truncate(72);
+
+    // EksBlowfishSetup
+    let mut state = BlowfishState::initial();
+    state.expand_key(&key, salt);
+    for _ in 0..1u64 << cost {
+        state.expand_key(&key, &[0; 16]);
+        state.expand_key(salt, &[0; 16]);
+    }
+
+    // encrypt the magic value 64 times
+    let mut ctext: [u32; 6] = core::array::from_fn(|i| u32::from_be_bytes(b"OrpheanBeholderScryDoubt"[i * 4..i * 4 + 4].try_into().unwrap()));
+    for _ in 0..64 {
+        for i in (0..6).step_by(2) {
+            (ctext[i], ctext[i + 1]) = state.encrypt(ctext[i], ctext[i + 1]);
+        }
+    }
+
+    let mut ret = [0; 23];
+    for (i, b) in ctext.iter().flat_map(|w| w.to_be_bytes()).take(23).enumerate() {
+        ret[i] = b;
+    }
+    Ok(ret)
+}
+
+/// Computes the bcrypt hash of the `password`, in the modular crypt format (`$2b$<cost>$<salt><hash>`).
+pub fn bcrypt_hash(password: &[u8], salt: &[u8; 16], cost: u32) -> Result<String, &'static str> {
+    let hash = bcrypt(password, salt, cost)?;
+    Ok(format!("$2b${:02}${}{}", cost, encode_base64(salt), encode_base64(&hash)))
+}
+
+/// Checks the `password` against a hash in the modular crypt format. `$2a$`, `$2b$` and `$2y$` hashes are
+/// accepted.
+pub fn bcrypt_verify(password: &[u8], hash: &str) -> Result<bool, &'static str> {
+    let parts: Vec<&str> = hash.split('$').collect();
+    if parts.len() != 4 || !parts[0].is_empty() || !["2a", "2b", "2y"].contains(&parts[1]) {
+        return Err("Invalid bcrypt hash format");
+    }
+    if parts[2].len() != 2 || parts[3].len() != 53 || !parts[3].is_ascii() {
+        return Err("Invalid bcrypt hash length");
+    }
+
+    if !parts[2].bytes().all(|b| b.is_ascii_digit()) {
+        return Err("Invalid bcrypt cost");
+    }
+
+    let cost = parts[2].parse().or(Err("Invalid bcrypt cost"))?;
+    let salt = decode_base64::<16>(&parts[3][..22])?;
+    let expected = decode_base64::<23>(&parts[3][22..])?;
+
+    let computed = bcrypt(password, &salt, cost)?;
+    Ok(constant_time_eq(&computed, &expected))
+}
+
+#[cfg(test)]
+mod tests_bcrypt {
+    use super::*;
+
+    // test vectors from the Openwall crypt_blowfish package
+    #[test]
+    fn test_verify() {
+        let vectors = [
+            ("U*U", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"),
+            ("U*U*", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK"),
+            ("U*U*U", "$2a$05$XXXXXXXXXXXXXXXXXXXXXOAcXxm9kjPGEMsLznoKqmqw7tc8WCx4a"),
+            ("", "$2a$05$CCCCCCCCCCCCCCCCCCCCC.7uG0VCzI2bS7j6ymqJi9CdcdxiRTWNy"),
+            (
+                "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789chars after 72 are ignored",
+                "$2a$05$abcdefghijklmnopqrstuu5s2v8.iXieOjg/.AySBTTZIIVFJeBui",
+            ),
+        ];
+
+        for (password, hash) in vectors {
+            assert!(bcrypt_verify(password.as_bytes(), hash).expect("Error during bcrypt execution"));
+            assert!(!bcrypt_verify(b"wrong password", hash).expect("Error during bcrypt execution"));
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        let hash = "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+        let salt = decode_base64::<16>("CCCCCCCCCCCCCCCCCCCCC.").expect("Error during bcrypt execution");
+        assert_eq!(bcrypt_hash(b"U*U", &salt, 5).expect("Error during bcrypt execution"), hash);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(bcrypt(b"password", &[0; 16], 3).is_err());
+        assert!(bcrypt(b"password", &[0; 16], 32).is_err());
+        assert!(bcrypt_verify(b"U*U", "$2x$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW").is_err());
+        assert!(bcrypt_verify(b"U*U", "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOe").is_err());
+        assert!(bcrypt_verify(b"U*U", "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOe!").is_err());
+        assert_eq!(bcrypt_verify(b"U*U", "$2a$+5$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"), Err("Invalid bcrypt cost"));
+    }
+}
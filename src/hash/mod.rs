@@ -8,10 +8,14 @@
 //! - SHA256
 //! - SHA384
 //! - SHA512
+//! - SM3
+//! - Streebog256 / Streebog512
 //!
 //! Each hash exposes the `update` and `finalise` methods to respectively treat some data and compute the final hash
 //! (see [crate::hash::common] for more details).
 //!
+//! The [bcrypt] password hash is also defined here.
+//!
 //! # Example
 //!
 //! ```
@@ -34,8 +38,11 @@
 //! assert_eq!(res, [0xd5, 0x97, 0x6f, 0x79, 0xd8, 0x3d, 0x3a, 0x0d, 0xc9, 0x80, 0x6c, 0x3c, 0x66, 0xf3, 0xef, 0xd8]);
 //! ```
 
+pub mod bcrypt;
 pub mod common;
 pub mod md2;
 pub mod md4;
 pub mod mgf1;
 pub mod sha;
+pub mod sm3;
+pub mod streebog;
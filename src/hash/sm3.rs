@@ -0,0 +1,204 @@
+//! Definition of SM3 algorithm
+
+// see GB/T 32905-2016 (and draft-sca-cfrg-sm3)
+
+use crate::hash::common::{generic_update_func, Hash};
+
+pub struct SM3Context {
+    state: [u32; 8],
+}
+
+pub struct SM3 {
+    context: SM3Context,
+    remaining_bytes: [u8; 63],
+    remaining_bytes_len: usize,
+    msg_length: u64, // in bits
+}
+
+impl Hash<32> for SM3 {
+    const BLOCK_SIZE: usize = 64;
+
+    type InitStruct = ();
+    type Context = SM3Context;
+
+    fn new(_: &Self::InitStruct) -> Self {
+        SM3 {
+            context: SM3Context {
+                state: [0x7380166F, 0x4914B2B9, 0x172442D7, 0xDA8A0600, 0xA96F30BC, 0x163138AA, 0xE38DEE4D, 0xB0FB0E4E],
+            },
+            remaining_bytes: [0; 63],
+            remaining_bytes_len: 0,
+            msg_length: 0,
+        }
+    }
+
+    generic_update_func!(process_block u64);
+
+    fn finalise(&mut self) -> [u8; 32] {
+        let mut cur_block = [0; 64];
+
+        // take remaining bytes from the previous uncompleted block
+        cur_block[..self.remaining_bytes_len].copy_from_slice(&self.remaining_bytes[..self.remaining_bytes_len]);
+
+        // pad
+        cur_block[self.remaining_bytes_len] = 0x80;
+        if self.remaining_bytes_len >= 56 {
+            // no room left for the length, must fill this block and create another
+            process_block(&mut self.context, &cur_block);
+            cur_block.fill(0);
+        }
+
+        // append msg length
+        cur_block[56..].copy_from_slice(&self.msg_length.to_be_bytes());
+
+        // process padded block
+        process_block(&mut self.context, &cur_block);
+
+        // return digest
+        let mut ret = [0; 32];
+        for i in 0..8 {
+            ret[i * 4..i * 4 + 4].copy_from_slice(&self.context.state[i].to_be_bytes());
+        }
+        ret
+    }
+}
+
+#[inline(always)]
+fn p0(x: u32) -> u32 {
+    x ^ x.rotate_left(9) ^ x.rotate_left(17)
+}
+
+#[inline(always)]
+fn p1(x: u32) -> u32 {
+    x ^ x.rotate_left(15) ^ x.rotate_left(23)
+}
+
+#[inline(always)]
+fn ff(j: usize, x: u32, y: u32, z: u32) -> u32 {
+    if j < 16 {
+        x ^ y ^ z
+    } else {
+        (x & y) | (x & z) | (y & z)
+    }
+}
+
+#[inline(always)]
+fn gg(j: usize, x: u32, y: u32, z: u32) -> u32 {
+    if j < 16 {
+        x ^ y ^ z
+    } else {
+        (x & y) | (!x & z)
+    }
+}
+
+fn process_block(context: &mut SM3Context, block: &[u8; 64]) {
+    // message expansion
+    let mut w = [0u32; 68];
+    for i in 0..16 {
+        w[i] = u32::from_be_bytes(block[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+    for j in 16..68 {
+        w[j] = p1(w[j - 16] ^ w[j - 9] ^ w[j - 3].rotate_left(15)) ^ w[j - 13].rotate_left(7) ^ w[j - 6];
+    }
+
+    // do rounds, W'j = Wj xor W(j+4) is computed on the fly
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = context.state;
+    for j in 0..64 {
+        let t: u32 = if j < 16 { 0x79CC4519 } else { 0x7A879D8A };
+        let ss1 = a.rotate_left(12).wrapping_add(e).wrapping_add(t.rotate_left(j as u32 % 32)).rotate_left(7);
+        let ss2 = ss1 ^ a.rotate_left(12);
+        let tt1 = ff(j, a, b, c).wrapping_add(d).wrapping_add(ss2).wrapping_add(w[j] ^ w[j + 4]);
+        let tt2 = gg(j, e, f, g).wrapping_add(h).wrapping_add(ss1).wrapping_add(w[j]);
+        d = c;
+        c = b.rotate_left(9);
+        b = a;
+        a = tt1;
+        h = g;
+        g = f.rotate_left(19);
+        f = e;
+        e = p0(tt2);
+    }
+
+    // do final xor and save context
+    for (s, v) in context.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s ^= v;
+    }
+}
+
+#[cfg(test)]
+mod tests_sm3 {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let mut sm3 = SM3::new(&());
+
+        sm3.update(&[]);
+
+        let res = sm3.finalise();
+        assert_eq!(
+            res,
+            [
+                0x1a, 0xb2, 0x1d, 0x83, 0x55, 0xcf, 0xa1, 0x7f, 0x8e, 0x61, 0x19, 0x48, 0x31, 0xe8,
+                0x1a, 0x8f, 0x22, 0xbe, 0xc8, 0xc7, 0x28, 0xfe, 0xfb, 0x74, 0x7e, 0xd0, 0x35, 0xeb,
+                0x50, 0x82, 0xaa, 0x2b
+            ]
+        );
+    }
+
+    #[test]
+    fn test_abc() {
+        // GB/T 32905-2016, appendix A.1
+        let mut sm3 = SM3::new(&());
+
+        sm3.update("abc".as_bytes());
+
+        let res = sm3.finalise();
+        assert_eq!(
+            res,
+            [
+                0x66, 0xc7, 0xf0, 0xf4, 0x62, 0xee, 0xed, 0xd9, 0xd1, 0xf2, 0xd4, 0x6b, 0xdc, 0x10,
+                0xe4, 0xe2, 0x41, 0x67, 0xc4, 0x87, 0x5c, 0xf2, 0xf7, 0xa2, 0x29, 0x7d, 0xa0, 0x2b,
+                0x8f, 0x4b, 0xa8, 0xe0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_one_block() {
+        // GB/T 32905-2016, appendix A.2
+        let mut sm3 = SM3::new(&());
+
+        sm3.update("abcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd".as_bytes());
+
+        let res = sm3.finalise();
+        assert_eq!(
+            res,
+            [
+                0xde, 0xbe, 0x9f, 0xf9, 0x22, 0x75, 0xb8, 0xa1, 0x38, 0x60, 0x48, 0x89, 0xc1, 0x8e,
+                0x5a, 0x4d, 0x6f, 0xdb, 0x70, 0xe5, 0x38, 0x7e, 0x57, 0x65, 0x29, 0x3d, 0xcb, 0xa3,
+                0x9c, 0x0c, 0x57, 0x32
+            ]
+        );
+    }
+
+    #[test]
+    fn test_big_splitted() {
+        let mut sm3 = SM3::new(&());
+
+        sm3.update("1234567".as_bytes());
+        sm3.update("890123456789012345678".as_bytes());
+        sm3.update("901234567890123456789012345678901234567890123456".as_bytes());
+        sm3.update("7890".as_bytes());
+
+        let res = sm3.finalise();
+        assert_eq!(
+            res,
+            [
+                0xad, 0x81, 0x80, 0x53, 0x21, 0xf3, 0xe6, 0x9d, 0x25, 0x12, 0x35, 0xbf, 0x88, 0x6a,
+                0x56, 0x48, 0x44, 0x87, 0x3b, 0x56, 0xdd, 0x7d, 0xde, 0x40, 0x0f, 0x05, 0x5b, 0x7d,
+                0xde, 0x39, 0x30, 0x7a
+            ]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! Definition of Streebog (GOST R 34.11-2012, RFC 6986), the hash function of the Russian standard
+//!
+//! Streebog processes 512-bit blocks m with a compression function built on a 12-round block cipher E:
+//!```text
+//!   g(N, h, m) = E(LPS(h xor N), m) xor h xor m
+//!   LPS        = L(P(S(x)))      byte substitution pi, byte transposition, multiplication by a matrix A
+//!
+//!   for each block m:  h = g(N, h, m),  N = N + 512,  Sigma = Sigma + m
+//!   h = g(0, h, N),  h = g(0, h, Sigma)
+//! ```
+//! The last block is padded with a single 0x01 byte then zeroes, and N only counts its real length.
+//! Streebog-256 uses another initial value, and keeps half of the final state.
+//!
+//! Bytes are in the order of RFC 6986 test vectors reversed, i.e. in the order of the other hash
+//! implementations.
+
+use crate::hash::common::{generic_update_func, Hash};
+use crate::symmetric::block_ciphers::kuznyechik::PI;
+
+// rows of the matrix of the linear transformation L, A[0] corresponds to the most significant bit of a word
+const A: [u64; 64] = [
+    0x8E20FAA72BA0B470, 0x47107DDD9B505A38, 0xAD08B0E0C3282D1C, 0xD8045870EF14980E,
+    0x6C022C38F90A4C07, 0x3601161CF205268D, 0x1B8E0B0E798C13C8, 0x83478B07B2468764,
+    0xA011D380818E8F40, 0x5086E740CE47C920, 0x2843FD2067ADEA10, 0x14AFF010BDD87508,
+    0x0AD97808D06CB404, 0x05E23C0468365A02, 0x8C711E02341B2D01, 0x46B60F011A83988E,
+    0x90DAB52A387AE76F, 0x486DD4151C3DFDB9, 0x24B86A840E90F0D2, 0x125C354207487869,
+    0x092E94218D243CBA, 0x8A174A9EC8121E5D, 0x4585254F64090FA0, 0xACCC9CA9328A8950,
+    0x9D4DF05D5F661451, 0xC0A878A0A1330AA6, 0x60543C50DE970553, 0x302A1E286FC58CA7,
+    0x18150F14B9EC46DD, 0x0C84890AD27623E0, 0x0642CA05693B9F70, 0x0321658CBA93C138,
+    0x86275DF09CE8AAA8, 0x439DA0784E745554, 0xAFC0503C273AA42A, 0xD960281E9D1D5215,
+    0xE230140FC0802984, 0x71180A8960409A42, 0xB60C05CA30204D21, 0x5B068C651810A89E,
+    0x456C34887A3805B9, 0xAC361A443D1C8CD2, 0x561B0D22900E4669, 0x2B838811480723BA,
+    0x9BCF4486248D9F5D, 0xC3E9224312C8C1A0, 0xEFFA11AF0964EE50, 0xF97D86D98A327728,
+    0xE4FA2054A80B329C, 0x727D102A548B194E, 0x39B008152ACB8227, 0x9258048415EB419D,
+    0x492C024284FBAEC0, 0xAA16012142F35760, 0x550B8E9E21F7A530, 0xA48B474F9EF5DC18,
+    0x70A6A56E2440598E, 0x3853DC371220A247, 0x1CA76E95091051AD, 0x0EDD37C48A08A6D8,
+    0x07E095624504536C, 0x8D70C431AC02A736, 0xC83862965601DD1B, 0x641C314B2B8EE083,
+];
+
+// round constants of the key schedule of E, the first word being the least significant one
+const C: [[u64; 8]; 12] = [
+    [
+        0xDD806559F2A64507,
+        0x05767436CC744D23,
+        0xA2422A08A460D315,
+        0x4B7CE09192676901,
+        0x714EB88D7585C4FC,
+        0x2F6A76432E45D016,
+        0xEBCB2F81C0657C1F,
+        0xB1085BDA1ECADAE9,
+    ],
+    [
+        0xE679047021B19BB7,
+        0x55DDA21BD7CBCD56,
+        0x5CB561C2DB0AA7CA,
+        0x9AB5176B12D69958,
+        0x61D55E0F16B50131,
+        0xF3FEEA720A232B98,
+        0x4FE39D460F70B5D7,
+        0x6FA3B58AA99D2F1A,
+    ],
+    [
+        0x991E96F50ABA0AB2,
+        0xC2B6F443867ADB31,
+        0xC1C93A376062DB09,
+        0xD3E20FE490359EB1,
+        0xF2EA7514B1297B7B,
+        0x06F15E5F529C1F8B,
+        0x0A39FC286A3D8435,
+        0xF574DCAC2BCE2FC7,
+    ],
+    [
+        0x220CBEBC84E3D12E,
+        0x3453EAA193E837F1,
+        0xD8B71333935203BE,
+        0xA9D72C82ED03D675,
+        0x9D721CAD685E353F,
+        0x488E857E335C3C7D,
+        0xF948E1A05D71E4DD,
+        0xEF1FDFB3E81566D2,
+    ],
+    [
+        0x601758FD7C6CFE57,
+        0x7A56A27EA9EA63F5,
+        0xDFFF00B723271A16,
+        0xBFCD1747253AF5A3,
+        0x359E35D7800FFFBD,
+        0x7F151C1F1686104A,
+        0x9A3F410C6CA92363,
+        0x4BEA6BACAD474799,
+    ],
+    [
+        0xFA68407A46647D6E,
+        0xBF71C57236904F35,
+        0x0AF21F66C2BEC6B6,
+        0xCFFAA6B71C9AB7B4,
+        0x187F9AB49AF08EC6,
+        0x2D66C4F95142A46C,
+        0x6FA4C33B7A3039C0,
+        0xAE4FAEAE1D3AD3D9,
+    ],
+    [
+        0x8886564D3A14D493,
+        0x3517454CA23C4AF3,
+        0x06476983284A0504,
+        0x0992ABC52D822C37,
+        0xD3473E33197A93C9,
+        0x399EC6C7E6BF87C9,
+        0x51AC86FEBF240954,
+        0xF4C70E16EEAAC5EC,
+    ],
+    [
+        0xA47F0DD4BF02E71E,
+        0x36ACC2355951A8D9,
+        0x69D18D2BD1A5C42F,
+        0xF4892BCB929B0690,
+        0x89B4443B4DDBC49A,
+        0x4EB7F8719C36DE1E,
+        0x03E7AA020C6E4141,
+        0x9B1F5B424D93C9A7,
+    ],
+    [
+        0x7261445183235ADB,
+        0x0E38DC92CB1F2A60,
+        0x7B2B8A9AA6079C54,
+        0x800A440BDBB2CEB1,
+        0x3CD955B7E00D0984,
+        0x3A7D3A1B25894224,
+        0x944C9AD8EC165FDE,
+        0x378F5A541631229B,
+    ],
+    [
+        0x74B4C7FB98459CED,
+        0x3698FAD1153BB6C3,
+        0x7A1E6C303B7652F4,
+        0x9FE76702AF69334B,
+        0x1FFFE18A1B336103,
+        0x8941E71CFF8A78DB,
+        0x382AE548B2E4F3F3,
+        0xABBEDEA680056F52,
+    ],
+    [
+        0x6BCAA4CD81F32D1B,
+        0xDEA2594AC06FD85D,
+        0xEFBACD1D7D476E98,
+        0x8A1D71EFEA48B9CA,
+        0x2001802114846679,
+        0xD8FA6BBBEBAB0761,
+        0x3002C6CD635AFE94,
+        0x7BCD9ED0EFC889FB,
+    ],
+    [
+        0x48BC924AF11BD720,
+        0xFAF417D5D9B21B99,
+        0xE71DA4AA88E12852,
+        0x5D80EF9D1891CC86,
+        0xF82012D430219F9B,
+        0xCDA43C32BCDF1D77,
+        0xD21380B00449B17A,
+        0x378EE767F11631BA,
+    ],
+];
+
+type State = [u64; 8];
+
+pub struct StreebogContext {
+    h: State,
+    n: State,
+    sigma: State,
+}
+
+macro_rules! define_streebog {
+    ( $streebog_name:ident, $digest_size:literal, $iv:expr ) => {
+        pub struct $streebog_name {
+            context: StreebogContext,
+            remaining_bytes: [u8; 63],
+            remaining_bytes_len: usize,
+        }
+
+        impl Hash<$digest_size> for $streebog_name {
+            const BLOCK_SIZE: usize = 64;
+
+            type InitStruct = ();
+            type Context = StreebogContext;
+
+            fn new(_: &Self::InitStruct) -> Self {
+                $streebog_name {
+                    context: StreebogContext { h: [$iv; 8], n: [0; 8], sigma: [0; 8] },
+                    remaining_bytes: [0; 63],
+                    remaining_bytes_len: 0,
+                }
+            }
+
+            generic_update_func!(process_block);
+
+            fn finalise(&mut self) -> [u8; $digest_size] {
+                let mut cur_block = [0; 64];
+                cur_block[..self.remaining_bytes_len].copy_from_slice(&self.remaining_bytes[..self.remaining_bytes_len]);
+                cur_block[self.remaining_bytes_len] = 0x01;
+                let state = finalise_state(&mut self.context, &cur_block, self.remaining_bytes_len);
+
+                // keep the most significant bytes
+                let bytes: Vec<u8> = state.iter().flat_map(|w| w.to_le_bytes()).collect();
+                bytes[64 - $digest_size..].try_into().unwrap()
+            }
+        }
+    };
+}
+
+define_streebog!(Streebog256, 32, 0x0101010101010101);
+define_streebog!(Streebog512, 64, 0);
+
+fn to_state(block: &[u8; 64]) -> State {
+    core::array::from_fn(|i| u64::from_le_bytes(block[i * 8..i * 8 + 8].try_into().unwrap()))
+}
+
+fn xor(a: &State, b: &State) -> State {
+    core::array::from_fn(|i| a[i] ^ b[i])
+}
+
+// addition modulo 2^512
+fn add(a: &mut State, b: &State) {
+    let mut carry = false;
+    for (x, y) in a.iter_mut().zip(b) {
+        let (s1, c1) = x.overflowing_add(*y);
+        let (s2, c2) = s1.overflowing_add(carry as u64);
+        *x = s2;
+        carry = c1 || c2;
+    }
+}
+
+fn lps(a: &State) -> State {
+    let bytes: Vec<u8> = a.iter().flat_map(|w| w.to_le_bytes()).collect();
+    core::array::from_fn(|i| {
+        // after the transposition, the byte j of the word i is the byte i of the word j
+        let w = (0..8).fold(0, |acc, j| acc | (PI[bytes[8 * j + i] as usize] as u64) << (8 * j));
+        (0..64).filter(|k| w >> k & 1 == 1).fold(0, |acc, k| acc ^ A[63 - k])
+    })
+}
+
+fn e(mut k: State, m: &State) -> State {
+    let mut state = xor(m, &k);
+    for c in &C {
+        k = lps(&xor(&k, c));
+        state = xor(&lps(&state), &k);
+    }
+    state
+}
+
+fn g(n: &State, h: &State, m: &State) -> State {
+    xor(&xor(&e(lps(&xor(h, n)), m), h), m)
+}
+
+fn process_block(context: &mut StreebogContext, block: &[u8; 64]) {
+    let m = to_state(block);
+    context.h = g(&context.n, &context.h, &m);
+    add(&mut context.n, &[512, 0, 0, 0, 0, 0, 0, 0]);
+    add(&mut context.sigma, &m);
+}
+
+// processes the padded last block, which contains `length` bytes of message
+fn finalise_state(context: &mut StreebogContext, block: &[u8; 64], length: usize) -> State {
+    let m = to_state(block);
+    context.h = g(&context.n, &context.h, &m);
+    add(&mut context.n, &[length as u64 * 8, 0, 0, 0, 0, 0, 0, 0]);
+    add(&mut context.sigma, &m);
+
+    context.h = g(&[0; 8], &context.h, &context.n);
+    g(&[0; 8], &context.h, &context.sigma)
+}
+
+#[cfg(test)]
+mod tests_streebog {
+    use super::*;
+
+    const M1: &[u8] = b"012345678901234567890123456789012345678901234567890123456789012";
+
+    // RFC 6986, section 10.2 (the message is given in reverse order)
+    const M2: [u8; 72] = [
+        0xd1, 0xe5, 0x20, 0xe2, 0xe5, 0xf2, 0xf0, 0xe8, 0x2c, 0x20, 0xd1, 0xf2, 0xf0, 0xe8,
+        0xe1, 0xee, 0xe6, 0xe8, 0x20, 0xe2, 0xed, 0xf3, 0xf6, 0xe8, 0x2c, 0x20, 0xe2, 0xe5,
+        0xfe, 0xf2, 0xfa, 0x20, 0xf1, 0x20, 0xec, 0xee, 0xf0, 0xff, 0x20, 0xf1, 0xf2, 0xf0,
+        0xe5, 0xeb, 0xe0, 0xec, 0xe8, 0x20, 0xed, 0xe0, 0x20, 0xf5, 0xf0, 0xe0, 0xe1, 0xf0,
+        0xfb, 0xff, 0x20, 0xef, 0xeb, 0xfa, 0xea, 0xfb, 0x20, 0xc8, 0xe3, 0xee, 0xf0, 0xe5,
+        0xe2, 0xfb,
+    ];
+
+    #[test]
+    fn test_streebog512() {
+        // RFC 6986, section 10.1.1
+        let mut streebog = Streebog512::new(&());
+        streebog.update(M1);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x1b, 0x54, 0xd0, 0x1a, 0x4a, 0xf5, 0xb9, 0xd5, 0xcc, 0x3d, 0x86, 0xd6, 0x8d, 0x28,
+                0x54, 0x62, 0xb1, 0x9a, 0xbc, 0x24, 0x75, 0x22, 0x2f, 0x35, 0xc0, 0x85, 0x12, 0x2b,
+                0xe4, 0xba, 0x1f, 0xfa, 0x00, 0xad, 0x30, 0xf8, 0x76, 0x7b, 0x3a, 0x82, 0x38, 0x4c,
+                0x65, 0x74, 0xf0, 0x24, 0xc3, 0x11, 0xe2, 0xa4, 0x81, 0x33, 0x2b, 0x08, 0xef, 0x7f,
+                0x41, 0x79, 0x78, 0x91, 0xc1, 0x64, 0x6f, 0x48,
+            ]
+        );
+
+        // RFC 6986, section 10.2.1
+        let mut streebog = Streebog512::new(&());
+        streebog.update(&M2);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x1e, 0x88, 0xe6, 0x22, 0x26, 0xbf, 0xca, 0x6f, 0x99, 0x94, 0xf1, 0xf2, 0xd5, 0x15,
+                0x69, 0xe0, 0xda, 0xf8, 0x47, 0x5a, 0x3b, 0x0f, 0xe6, 0x1a, 0x53, 0x00, 0xee, 0xe4,
+                0x6d, 0x96, 0x13, 0x76, 0x03, 0x5f, 0xe8, 0x35, 0x49, 0xad, 0xa2, 0xb8, 0x62, 0x0f,
+                0xcd, 0x7c, 0x49, 0x6c, 0xe5, 0xb3, 0x3f, 0x0c, 0xb9, 0xdd, 0xdc, 0x2b, 0x64, 0x60,
+                0x14, 0x3b, 0x03, 0xda, 0xba, 0xc9, 0xfb, 0x28,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streebog256() {
+        // RFC 6986, section 10.1.2
+        let mut streebog = Streebog256::new(&());
+        streebog.update(M1);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x9d, 0x15, 0x1e, 0xef, 0xd8, 0x59, 0x0b, 0x89, 0xda, 0xa6, 0xba, 0x6c, 0xb7, 0x4a,
+                0xf9, 0x27, 0x5d, 0xd0, 0x51, 0x02, 0x6b, 0xb1, 0x49, 0xa4, 0x52, 0xfd, 0x84, 0xe5,
+                0xe5, 0x7b, 0x55, 0x00,
+            ]
+        );
+
+        // RFC 6986, section 10.2.2
+        let mut streebog = Streebog256::new(&());
+        streebog.update(&M2);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x9d, 0xd2, 0xfe, 0x4e, 0x90, 0x40, 0x9e, 0x5d, 0xa8, 0x7f, 0x53, 0x97, 0x6d, 0x74,
+                0x05, 0xb0, 0xc0, 0xca, 0xc6, 0x28, 0xfc, 0x66, 0x9a, 0x74, 0x1d, 0x50, 0x06, 0x3c,
+                0x55, 0x7e, 0x8f, 0x50,
+            ]
+        );
+    }
+
+    // checked with Nettle
+    #[test]
+    fn test_empty() {
+        let mut streebog = Streebog512::new(&());
+        streebog.update(&[]);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x8e, 0x94, 0x5d, 0xa2, 0x09, 0xaa, 0x86, 0x9f, 0x04, 0x55, 0x92, 0x85, 0x29, 0xbc,
+                0xae, 0x46, 0x79, 0xe9, 0x87, 0x3a, 0xb7, 0x07, 0xb5, 0x53, 0x15, 0xf5, 0x6c, 0xeb,
+                0x98, 0xbe, 0xf0, 0xa7, 0x36, 0x2f, 0x71, 0x55, 0x28, 0x35, 0x6e, 0xe8, 0x3c, 0xda,
+                0x5f, 0x2a, 0xac, 0x4c, 0x6a, 0xd2, 0xba, 0x3a, 0x71, 0x5c, 0x1b, 0xcd, 0x81, 0xcb,
+                0x8e, 0x9f, 0x90, 0xbf, 0x4c, 0x1c, 0x1a, 0x8a,
+            ]
+        );
+
+        let mut streebog = Streebog256::new(&());
+        streebog.update(&[]);
+        assert_eq!(
+            streebog.finalise(),
+            [
+                0x3f, 0x53, 0x9a, 0x21, 0x3e, 0x97, 0xc8, 0x02, 0xcc, 0x22, 0x9d, 0x47, 0x4c, 0x6a,
+                0xa3, 0x2a, 0x82, 0x5a, 0x36, 0x0b, 0x2a, 0x93, 0x3a, 0x94, 0x9f, 0xd9, 0x25, 0x20,
+                0x8d, 0x9c, 0xe1, 0xbb,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_big_splitted() {
+        let mut streebog512 = Streebog512::new(&());
+        let mut streebog256 = Streebog256::new(&());
+        for data in ["1234567", "890123456789012345678", "901234567890123456789012345678901234567890123456", "7890"] {
+            streebog512.update(data.as_bytes());
+            streebog256.update(data.as_bytes());
+        }
+
+        assert_eq!(
+            streebog512.finalise(),
+            [
+                0x11, 0x62, 0x01, 0x02, 0x3e, 0x88, 0xd9, 0x3a, 0x4d, 0x07, 0x6b, 0xa7, 0x72, 0x07,
+                0xe8, 0x70, 0x2c, 0x6c, 0xfa, 0x6f, 0xcc, 0x69, 0xb8, 0x2b, 0xb2, 0x2a, 0xe6, 0xbe,
+                0x9b, 0x63, 0xf1, 0x6b, 0x19, 0xba, 0xaf, 0x87, 0x71, 0xe0, 0x1e, 0x6d, 0xc2, 0x5c,
+                0x2b, 0x44, 0x86, 0xfa, 0x3b, 0xbf, 0x86, 0x01, 0x90, 0x57, 0x62, 0xcb, 0xba, 0xd5,
+                0xba, 0x25, 0xa1, 0xe0, 0x34, 0x87, 0x91, 0x92,
+            ]
+        );
+        assert_eq!(
+            streebog256.finalise(),
+            [
+                0x2c, 0x66, 0x44, 0xb1, 0xf5, 0xab, 0x3e, 0x0a, 0xb5, 0x6a, 0xdf, 0x1f, 0xee, 0xb4,
+                0xd6, 0xa8, 0x74, 0x2f, 0xcf, 0xc6, 0x1b, 0x53, 0xb6, 0x9c, 0x3b, 0x53, 0x6a, 0xc2,
+                0x83, 0xab, 0x88, 0xaa,
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! cf. NIST SP 800-38B and RFC 4493
+//!
+//!```text
+//!   L  = E_K(0^b)
+//!   K1 = dbl(L), K2 = dbl(K1)
+//!
+//!   M1       M2            Mn xor K1    (full last block)
+//!   |        |             Mn || 10* xor K2    (partial last block)
+//!   |    ----+         ----+
+//!   |   |    |        |    |
+//!  ---  |   ---  ...  |   ---
+//! | K | |  | K |      |  | K |
+//!  ---  |   ---       |   ---
+//!   |---     |--------     |
+//!                          T
+//! ```
+
+use crate::mac::common::MAC;
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::utils::xor_arrays;
+
+/// Multiplication by x in GF(2^b), for 64 and 128-bit blocks (big endian representation).
+pub(crate) fn dbl<const NB: usize>(block: &[u8; NB]) -> [u8; NB] {
+    let r = if NB == 8 { 0x1B } else { 0x87 };
+    let mut res = [0; NB];
+    for i in 0..NB - 1 {
+        res[i] = (block[i] << 1) | (block[i + 1] >> 7);
+    }
+    res[NB - 1] = (block[NB - 1] << 1) ^ (r * (block[0] >> 7));
+    res
+}
+
+/// CMAC over the block cipher `T` (e.g. AES-CMAC with `CMAC<AES128>`). The MAC is a full block.
+pub struct CMAC<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize, const NB: usize> CMAC<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    /// Computes the CMAC of the concatenation of `parts`, without copying them.
+    pub(crate) fn compute_parts(parts: &[&[u8]], key: &[u8; NK]) -> [u8; NB] {
+        // subkeys
+        let mut l = [0; NB];
+        T::cipher(&[0; NB], &mut l, key).unwrap();
+        let k1 = dbl(&l);
+        let k2 = dbl(&k1);
+
+        // all the blocks but the last one are chained directly
+        let len: usize = parts.iter().map(|part| part.len()).sum();
+        let n = len.div_ceil(NB).max(1);
+        let mut mac = [0; NB];
+        let mut block = [0; NB];
+        let mut last = [0; NB];
+        for (i, byte) in parts.iter().flat_map(|part| part.iter()).enumerate() {
+            if i < (n - 1) * NB {
+                block[i % NB] = mac[i % NB] ^ byte;
+                if i % NB == NB - 1 {
+                    T::cipher(&block, &mut mac, key).unwrap();
+                }
+            } else {
+                last[i - (n - 1) * NB] = *byte;
+            }
+        }
+
+        // last block
+        let last_len = len - (n - 1) * NB;
+        let last = if last_len == NB {
+            xor_arrays(&last, &k1)
+        } else {
+            last[last_len] = 0x80;
+            xor_arrays(&last, &k2)
+        };
+        let input = xor_arrays(&mac, &last);
+        T::cipher(&input, &mut mac, key).unwrap();
+        mac
+    }
+}
+
+impl<T, const NK: usize, const NB: usize> MAC<NB> for CMAC<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    type KeyType = [u8; NK];
+
+    fn compute(data: &[u8], key: Self::KeyType) -> [u8; NB] {
+        Self::compute_parts(&[data], &key)
+    }
+}
+
+#[cfg(test)]
+mod tests_cmac {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES256};
+    use super::*;
+
+    const KEY: [u8; 16] = [0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+    const MESSAGE: [u8; 64] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF,
+        0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10,
+    ];
+
+    #[test]
+    fn test_cmac_aes128() {
+        // RFC 4493, 4 (examples 1 to 4)
+        assert_eq!(CMAC::<AES128>::compute(&[], KEY), [0xBB, 0x1D, 0x69, 0x29, 0xE9, 0x59, 0x37, 0x28, 0x7F, 0xA3, 0x7D, 0x12, 0x9B, 0x75, 0x67, 0x46]);
+        assert_eq!(CMAC::<AES128>::compute(&MESSAGE[..16], KEY), [0x07, 0x0A, 0x16, 0xB4, 0x6B, 0x4D, 0x41, 0x44, 0xF7, 0x9B, 0xDD, 0x9D, 0xD0, 0x4A, 0x28, 0x7C]);
+        assert_eq!(CMAC::<AES128>::compute(&MESSAGE[..40], KEY), [0xDF, 0xA6, 0x67, 0x47, 0xDE, 0x9A, 0xE6, 0x30, 0x30, 0xCA, 0x32, 0x61, 0x14, 0x97, 0xC8, 0x27]);
+        assert_eq!(CMAC::<AES128>::compute(&MESSAGE, KEY), [0x51, 0xF0, 0xBE, 0xBF, 0x7E, 0x3B, 0x9D, 0x92, 0xFC, 0x49, 0x74, 0x17, 0x79, 0x36, 0x3C, 0xFE]);
+    }
+
+    #[test]
+    fn test_cmac_parts() {
+        let parts: [&[u8]; 4] = [&MESSAGE[..5], &[], &MESSAGE[5..32], &MESSAGE[32..40]];
+        assert_eq!(CMAC::<AES128>::compute_parts(&parts, &KEY), CMAC::<AES128>::compute(&MESSAGE[..40], KEY));
+        assert_eq!(CMAC::<AES128>::compute_parts(&[&MESSAGE[..16], &MESSAGE[16..]], &KEY), CMAC::<AES128>::compute(&MESSAGE, KEY));
+    }
+
+    #[test]
+    fn test_cmac_aes256() {
+        // SP 800-38B, D.3 (example 12)
+        let key = [
+            0x60, 0x3D, 0xEB, 0x10, 0x15, 0xCA, 0x71, 0xBE, 0x2B, 0x73, 0xAE, 0xF0, 0x85, 0x7D, 0x77, 0x81,
+            0x1F, 0x35, 0x2C, 0x07, 0x3B, 0x61, 0x08, 0xD7, 0x2D, 0x98, 0x10, 0xA3, 0x09, 0x14, 0xDF, 0xF4,
+        ];
+        assert_eq!(CMAC::<AES256>::compute(&MESSAGE, key), [0xE1, 0x99, 0x21, 0x90, 0x54, 0x9F, 0x6E, 0xD5, 0x69, 0x6A, 0x2C, 0x05, 0x6C, 0x31, 0x54, 0x10]);
+    }
+}
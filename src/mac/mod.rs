@@ -2,6 +2,10 @@
 //!
 //! Currently, these MAC algorithms are implemented:
 //! - HMAC (see [crate::hash] for implemented hashes)
+//! - CMAC (see [crate::symmetric::block_ciphers] for implemented block ciphers)
+//! - Poly1305
 
 pub mod common;
-pub mod hmac;
\ No newline at end of file
+pub mod cmac;
+pub mod hmac;
+pub mod poly1305;
\ No newline at end of file
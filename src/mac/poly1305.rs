@@ -0,0 +1,202 @@
+//! cf. RFC 8439, section 2.5
+//!
+//! Poly1305 is a one-time authenticator: a key must never be used for more than one message.
+//! The computation uses 26-bit limbs, without branches nor memory accesses that depend on secret data.
+
+use crate::mac::common::MAC;
+
+const MASK_26: u32 = 0x3FFFFFF;
+
+fn le32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+/// Streaming Poly1305: data can be given in several calls to `update`.
+pub struct Poly1305 {
+    r: [u32; 5],
+    s: [u32; 4],
+    h: [u32; 5],
+    buffer: [u8; 16],
+    buffer_len: usize
+}
+
+impl Poly1305 {
+    /// `key` is `r || s`. `r` is clamped.
+    pub fn new(key: &[u8; 32]) -> Self {
+        let r = [
+            le32(&key[0..]) & 0x3FFFFFF,
+            (le32(&key[3..]) >> 2) & 0x3FFFF03,
+            (le32(&key[6..]) >> 4) & 0x3FFC0FF,
+            (le32(&key[9..]) >> 6) & 0x3F03FFF,
+            (le32(&key[12..]) >> 8) & 0x00FFFFF,
+        ];
+        let s = [le32(&key[16..]), le32(&key[20..]), le32(&key[24..]), le32(&key[28..])];
+
+        Poly1305 { r, s, h: [0; 5], buffer: [0; 16], buffer_len: 0 }
+    }
+
+    // h = (h + block) * r mod 2^130 - 5
+    // `hibit` is the 2^128 bit, added to full blocks (partial blocks are padded by `finalise`)
+    fn process_block(&mut self, block: &[u8; 16], hibit: u32) {
+        let [r0, r1, r2, r3, r4] = self.r.map(|x| x as u64);
+        let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+
+        let h = &mut self.h;
+        h[0] += le32(&block[0..]) & MASK_26;
+        h[1] += (le32(&block[3..]) >> 2) & MASK_26;
+        h[2] += (le32(&block[6..]) >> 4) & MASK_26;
+        h[3] += (le32(&block[9..]) >> 6) & MASK_26;
+        h[4] += (le32(&block[12..]) >> 8) | hibit;
+        let [h0, h1, h2, h3, h4] = h.map(|x| x as u64);
+
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        // partial carry propagation
+        d1 += d0 >> 26;
+        d2 += d1 >> 26;
+        d3 += d2 >> 26;
+        d4 += d3 >> 26;
+        let c = (d4 >> 26) as u32;
+        h[0] = (d0 as u32) & MASK_26;
+        h[1] = (d1 as u32) & MASK_26;
+        h[2] = (d2 as u32) & MASK_26;
+        h[3] = (d3 as u32) & MASK_26;
+        h[4] = (d4 as u32) & MASK_26;
+        h[0] += c * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= MASK_26;
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for byte in data {
+            self.buffer[self.buffer_len] = *byte;
+            self.buffer_len += 1;
+            if self.buffer_len == 16 {
+                let block = self.buffer;
+                self.process_block(&block, 1 << 24);
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    pub fn finalise(mut self) -> [u8; 16] {
+        // last partial block: a 1 byte is appended, then zeros
+        if self.buffer_len > 0 {
+            let mut block = [0; 16];
+            block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+            block[self.buffer_len] = 1;
+            self.process_block(&block, 0);
+        }
+
+        // full carry propagation
+        let mut h = self.h;
+        for i in 1..5 {
+            h[i] += h[i - 1] >> 26;
+            h[i - 1] &= MASK_26;
+        }
+        h[0] += (h[4] >> 26) * 5;
+        h[4] &= MASK_26;
+        h[1] += h[0] >> 26;
+        h[0] &= MASK_26;
+
+        // g = h - p = h + 5 - 2^130, kept if it is not negative
+        let mut g = [0; 5];
+        let mut carry = 5;
+        for i in 0..5 {
+            g[i] = h[i] + carry;
+            carry = g[i] >> 26;
+            g[i] &= MASK_26;
+        }
+        g[4] = g[4].wrapping_add(carry << 26).wrapping_sub(1 << 26);
+        let mask = (g[4] >> 31).wrapping_sub(1);
+        for i in 0..5 {
+            h[i] = (h[i] & !mask) | (g[i] & mask);
+        }
+
+        // h = (h + s) mod 2^128
+        let words = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8),
+        ];
+        let mut tag = [0; 16];
+        let mut f = 0u64;
+        for i in 0..4 {
+            f = words[i] as u64 + self.s[i] as u64 + (f >> 32);
+            tag[4 * i..4 * i + 4].copy_from_slice(&(f as u32).to_le_bytes());
+        }
+        tag
+    }
+}
+
+impl MAC<16> for Poly1305 {
+    type KeyType = [u8; 32];
+
+    fn compute(data: &[u8], key: Self::KeyType) -> [u8; 16] {
+        let mut poly = Poly1305::new(&key);
+        poly.update(data);
+        poly.finalise()
+    }
+}
+
+#[cfg(test)]
+mod tests_poly1305 {
+    use super::*;
+
+    #[test]
+    fn test_poly1305_rfc8439() {
+        // RFC 8439, 2.5.2
+        let key = [
+            0x85, 0xD6, 0xBE, 0x78, 0x57, 0x55, 0x6D, 0x33, 0x7F, 0x44, 0x52, 0xFE, 0x42, 0xD5, 0x06, 0xA8,
+            0x01, 0x03, 0x80, 0x8A, 0xFB, 0x0D, 0xB2, 0xFD, 0x4A, 0xBF, 0xF6, 0xAF, 0x41, 0x49, 0xF5, 0x1B,
+        ];
+        let expected = [0xA8, 0x06, 0x1D, 0xC1, 0x30, 0x51, 0x36, 0xC6, 0xC2, 0x2B, 0x8B, 0xAF, 0x0C, 0x01, 0x27, 0xA9];
+
+        let res = Poly1305::compute(b"Cryptographic Forum Research Group", key);
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn test_poly1305_streaming() {
+        let key = [
+            0x85, 0xD6, 0xBE, 0x78, 0x57, 0x55, 0x6D, 0x33, 0x7F, 0x44, 0x52, 0xFE, 0x42, 0xD5, 0x06, 0xA8,
+            0x01, 0x03, 0x80, 0x8A, 0xFB, 0x0D, 0xB2, 0xFD, 0x4A, 0xBF, 0xF6, 0xAF, 0x41, 0x49, 0xF5, 0x1B,
+        ];
+        let expected = [0xA8, 0x06, 0x1D, 0xC1, 0x30, 0x51, 0x36, 0xC6, 0xC2, 0x2B, 0x8B, 0xAF, 0x0C, 0x01, 0x27, 0xA9];
+
+        let mut poly = Poly1305::new(&key);
+        poly.update(b"Cryptographic ");
+        poly.update(b"Forum Research");
+        poly.update(b" Group");
+        assert_eq!(poly.finalise(), expected);
+    }
+
+    #[test]
+    fn test_poly1305_reduction() {
+        // RFC 8439, A.3, test vectors 5, 6 and 9 (h reaches p or wraps)
+        let mut key = [0; 32];
+        key[0] = 2;
+        let res = Poly1305::compute(&[0xFF; 16], key);
+        assert_eq!(res, [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        key[16..].copy_from_slice(&[0xFF; 16]);
+        let mut data = [0; 16];
+        data[0] = 2;
+        let res = Poly1305::compute(&data, key);
+        assert_eq!(res, [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let mut key = [0; 32];
+        key[0] = 1;
+        let mut data = [0xFF; 48];
+        data[16] = 0xF0;
+        data[32] = 0x11;
+        data[33..].fill(0);
+        let res = Poly1305::compute(&data, key);
+        assert_eq!(res, [0x05, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,223 @@
+//! AEAD algorithms based on AES: AES-GCM, AES-CCM, AES-SIV, AES-GCM-SIV, AES-OCB3 and
+//! AES-EAX.
+
+use crate::symmetric::aead::common::{define_aead, Aead, AeadError};
+use crate::symmetric::block_ciphers::aes::{AES128, AES192, AES256};
+use crate::symmetric::block_ciphers_modes::ccm::CCM;
+use crate::symmetric::block_ciphers_modes::eax::EAX;
+use crate::symmetric::block_ciphers_modes::gcm::GCM;
+use crate::symmetric::block_ciphers_modes::gcm_siv::GCMSIV;
+use crate::symmetric::block_ciphers_modes::ocb::OCB;
+use crate::symmetric::block_ciphers_modes::siv::SIV;
+
+/// Defines AES-SIV. Unlike the other AEADs:
+/// - the nonce can have any length (16 bytes is recommended)
+/// - `seal` returns `V || C`, the synthetic IV `V` being the tag (cf. RFC 5297, 6)
+macro_rules! define_aes_siv {
+    ( $aead_name:ident, $cipher:ty, $key_length:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// The nonce can have any length, and `seal` puts the tag (synthetic IV) before the ciphertext.
+        pub struct $aead_name {}
+
+        impl Aead for $aead_name {
+            const KEY_SIZE: usize = $key_length;
+            const NONCE_SIZE: usize = 16;
+            const TAG_SIZE: usize = 16;
+
+            type KeyType = [u8; $key_length];
+            type NonceType = [u8];
+            type TagType = [u8; 16];
+
+            fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError> {
+                let mut tag = [0; 16];
+                SIV::<$cipher>::encrypt_in_place(key, nonce, aad, buffer, &mut tag)?;
+                Ok(tag)
+            }
+
+            fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError> {
+                SIV::<$cipher>::decrypt_in_place(key, nonce, aad, buffer, tag)
+            }
+
+            /// Encrypts `buffer` and inserts the tag before it.
+            fn seal_in_place(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), AeadError> {
+                let tag = Self::seal_in_place_detached(key, nonce, aad, buffer)?;
+                buffer.splice(0..0, tag);
+                Ok(())
+            }
+
+            /// Checks the tag at the beginning of `buffer`, then replaces `buffer` with the plaintext.
+            fn open_in_place(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), AeadError> {
+                if buffer.len() < Self::TAG_SIZE {
+                    return Err(AeadError::BadLength);
+                }
+                let tag = buffer[..Self::TAG_SIZE].try_into().unwrap();
+
+                Self::open_in_place_detached(key, nonce, aad, &mut buffer[Self::TAG_SIZE..], &tag)?;
+                buffer.drain(..Self::TAG_SIZE);
+                Ok(())
+            }
+        }
+    };
+}
+
+define_aead!(AES128GCM, GCM<AES128>, 16, 12, 16, "AES-128-GCM (cf. RFC 5116).");
+define_aead!(AES192GCM, GCM<AES192>, 24, 12, 16, "AES-192-GCM.");
+define_aead!(AES256GCM, GCM<AES256>, 32, 12, 16, "AES-256-GCM (cf. RFC 5116).");
+define_aead!(AES128CCM, CCM<AES128>, 16, 12, 16, "AES-128-CCM with a 12-byte nonce (cf. RFC 6655).");
+define_aead!(AES256CCM, CCM<AES256>, 32, 12, 16, "AES-256-CCM with a 12-byte nonce (cf. RFC 6655).");
+define_aead!(AES128CCM8, CCM<AES128>, 16, 12, 8, "AES-128-CCM with a 12-byte nonce and an 8-byte tag (cf. RFC 6655).");
+define_aes_siv!(AES128SIV, AES128, 32, "AES-SIV with a 256-bit key (cf. RFC 5297), the components being `[aad, nonce]`.");
+define_aes_siv!(AES256SIV, AES256, 64, "AES-SIV with a 512-bit key (cf. RFC 5297), the components being `[aad, nonce]`.");
+define_aead!(AES128GCMSIV, GCMSIV<AES128>, 16, 12, 16, "AES-128-GCM-SIV (cf. RFC 8452).");
+define_aead!(AES256GCMSIV, GCMSIV<AES256>, 32, 12, 16, "AES-256-GCM-SIV (cf. RFC 8452).");
+define_aead!(AES128OCB, OCB<AES128>, 16, 12, 16, "AES-128-OCB3 with a 12-byte nonce (cf. RFC 7253).");
+define_aead!(AES256OCB, OCB<AES256>, 32, 12, 16, "AES-256-OCB3 with a 12-byte nonce (cf. RFC 7253).");
+define_aead!(AES128EAX, EAX<AES128>, 16, 16, 16, "AES-128-EAX with a 16-byte nonce.");
+
+#[cfg(test)]
+mod tests_aead_aes {
+    use super::*;
+
+    // "The Galois/Counter Mode of Operation (GCM)", test case 4
+    const GCM_KEY: [u8; 16] = [0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08];
+    const GCM_NONCE: [u8; 12] = [0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88];
+    const GCM_AAD: [u8; 20] = [
+        0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF,
+        0xAB, 0xAD, 0xDA, 0xD2,
+    ];
+    const GCM_PLAIN: [u8; 60] = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26, 0x9A,
+        0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31, 0x8A, 0x72,
+        0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49, 0xA6, 0xB5, 0x25,
+        0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+    ];
+    const GCM_SEALED: [u8; 76] = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4, 0x9C,
+        0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC, 0xA1, 0x2E,
+        0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC, 0x84, 0xAA, 0x05,
+        0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91, 0x5B, 0xC9, 0x4F, 0xBC,
+        0x32, 0x21, 0xA5, 0xDB, 0x94, 0xFA, 0xE9, 0x5A, 0xE7, 0x12, 0x1A, 0x47,
+    ];
+
+    #[test]
+    fn aes128_gcm_seal_open() {
+        let sealed = AES128GCM::seal(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &GCM_PLAIN).expect("Error during AES128GCM execution");
+        assert_eq!(sealed, GCM_SEALED);
+
+        let plain = AES128GCM::open(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &GCM_SEALED).expect("Error during AES128GCM execution");
+        assert_eq!(plain, GCM_PLAIN);
+    }
+
+    #[test]
+    fn aes128_gcm_detached() {
+        let mut ciphertext = [0; 60];
+        let tag = AES128GCM::seal_detached(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &GCM_PLAIN, &mut ciphertext).expect("Error during AES128GCM execution");
+        assert_eq!(ciphertext, GCM_SEALED[..60]);
+        assert_eq!(tag, GCM_SEALED[60..]);
+
+        let mut plain = [0; 60];
+        AES128GCM::open_detached(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &ciphertext, &tag, &mut plain).expect("Error during AES128GCM execution");
+        assert_eq!(plain, GCM_PLAIN);
+    }
+
+    #[test]
+    fn aes128_gcm_in_place() {
+        let mut buffer = GCM_PLAIN.to_vec();
+        AES128GCM::seal_in_place(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &mut buffer).expect("Error during AES128GCM execution");
+        assert_eq!(buffer, GCM_SEALED);
+
+        AES128GCM::open_in_place(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &mut buffer).expect("Error during AES128GCM execution");
+        assert_eq!(buffer, GCM_PLAIN);
+    }
+
+    #[test]
+    fn aes128_gcm_errors() {
+        let mut sealed = GCM_SEALED.to_vec();
+        sealed[0] ^= 1;
+        assert_eq!(AES128GCM::open_in_place(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &mut sealed), Err(AeadError::AuthenticationFailed));
+        assert_eq!(sealed[1..], GCM_SEALED[1..]);
+
+        assert_eq!(AES128GCM::open(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &GCM_SEALED[..15]), Err(AeadError::BadLength));
+
+        let mut plain = [0; 59];
+        assert_eq!(AES128GCM::open_detached(&GCM_KEY, &GCM_NONCE, &GCM_AAD, &GCM_SEALED[..60], &[0; 16], &mut plain), Err(AeadError::BadLength));
+    }
+
+    #[test]
+    fn aes128_ccm8_seal_open() {
+        // SP 800-38C, C.3
+        let key = [0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F];
+        let nonce = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B];
+        let aad: [u8; 20] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 24] = core::array::from_fn(|i| 0x20 + i as u8);
+        let expected = [
+            0xE3, 0xB2, 0x01, 0xA9, 0xF5, 0xB7, 0x1A, 0x7A, 0x9B, 0x1C, 0xEA, 0xEC, 0xCD, 0x97, 0xE7, 0x0B,
+            0x61, 0x76, 0xAA, 0xD9, 0xA4, 0x42, 0x8A, 0xA5, 0x48, 0x43, 0x92, 0xFB, 0xC1, 0xB0, 0x99, 0x51,
+        ];
+
+        let sealed = AES128CCM8::seal(&key, &nonce, &aad, &plain).expect("Error during AES128CCM8 execution");
+        assert_eq!(sealed, expected);
+
+        let opened = AES128CCM8::open(&key, &nonce, &aad, &sealed).expect("Error during AES128CCM8 execution");
+        assert_eq!(opened, plain);
+        assert_eq!(AES128CCM8::open(&key, &nonce, &aad[1..], &sealed), Err(AeadError::AuthenticationFailed));
+    }
+
+    // same code for any AEAD
+    fn round_trip<A: Aead>(key: &A::KeyType, nonce: &A::NonceType) {
+        let sealed = A::seal(key, nonce, b"aad", b"some data to protect").expect("Error during AEAD execution");
+        assert_eq!(sealed.len(), 20 + A::TAG_SIZE);
+        let opened = A::open(key, nonce, b"aad", &sealed).expect("Error during AEAD execution");
+        assert_eq!(opened, b"some data to protect");
+
+        // a failed in-place opening leaves the ciphertext in the buffer
+        let mut buffer = sealed.clone();
+        buffer[0] ^= 1;
+        assert_eq!(A::open_in_place(key, nonce, b"aad", &mut buffer), Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer[1..], sealed[1..]);
+    }
+
+    #[test]
+    fn aes_aead_generic() {
+        round_trip::<AES192GCM>(&[1; 24], &[2; 12]);
+        round_trip::<AES256GCM>(&[1; 32], &[2; 12]);
+        round_trip::<AES128CCM>(&[1; 16], &[2; 12]);
+        round_trip::<AES256CCM>(&[1; 32], &[2; 12]);
+        round_trip::<AES128SIV>(&[1; 32], &[2; 12]);
+        round_trip::<AES256SIV>(&[1; 64], &[2; 16]);
+        round_trip::<AES128GCMSIV>(&[1; 16], &[2; 12]);
+        round_trip::<AES256GCMSIV>(&[1; 32], &[2; 12]);
+        round_trip::<AES128OCB>(&[1; 16], &[2; 12]);
+        round_trip::<AES256OCB>(&[1; 32], &[2; 12]);
+        round_trip::<AES128EAX>(&[1; 16], &[2; 16]);
+    }
+
+    #[test]
+    fn aes128_siv_seal_open() {
+        // V || C, as returned by AESSIV.encrypt(data, [aad, nonce]) of Python cryptography
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let expected = [
+            0xB3, 0xBC, 0x5B, 0x0D, 0x5D, 0x9E, 0xAC, 0x09, 0x2F, 0x57, 0xAA, 0x9A, 0x1C, 0x7E, 0xF5, 0x7A,
+            0x6B, 0x11, 0x21, 0xB4, 0xD3, 0xE5, 0xC5, 0xCB, 0x89,
+        ];
+
+        let sealed = AES128SIV::seal(&key, &nonce, b"header", b"Hello SIV").expect("Error during AES128SIV execution");
+        assert_eq!(sealed, expected);
+        let opened = AES128SIV::open(&key, &nonce, b"header", &sealed).expect("Error during AES128SIV execution");
+        assert_eq!(opened, b"Hello SIV");
+        assert_eq!(AES128SIV::open(&key, &nonce, b"Header", &sealed), Err(AeadError::AuthenticationFailed));
+        assert_eq!(AES128SIV::open(&key, &nonce, b"header", &sealed[..15]), Err(AeadError::BadLength));
+
+        // 12-byte nonce
+        let expected = [
+            0xF3, 0x88, 0x7E, 0x73, 0x67, 0xB2, 0x95, 0x9A, 0xA5, 0x71, 0x00, 0x97, 0xB3, 0xF6, 0x3C, 0x9F,
+            0x11, 0x89, 0xC8, 0x6E, 0x55, 0xB6, 0x0A, 0x55, 0x6A,
+        ];
+        let sealed = AES128SIV::seal(&key, &nonce[..12], b"header", b"Hello SIV").expect("Error during AES128SIV execution");
+        assert_eq!(sealed, expected);
+        let opened = AES128SIV::open(&key, &nonce[..12], b"header", &sealed).expect("Error during AES128SIV execution");
+        assert_eq!(opened, b"Hello SIV");
+    }
+}
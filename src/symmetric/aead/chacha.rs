@@ -0,0 +1,158 @@
+//! AEAD algorithms based on ChaCha20: ChaCha20-Poly1305 (cf. RFC 8439) and XChaCha20-Poly1305
+//! (cf. draft-irtf-cfrg-xchacha).
+//!
+//!```text
+//!   otk = first 32 bytes of ChaCha20(K, N, counter = 0)
+//!   C   = P xor ChaCha20(K, N, counter = 1)
+//!   T   = Poly1305(otk, A || 0^v || C || 0^u || len(A) || len(C))
+//! ```
+
+use crate::mac::poly1305::Poly1305;
+use crate::symmetric::aead::common::{verify_tag, Aead, AeadError};
+use crate::symmetric::stream_ciphers::chacha::{ChaCha20, ChaChaInitStruct, XChaCha20, XChaChaInitStruct};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+fn compute_tag(one_time_key: &[u8; 32], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let zeros = [0; 16];
+    let mut poly = Poly1305::new(one_time_key);
+    poly.update(aad);
+    poly.update(&zeros[..(16 - aad.len() % 16) % 16]);
+    poly.update(ciphertext);
+    poly.update(&zeros[..(16 - ciphertext.len() % 16) % 16]);
+    poly.update(&(aad.len() as u64).to_le_bytes());
+    poly.update(&(ciphertext.len() as u64).to_le_bytes());
+    poly.finalise()
+}
+
+// `new_cipher` returns the stream cipher, starting at the given block counter
+fn seal_in_place<S: StreamCipher>(new_cipher: impl Fn(u32) -> S, aad: &[u8], buffer: &mut [u8]) -> Result<[u8; 16], AeadError> {
+    let mut one_time_key = [0; 32];
+    new_cipher(0).cipher_in_place(&mut one_time_key)?;
+
+    new_cipher(1).cipher_in_place(buffer)?;
+
+    Ok(compute_tag(&one_time_key, aad, buffer))
+}
+
+fn open_in_place<S: StreamCipher>(new_cipher: impl Fn(u32) -> S, aad: &[u8], buffer: &mut [u8], tag: &[u8; 16]) -> Result<(), AeadError> {
+    let mut one_time_key = [0; 32];
+    new_cipher(0).cipher_in_place(&mut one_time_key)?;
+
+    verify_tag(&compute_tag(&one_time_key, aad, buffer), tag)?;
+
+    new_cipher(1).cipher_in_place(buffer)?;
+    Ok(())
+}
+
+/// ChaCha20-Poly1305 (cf. RFC 8439).
+pub struct ChaCha20Poly1305 {}
+
+impl Aead for ChaCha20Poly1305 {
+    const KEY_SIZE: usize = 32;
+    const NONCE_SIZE: usize = 12;
+    const TAG_SIZE: usize = 16;
+
+    type KeyType = [u8; 32];
+    type NonceType = [u8; 12];
+    type TagType = [u8; 16];
+
+    fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError> {
+        seal_in_place(|counter| ChaCha20::new(&ChaChaInitStruct { nonce: *nonce, counter }, key), aad, buffer)
+    }
+
+    fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError> {
+        open_in_place(|counter| ChaCha20::new(&ChaChaInitStruct { nonce: *nonce, counter }, key), aad, buffer, tag)
+    }
+}
+
+/// XChaCha20-Poly1305: 192-bit nonces, that can safely be chosen at random.
+pub struct XChaCha20Poly1305 {}
+
+impl Aead for XChaCha20Poly1305 {
+    const KEY_SIZE: usize = 32;
+    const NONCE_SIZE: usize = 24;
+    const TAG_SIZE: usize = 16;
+
+    type KeyType = [u8; 32];
+    type NonceType = [u8; 24];
+    type TagType = [u8; 16];
+
+    fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError> {
+        seal_in_place(|counter| XChaCha20::new(&XChaChaInitStruct { nonce: *nonce, counter }, key), aad, buffer)
+    }
+
+    fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError> {
+        open_in_place(|counter| XChaCha20::new(&XChaChaInitStruct { nonce: *nonce, counter }, key), aad, buffer, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests_aead_chacha {
+    use super::*;
+
+    const KEY: [u8; 32] = [
+        0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F,
+        0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9D, 0x9E, 0x9F,
+    ];
+    const AAD: [u8; 12] = [0x50, 0x51, 0x52, 0x53, 0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7];
+    const PLAIN: &[u8; 114] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    // RFC 8439, 2.8.2
+    const SEALED: [u8; 130] = [
+        0xD3, 0x1A, 0x8D, 0x34, 0x64, 0x8E, 0x60, 0xDB, 0x7B, 0x86, 0xAF, 0xBC, 0x53, 0xEF, 0x7E, 0xC2,
+        0xA4, 0xAD, 0xED, 0x51, 0x29, 0x6E, 0x08, 0xFE, 0xA9, 0xE2, 0xB5, 0xA7, 0x36, 0xEE, 0x62, 0xD6,
+        0x3D, 0xBE, 0xA4, 0x5E, 0x8C, 0xA9, 0x67, 0x12, 0x82, 0xFA, 0xFB, 0x69, 0xDA, 0x92, 0x72, 0x8B,
+        0x1A, 0x71, 0xDE, 0x0A, 0x9E, 0x06, 0x0B, 0x29, 0x05, 0xD6, 0xA5, 0xB6, 0x7E, 0xCD, 0x3B, 0x36,
+        0x92, 0xDD, 0xBD, 0x7F, 0x2D, 0x77, 0x8B, 0x8C, 0x98, 0x03, 0xAE, 0xE3, 0x28, 0x09, 0x1B, 0x58,
+        0xFA, 0xB3, 0x24, 0xE4, 0xFA, 0xD6, 0x75, 0x94, 0x55, 0x85, 0x80, 0x8B, 0x48, 0x31, 0xD7, 0xBC,
+        0x3F, 0xF4, 0xDE, 0xF0, 0x8E, 0x4B, 0x7A, 0x9D, 0xE5, 0x76, 0xD2, 0x65, 0x86, 0xCE, 0xC6, 0x4B,
+        0x61, 0x16, 0x1A, 0xE1, 0x0B, 0x59, 0x4F, 0x09, 0xE2, 0x6A, 0x7E, 0x90, 0x2E, 0xCB, 0xD0, 0x60,
+        0x06, 0x91,
+    ];
+
+    #[test]
+    fn chacha20_poly1305_seal_open() {
+        let nonce = [0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47];
+        let sealed = ChaCha20Poly1305::seal(&KEY, &nonce, &AAD, PLAIN).expect("Error during ChaCha20Poly1305 execution");
+        assert_eq!(sealed, SEALED);
+
+        let opened = ChaCha20Poly1305::open(&KEY, &nonce, &AAD, &SEALED).expect("Error during ChaCha20Poly1305 execution");
+        assert_eq!(opened, PLAIN);
+    }
+
+    #[test]
+    fn chacha20_poly1305_bad_tag() {
+        let nonce = [0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47];
+        let mut sealed = SEALED.to_vec();
+        sealed[129] ^= 0x10;
+        assert_eq!(ChaCha20Poly1305::open_in_place(&KEY, &nonce, &AAD, &mut sealed), Err(AeadError::AuthenticationFailed));
+        assert_eq!(sealed[..114], SEALED[..114]);
+        assert_eq!(ChaCha20Poly1305::open(&KEY, &nonce, &AAD[1..], &SEALED), Err(AeadError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn xchacha20_poly1305_seal_open() {
+        // draft-irtf-cfrg-xchacha, A.3.1
+        let nonce: [u8; 24] = core::array::from_fn(|i| 0x40 + i as u8);
+        let expected = [
+            0xBD, 0x6D, 0x17, 0x9D, 0x3E, 0x83, 0xD4, 0x3B, 0x95, 0x76, 0x57, 0x94, 0x93, 0xC0, 0xE9, 0x39,
+            0x57, 0x2A, 0x17, 0x00, 0x25, 0x2B, 0xFA, 0xCC, 0xBE, 0xD2, 0x90, 0x2C, 0x21, 0x39, 0x6C, 0xBB,
+            0x73, 0x1C, 0x7F, 0x1B, 0x0B, 0x4A, 0xA6, 0x44, 0x0B, 0xF3, 0xA8, 0x2F, 0x4E, 0xDA, 0x7E, 0x39,
+            0xAE, 0x64, 0xC6, 0x70, 0x8C, 0x54, 0xC2, 0x16, 0xCB, 0x96, 0xB7, 0x2E, 0x12, 0x13, 0xB4, 0x52,
+            0x2F, 0x8C, 0x9B, 0xA4, 0x0D, 0xB5, 0xD9, 0x45, 0xB1, 0x1B, 0x69, 0xB9, 0x82, 0xC1, 0xBB, 0x9E,
+            0x3F, 0x3F, 0xAC, 0x2B, 0xC3, 0x69, 0x48, 0x8F, 0x76, 0xB2, 0x38, 0x35, 0x65, 0xD3, 0xFF, 0xF9,
+            0x21, 0xF9, 0x66, 0x4C, 0x97, 0x63, 0x7D, 0xA9, 0x76, 0x88, 0x12, 0xF6, 0x15, 0xC6, 0x8B, 0x13,
+            0xB5, 0x2E, 0xC0, 0x87, 0x59, 0x24, 0xC1, 0xC7, 0x98, 0x79, 0x47, 0xDE, 0xAF, 0xD8, 0x78, 0x0A,
+            0xCF, 0x49,
+        ];
+
+        let mut ciphertext = [0; 114];
+        let tag = XChaCha20Poly1305::seal_detached(&KEY, &nonce, &AAD, PLAIN, &mut ciphertext).expect("Error during XChaCha20Poly1305 execution");
+        assert_eq!(ciphertext, expected[..114]);
+        assert_eq!(tag, expected[114..]);
+
+        let mut plain = [0; 114];
+        XChaCha20Poly1305::open_detached(&KEY, &nonce, &AAD, &ciphertext, &tag, &mut plain).expect("Error during XChaCha20Poly1305 execution");
+        assert_eq!(&plain, PLAIN);
+    }
+}
@@ -0,0 +1,168 @@
+//! Shared code between AEAD algorithms
+
+use std::fmt;
+
+use crate::utils::constant_time_eq;
+
+/// Errors returned by AEAD algorithms and by the authenticated block cipher modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AeadError {
+    /// Buffers have inconsistent lengths, the sealed data is shorter than the tag, or the data is too long.
+    BadLength,
+    /// The key length is not supported by the algorithm.
+    InvalidKeyLength,
+    /// The nonce length is not supported by the algorithm.
+    InvalidNonceLength,
+    /// The tag length is not supported by the algorithm.
+    InvalidTagLength,
+    /// The tag does not match the data. No plaintext is released.
+    AuthenticationFailed,
+    /// Any other error (e.g. misuse, or error returned by the underlying cipher).
+    Cipher(&'static str)
+}
+
+impl fmt::Display for AeadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AeadError::BadLength => write!(f, "Bad buffer length"),
+            AeadError::InvalidKeyLength => write!(f, "Invalid key length"),
+            AeadError::InvalidNonceLength => write!(f, "Invalid nonce length"),
+            AeadError::InvalidTagLength => write!(f, "Invalid tag length"),
+            AeadError::AuthenticationFailed => write!(f, "Authentication failed"),
+            AeadError::Cipher(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for AeadError {}
+
+/// Errors of the underlying primitives, which are plain messages.
+impl From<&'static str> for AeadError {
+    fn from(message: &'static str) -> Self {
+        AeadError::Cipher(message)
+    }
+}
+
+/// Compares the computed tag with the received one in constant time.
+pub(crate) fn verify_tag(expected: &[u8], tag: &[u8]) -> Result<(), AeadError> {
+    if constant_time_eq(expected, tag) {
+        Ok(())
+    } else {
+        Err(AeadError::AuthenticationFailed)
+    }
+}
+
+/// Trait implemented by all AEAD algorithms.
+///
+/// Each AEAD has
+/// - 3 constants:
+///     - `KEY_SIZE`, `NONCE_SIZE` and `TAG_SIZE`, expressed as bytes (when `NonceType` is `[u8]`, the nonce can
+///       have any length and `NONCE_SIZE` is the recommended one)
+/// - 3 types:
+///     - `KeyType`, `NonceType` and `TagType`
+/// - 2 required static methods, that work in place on `buffer` with a detached tag:
+///     - `seal_in_place_detached`: encrypts `buffer` and returns the tag
+///     - `open_in_place_detached`: checks the tag and decrypts `buffer`
+///
+/// Other static methods are derived from these ones:
+/// - `seal_detached` and `open_detached`: same with distinct input and output buffers
+/// - `seal` and `open`: the tag is appended to the ciphertext (AES-SIV puts it first, cf. RFC 5297)
+/// - `seal_in_place` and `open_in_place`: same, in a `Vec`
+///
+/// When opening fails, output buffers are left untouched (in-place buffers still hold the ciphertext).
+pub trait Aead {
+    const KEY_SIZE: usize;
+    const NONCE_SIZE: usize;
+    const TAG_SIZE: usize;
+
+    type KeyType;  // [u8; KEY_SIZE]
+    type NonceType: ?Sized;  // [u8; NONCE_SIZE] or [u8]
+    type TagType: Copy + Default + AsRef<[u8]> + AsMut<[u8]>;  // [u8; TAG_SIZE]
+
+    fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError>;
+    fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError>;
+
+    fn seal_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8]) -> Result<Self::TagType, AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::seal_in_place_detached(key, nonce, aad, ciphertext)
+    }
+
+    fn open_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], ciphertext: &[u8], tag: &Self::TagType, plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut buffer = ciphertext.to_vec();
+        Self::open_in_place_detached(key, nonce, aad, &mut buffer, tag)?;
+        plaintext.copy_from_slice(&buffer);
+        Ok(())
+    }
+
+    /// Returns `ciphertext || tag`.
+    fn seal(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AeadError> {
+        let mut buffer = plaintext.to_vec();
+        Self::seal_in_place(key, nonce, aad, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Opens `ciphertext || tag`.
+    fn open(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, AeadError> {
+        let mut buffer = sealed.to_vec();
+        Self::open_in_place(key, nonce, aad, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Encrypts `buffer` and appends the tag.
+    fn seal_in_place(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), AeadError> {
+        let tag = Self::seal_in_place_detached(key, nonce, aad, buffer)?;
+        buffer.extend_from_slice(tag.as_ref());
+        Ok(())
+    }
+
+    /// Checks the tag at the end of `buffer`, then replaces `buffer` with the plaintext.
+    fn open_in_place(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), AeadError> {
+        if buffer.len() < Self::TAG_SIZE {
+            return Err(AeadError::BadLength);
+        }
+        let text_len = buffer.len() - Self::TAG_SIZE;
+        let mut tag = Self::TagType::default();
+        tag.as_mut().copy_from_slice(&buffer[text_len..]);
+
+        Self::open_in_place_detached(key, nonce, aad, &mut buffer[..text_len], &tag)?;
+        buffer.truncate(text_len);
+        Ok(())
+    }
+}
+
+/// Defines an AEAD from an authenticated mode that exposes static `encrypt_in_place` and `decrypt_in_place`
+/// methods, with the same parameters as [crate::symmetric::block_ciphers_modes::gcm::GCM].
+macro_rules! define_aead {
+    ( $aead_name:ident, $mode:ty, $key_length:expr, $nonce_length:expr, $tag_length:expr, $doc:expr) => {
+        #[doc = $doc]
+        pub struct $aead_name {}
+
+        impl Aead for $aead_name {
+            const KEY_SIZE: usize = $key_length;
+            const NONCE_SIZE: usize = $nonce_length;
+            const TAG_SIZE: usize = $tag_length;
+
+            type KeyType = [u8; $key_length];
+            type NonceType = [u8; $nonce_length];
+            type TagType = [u8; $tag_length];
+
+            fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError> {
+                let mut tag = [0; $tag_length];
+                <$mode>::encrypt_in_place(key, nonce, aad, buffer, &mut tag)?;
+                Ok(tag)
+            }
+
+            fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError> {
+                <$mode>::decrypt_in_place(key, nonce, aad, buffer, tag)
+            }
+        }
+    };
+}
+
+pub(crate) use define_aead;
@@ -0,0 +1,40 @@
+//! Authenticated encryption with associated data (AEAD).
+//!
+//! All AEAD algorithms implement the [common::Aead] trait, so protocols can be written once for all of
+//! them. Key, nonce and tag sizes are fixed for each algorithm (except the nonce of AES-SIV).
+//!
+//! Currently, these algorithms are available:
+//! - AES-GCM (128, 192 and 256-bit keys, 12-byte nonce, 16-byte tag)
+//! - AES-CCM (128 and 256-bit keys, 12-byte nonce, 16 or 8-byte tag)
+//! - AES-SIV (256 and 512-bit keys, nonce of any length, tag before the ciphertext) and AES-GCM-SIV
+//!   (128 and 256-bit keys, 12-byte nonce), which are resistant to nonce misuse
+//! - AES-OCB3 (128 and 256-bit keys, 12-byte nonce) and AES-EAX (128-bit key, 16-byte nonce)
+//! - ChaCha20-Poly1305 (12-byte nonce) and XChaCha20-Poly1305 (24-byte nonce)
+//! - XSalsa20-Poly1305, with NaCl/libsodium compatible `crypto_secretbox` and `crypto_box` (see [nacl])
+//!
+//! They are built on top of the authenticated modes of [crate::symmetric::block_ciphers_modes], which
+//! can be used directly for other nonce and tag sizes.
+//!
+//! # Example
+//!
+//! ```
+//! use crypto_lib::symmetric::aead::aes::AES128GCM;
+//! use crypto_lib::symmetric::aead::common::{Aead, AeadError};
+//!
+//! let key = [0x42; 16];
+//! let nonce = [0x24; 12];
+//!
+//! let sealed = AES128GCM::seal(&key, &nonce, b"header", b"secret message").unwrap();
+//! assert_eq!(sealed.len(), 14 + AES128GCM::TAG_SIZE);
+//!
+//! let opened = AES128GCM::open(&key, &nonce, b"header", &sealed).unwrap();
+//! assert_eq!(opened, b"secret message");
+//!
+//! let res = AES128GCM::open(&key, &nonce, b"other header", &sealed);
+//! assert_eq!(res, Err(AeadError::AuthenticationFailed));
+//! ```
+
+pub mod common;
+pub mod aes;
+pub mod chacha;
+pub mod nacl;
@@ -0,0 +1,214 @@
+//! NaCl/libsodium compatible `crypto_secretbox` (XSalsa20-Poly1305) and `crypto_box`
+//! (X25519 + XSalsa20-Poly1305).
+//!
+//!```text
+//!   (otk || K') = XSalsa20(K, N) xor (0^32 || P)
+//!   C = K'
+//!   T = Poly1305(otk, C)
+//!   secretbox = T || C
+//!
+//!   box key = HSalsa20(X25519(sk, pk), 0^16)
+//! ```
+//!
+//! The `*_easy` functions use the libsodium "combined" layout, where the tag comes **before** the
+//! ciphertext. [XSalsa20Poly1305] implements [Aead], whose `seal` appends the tag instead; its
+//! detached functions give the same ciphertext and tag as NaCl.
+
+use crate::asymmetric::x25519::shared_secret;
+use crate::mac::poly1305::Poly1305;
+use crate::symmetric::aead::common::{verify_tag, Aead, AeadError};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+use crate::symmetric::stream_ciphers::salsa::{hsalsa20, XSalsa20, XSalsaInitStruct};
+
+/// XSalsa20-Poly1305, as used by `crypto_secretbox`.
+pub struct XSalsa20Poly1305 {}
+
+// returns the one-time key (first 32 bytes of keystream), and the cipher positioned after it
+fn xsalsa20_start(key: &[u8; 32], nonce: &[u8; 24]) -> Result<([u8; 32], XSalsa20), AeadError> {
+    let mut cipher = XSalsa20::new(&XSalsaInitStruct { nonce: *nonce }, key);
+    let mut one_time_key = [0; 32];
+    cipher.cipher_in_place(&mut one_time_key)?;
+    Ok((one_time_key, cipher))
+}
+
+fn poly1305(one_time_key: &[u8; 32], data: &[u8]) -> [u8; 16] {
+    let mut poly = Poly1305::new(one_time_key);
+    poly.update(data);
+    poly.finalise()
+}
+
+impl Aead for XSalsa20Poly1305 {
+    const KEY_SIZE: usize = 32;
+    const NONCE_SIZE: usize = 24;
+    const TAG_SIZE: usize = 16;
+
+    type KeyType = [u8; 32];
+    type NonceType = [u8; 24];
+    type TagType = [u8; 16];
+
+    /// `aad` must be empty: NaCl does not authenticate additional data.
+    fn seal_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8]) -> Result<Self::TagType, AeadError> {
+        if !aad.is_empty() {
+            return Err(AeadError::Cipher("XSalsa20-Poly1305 does not support additional data"));
+        }
+
+        let (one_time_key, mut cipher) = xsalsa20_start(key, nonce)?;
+        cipher.cipher_in_place(buffer)?;
+        Ok(poly1305(&one_time_key, buffer))
+    }
+
+    fn open_in_place_detached(key: &Self::KeyType, nonce: &Self::NonceType, aad: &[u8], buffer: &mut [u8], tag: &Self::TagType) -> Result<(), AeadError> {
+        if !aad.is_empty() {
+            return Err(AeadError::Cipher("XSalsa20-Poly1305 does not support additional data"));
+        }
+
+        let (one_time_key, mut cipher) = xsalsa20_start(key, nonce)?;
+        verify_tag(&poly1305(&one_time_key, buffer), tag)?;
+        cipher.cipher_in_place(buffer)?;
+        Ok(())
+    }
+}
+
+/// `crypto_secretbox_easy`: returns `tag || ciphertext`.
+pub fn crypto_secretbox_easy(message: &[u8], nonce: &[u8; 24], key: &[u8; 32]) -> Result<Vec<u8>, AeadError> {
+    let mut boxed = vec![0; 16 + message.len()];
+    boxed[16..].copy_from_slice(message);
+    let tag = XSalsa20Poly1305::seal_in_place_detached(key, nonce, &[], &mut boxed[16..])?;
+    boxed[..16].copy_from_slice(&tag);
+    Ok(boxed)
+}
+
+/// `crypto_secretbox_open_easy`: opens `tag || ciphertext`.
+pub fn crypto_secretbox_open_easy(boxed: &[u8], nonce: &[u8; 24], key: &[u8; 32]) -> Result<Vec<u8>, AeadError> {
+    if boxed.len() < 16 {
+        return Err(AeadError::BadLength);
+    }
+    let tag = boxed[..16].try_into().unwrap();
+    let mut message = boxed[16..].to_vec();
+    XSalsa20Poly1305::open_in_place_detached(key, nonce, &[], &mut message, &tag)?;
+    Ok(message)
+}
+
+/// `crypto_box_beforenm`: computes the key shared by `secret_key` and the peer's `public_key`.
+pub fn crypto_box_beforenm(public_key: &[u8; 32], secret_key: &[u8; 32]) -> Result<[u8; 32], AeadError> {
+    let shared = shared_secret(secret_key, public_key)?;
+    Ok(hsalsa20(&shared, &[0; 16]))
+}
+
+/// `crypto_box_easy`: encrypts `message` for the owner of `public_key`; returns `tag || ciphertext`.
+pub fn crypto_box_easy(message: &[u8], nonce: &[u8; 24], public_key: &[u8; 32], secret_key: &[u8; 32]) -> Result<Vec<u8>, AeadError> {
+    let key = crypto_box_beforenm(public_key, secret_key)?;
+    crypto_secretbox_easy(message, nonce, &key)
+}
+
+/// `crypto_box_open_easy`: opens `tag || ciphertext` sent by the owner of `public_key`.
+pub fn crypto_box_open_easy(boxed: &[u8], nonce: &[u8; 24], public_key: &[u8; 32], secret_key: &[u8; 32]) -> Result<Vec<u8>, AeadError> {
+    let key = crypto_box_beforenm(public_key, secret_key)?;
+    crypto_secretbox_open_easy(boxed, nonce, &key)
+}
+
+#[cfg(test)]
+mod tests_nacl {
+    use super::*;
+
+    // "Cryptography in NaCl", section 7 (tests/box.c in NaCl)
+    const ALICE_SECRET: [u8; 32] = [
+        0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+        0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+    ];
+    const ALICE_PUBLIC: [u8; 32] = [
+        0x85, 0x20, 0xF0, 0x09, 0x89, 0x30, 0xA7, 0x54, 0x74, 0x8B, 0x7D, 0xDC, 0xB4, 0x3E, 0xF7, 0x5A,
+        0x0D, 0xBF, 0x3A, 0x0D, 0x26, 0x38, 0x1A, 0xF4, 0xEB, 0xA4, 0xA9, 0x8E, 0xAA, 0x9B, 0x4E, 0x6A,
+    ];
+    const BOB_SECRET: [u8; 32] = [
+        0x5D, 0xAB, 0x08, 0x7E, 0x62, 0x4A, 0x8A, 0x4B, 0x79, 0xE1, 0x7F, 0x8B, 0x83, 0x80, 0x0E, 0xE6,
+        0x6F, 0x3B, 0xB1, 0x29, 0x26, 0x18, 0xB6, 0xFD, 0x1C, 0x2F, 0x8B, 0x27, 0xFF, 0x88, 0xE0, 0xEB,
+    ];
+    const BOB_PUBLIC: [u8; 32] = [
+        0xDE, 0x9E, 0xDB, 0x7D, 0x7B, 0x7D, 0xC1, 0xB4, 0xD3, 0x5B, 0x61, 0xC2, 0xEC, 0xE4, 0x35, 0x37,
+        0x3F, 0x83, 0x43, 0xC8, 0x5B, 0x78, 0x67, 0x4D, 0xAD, 0xFC, 0x7E, 0x14, 0x6F, 0x88, 0x2B, 0x4F,
+    ];
+    const FIRST_KEY: [u8; 32] = [
+        0x1B, 0x27, 0x55, 0x64, 0x73, 0xE9, 0x85, 0xD4, 0x62, 0xCD, 0x51, 0x19, 0x7A, 0x9A, 0x46, 0xC7,
+        0x60, 0x09, 0x54, 0x9E, 0xAC, 0x64, 0x74, 0xF2, 0x06, 0xC4, 0xEE, 0x08, 0x44, 0xF6, 0x83, 0x89,
+    ];
+    const NONCE: [u8; 24] = [
+        0x69, 0x69, 0x6E, 0xE9, 0x55, 0xB6, 0x2B, 0x73, 0xCD, 0x62, 0xBD, 0xA8, 0x75, 0xFC, 0x73, 0xD6,
+        0x82, 0x19, 0xE0, 0x03, 0x6B, 0x7A, 0x0B, 0x37,
+    ];
+    const MESSAGE: [u8; 131] = [
+        0xBE, 0x07, 0x5F, 0xC5, 0x3C, 0x81, 0xF2, 0xD5, 0xCF, 0x14, 0x13, 0x16, 0xEB, 0xEB, 0x0C, 0x7B,
+        0x52, 0x28, 0xC5, 0x2A, 0x4C, 0x62, 0xCB, 0xD4, 0x4B, 0x66, 0x84, 0x9B, 0x64, 0x24, 0x4F, 0xFC,
+        0xE5, 0xEC, 0xBA, 0xAF, 0x33, 0xBD, 0x75, 0x1A, 0x1A, 0xC7, 0x28, 0xD4, 0x5E, 0x6C, 0x61, 0x29,
+        0x6C, 0xDC, 0x3C, 0x01, 0x23, 0x35, 0x61, 0xF4, 0x1D, 0xB6, 0x6C, 0xCE, 0x31, 0x4A, 0xDB, 0x31,
+        0x0E, 0x3B, 0xE8, 0x25, 0x0C, 0x46, 0xF0, 0x6D, 0xCE, 0xEA, 0x3A, 0x7F, 0xA1, 0x34, 0x80, 0x57,
+        0xE2, 0xF6, 0x55, 0x6A, 0xD6, 0xB1, 0x31, 0x8A, 0x02, 0x4A, 0x83, 0x8F, 0x21, 0xAF, 0x1F, 0xDE,
+        0x04, 0x89, 0x77, 0xEB, 0x48, 0xF5, 0x9F, 0xFD, 0x49, 0x24, 0xCA, 0x1C, 0x60, 0x90, 0x2E, 0x52,
+        0xF0, 0xA0, 0x89, 0xBC, 0x76, 0x89, 0x70, 0x40, 0xE0, 0x82, 0xF9, 0x37, 0x76, 0x38, 0x48, 0x64,
+        0x5E, 0x07, 0x05,
+    ];
+    const BOXED: [u8; 147] = [
+        0xF3, 0xFF, 0xC7, 0x70, 0x3F, 0x94, 0x00, 0xE5, 0x2A, 0x7D, 0xFB, 0x4B, 0x3D, 0x33, 0x05, 0xD9,
+        0x8E, 0x99, 0x3B, 0x9F, 0x48, 0x68, 0x12, 0x73, 0xC2, 0x96, 0x50, 0xBA, 0x32, 0xFC, 0x76, 0xCE,
+        0x48, 0x33, 0x2E, 0xA7, 0x16, 0x4D, 0x96, 0xA4, 0x47, 0x6F, 0xB8, 0xC5, 0x31, 0xA1, 0x18, 0x6A,
+        0xC0, 0xDF, 0xC1, 0x7C, 0x98, 0xDC, 0xE8, 0x7B, 0x4D, 0xA7, 0xF0, 0x11, 0xEC, 0x48, 0xC9, 0x72,
+        0x71, 0xD2, 0xC2, 0x0F, 0x9B, 0x92, 0x8F, 0xE2, 0x27, 0x0D, 0x6F, 0xB8, 0x63, 0xD5, 0x17, 0x38,
+        0xB4, 0x8E, 0xEE, 0xE3, 0x14, 0xA7, 0xCC, 0x8A, 0xB9, 0x32, 0x16, 0x45, 0x48, 0xE5, 0x26, 0xAE,
+        0x90, 0x22, 0x43, 0x68, 0x51, 0x7A, 0xCF, 0xEA, 0xBD, 0x6B, 0xB3, 0x73, 0x2B, 0xC0, 0xE9, 0xDA,
+        0x99, 0x83, 0x2B, 0x61, 0xCA, 0x01, 0xB6, 0xDE, 0x56, 0x24, 0x4A, 0x9E, 0x88, 0xD5, 0xF9, 0xB3,
+        0x79, 0x73, 0xF6, 0x22, 0xA4, 0x3D, 0x14, 0xA6, 0x59, 0x9B, 0x1F, 0x65, 0x4C, 0xB4, 0x5A, 0x74,
+        0xE3, 0x55, 0xA5,
+    ];
+
+    #[test]
+    fn test_box_beforenm() {
+        assert_eq!(crypto_box_beforenm(&BOB_PUBLIC, &ALICE_SECRET), Ok(FIRST_KEY));
+        assert_eq!(crypto_box_beforenm(&ALICE_PUBLIC, &BOB_SECRET), Ok(FIRST_KEY));
+    }
+
+    #[test]
+    fn test_box() {
+        let boxed = crypto_box_easy(&MESSAGE, &NONCE, &BOB_PUBLIC, &ALICE_SECRET).expect("Error during crypto_box execution");
+        assert_eq!(boxed, BOXED);
+
+        let message = crypto_box_open_easy(&BOXED, &NONCE, &ALICE_PUBLIC, &BOB_SECRET).expect("Error during crypto_box execution");
+        assert_eq!(message, MESSAGE);
+    }
+
+    #[test]
+    fn test_secretbox() {
+        let boxed = crypto_secretbox_easy(&MESSAGE, &NONCE, &FIRST_KEY).expect("Error during crypto_secretbox execution");
+        assert_eq!(boxed, BOXED);
+
+        let message = crypto_secretbox_open_easy(&BOXED, &NONCE, &FIRST_KEY).expect("Error during crypto_secretbox execution");
+        assert_eq!(message, MESSAGE);
+
+        // short message, in a single partial block
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce: [u8; 24] = core::array::from_fn(|i| i as u8);
+        let expected = [
+            0xF0, 0xF7, 0xFB, 0xE6, 0x75, 0x99, 0x4E, 0x14, 0xAB, 0x2F, 0xB4, 0x24, 0x3B, 0xF2, 0xC5, 0xA0,
+            0x6C, 0xEE, 0xE5, 0xE3, 0x08,
+        ];
+        let boxed = crypto_secretbox_easy(b"hello", &nonce, &key).expect("Error during crypto_secretbox execution");
+        assert_eq!(boxed, expected);
+    }
+
+    #[test]
+    fn test_secretbox_errors() {
+        let mut boxed = BOXED;
+        boxed[20] ^= 1;
+        assert_eq!(crypto_secretbox_open_easy(&boxed, &NONCE, &FIRST_KEY), Err(AeadError::AuthenticationFailed));
+        assert_eq!(crypto_secretbox_open_easy(&BOXED[..15], &NONCE, &FIRST_KEY), Err(AeadError::BadLength));
+        assert!(XSalsa20Poly1305::seal(&FIRST_KEY, &NONCE, b"aad", &MESSAGE).is_err());
+        assert!(crypto_box_easy(&MESSAGE, &NONCE, &[0; 32], &ALICE_SECRET).is_err());
+    }
+
+    #[test]
+    fn test_xsalsa20_poly1305_aead() {
+        let mut ciphertext = [0; 131];
+        let tag = XSalsa20Poly1305::seal_detached(&FIRST_KEY, &NONCE, &[], &MESSAGE, &mut ciphertext).expect("Error during XSalsa20Poly1305 execution");
+        assert_eq!(tag, BOXED[..16]);
+        assert_eq!(ciphertext, BOXED[16..]);
+    }
+}
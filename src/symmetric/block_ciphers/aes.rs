@@ -4,7 +4,7 @@ use crate::symmetric::block_ciphers::common::BlockCipher;
 
 type StateAes = [[u8; 4]; 4];
 
-const SBOX: [u8; 256] = [
+pub(crate) const SBOX: [u8; 256] = [
 0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5, 0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7, 0xAB, 0x76,
 0xCA, 0x82, 0xC9, 0x7D, 0xFA, 0x59, 0x47, 0xF0, 0xAD, 0xD4, 0xA2, 0xAF, 0x9C, 0xA4, 0x72, 0xC0,
 0xB7, 0xFD, 0x93, 0x26, 0x36, 0x3F, 0xF7, 0xCC, 0x34, 0xA5, 0xE5, 0xF1, 0x71, 0xD8, 0x31, 0x15,
@@ -22,7 +22,7 @@ const SBOX: [u8; 256] = [
 0xE1, 0xF8, 0x98, 0x11, 0x69, 0xD9, 0x8E, 0x94, 0x9B, 0x1E, 0x87, 0xE9, 0xCE, 0x55, 0x28, 0xDF,
 0x8C, 0xA1, 0x89, 0x0D, 0xBF, 0xE6, 0x42, 0x68, 0x41, 0x99, 0x2D, 0x0F, 0xB0, 0x54, 0xBB, 0x16];
 
-const INV_SBOX: [u8; 256] = [
+pub(crate) const INV_SBOX: [u8; 256] = [
 0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
 0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
 0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
@@ -159,6 +159,68 @@ fn sub_word(word: [u8;4]) -> [u8;4] {
     [SBOX[word[0] as usize], SBOX[word[1] as usize], SBOX[word[2] as usize], SBOX[word[3] as usize]]
 }
 
+fn block_to_state(block: &[u8; 16]) -> StateAes {
+    let mut state: StateAes = [[0;4];4];
+    for i in 0..4 {
+        for j in 0..4 {
+            state[j][i] = block[4*i+j];
+        }
+    }
+    state
+}
+
+fn state_to_block(state: &StateAes) -> [u8; 16] {
+    let mut block = [0; 16];
+    for i in 0..4 {
+        for j in 0..4 {
+            block[4*i+j] = state[j][i];
+        }
+    }
+    block
+}
+
+fn round_key(key_expanded: &[u8], round: usize) -> [u8; 16] {
+    key_expanded[4*4*round..4*(4*round+4)].try_into().unwrap()
+}
+
+// Encrypts a state with an expanded key.
+fn cipher_state(state: &mut StateAes, key_expanded: &[u8], nr: usize) {
+    // first add_round_key
+    add_round_key(state, &round_key(key_expanded, 0));
+
+    // for rounds from 1 to Nr - 1
+    for round in 1..nr {
+        sub_bytes(state);
+        shift_rows(state);
+        mix_columns(state);
+        add_round_key(state, &round_key(key_expanded, round));
+    }
+
+    // last round
+    sub_bytes(state);
+    shift_rows(state);
+    add_round_key(state, &round_key(key_expanded, nr));
+}
+
+// Decrypts a state with an expanded key.
+fn decipher_state(state: &mut StateAes, key_expanded: &[u8], nr: usize) {
+    // first add_round_key
+    add_round_key(state, &round_key(key_expanded, nr));
+
+    // for rounds from Nr - 1 to 1
+    for round in (1..nr).rev() {
+        inv_shift_rows(state);
+        inv_sub_bytes(state);
+        add_round_key(state, &round_key(key_expanded, round));
+        inv_mix_columns(state);
+    }
+
+    // last round
+    inv_shift_rows(state);
+    inv_sub_bytes(state);
+    add_round_key(state, &round_key(key_expanded, 0));
+}
+
 
 macro_rules! define_aes {
     ( $aes_name:ident, $key_expansion_name:ident, $key_length:expr, $expanded_key_length:expr, $nk:expr, $nr:expr) => {
@@ -202,83 +264,45 @@ macro_rules! define_aes {
             type BlockType = [u8; 16];
 
             fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
-                let nr = $nr;
-                let mut state: StateAes = [[0;4];4];
-
                 // key expansion
                 let key_expanded = $key_expansion_name(key);
 
-                // copy plaintext into state
-                for i in 0..4 {
-                    for j in 0..4 {
-                        state[j][i] = plaintext[4*i+j];
-                    }
-                }
-
-                // first add_round_key
-                add_round_key(&mut state, &key_expanded[0..4*4].try_into().unwrap());
-
-                // for rounds from 1 to Nr - 1
-                for round in 1..nr {
-                    sub_bytes(&mut state);
-                    shift_rows(&mut state);
-                    mix_columns(&mut state);
-                    add_round_key(&mut state, &key_expanded[4*4*round..4*(4*round+4)].try_into().unwrap());
-                }
-
-                // last round
-                sub_bytes(&mut state);
-                shift_rows(&mut state);
-                add_round_key(&mut state, &key_expanded[4*4*nr..4*(4*nr+4)].try_into().unwrap());
-
-                // copy state into ciphertext
-                for i in 0..4 {
-                    for j in 0..4 {
-                        ciphertext[4*i+j] = state[j][i];
-                    }
-                }
+                let mut state = block_to_state(plaintext);
+                cipher_state(&mut state, &key_expanded, $nr);
+                *ciphertext = state_to_block(&state);
 
                 return Ok(());
             }
 
             fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
-                let nr = $nr;
-                let mut state: StateAes = [[0;4];4];
-
                 // key expansion
                 let key_expanded = $key_expansion_name(key);
 
-                // copy ciphertext into state
-                for i in 0..4 {
-                    for j in 0..4 {
-                        state[j][i] = ciphertext[4*i+j];
-                    }
-                }
+                let mut state = block_to_state(ciphertext);
+                decipher_state(&mut state, &key_expanded, $nr);
+                *plaintext = state_to_block(&state);
 
-                // first add_round_key
-                add_round_key(&mut state, &key_expanded[4*4*nr..4*(4*nr+4)].try_into().unwrap());
+                return Ok(());
+            }
 
-                // for rounds from Nr - 1 to 1
-                for round in (1..nr).rev() {
-                    inv_shift_rows(&mut state);
-                    inv_sub_bytes(&mut state);
-                    add_round_key(&mut state, &key_expanded[4*4*round..4*(4*round+4)].try_into().unwrap());
-                    inv_mix_columns(&mut state);
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let key_expanded = $key_expansion_name(key);
+                for block in blocks.iter_mut() {
+                    let mut state = block_to_state(block);
+                    cipher_state(&mut state, &key_expanded, $nr);
+                    *block = state_to_block(&state);
                 }
+                Ok(())
+            }
 
-                // last round
-                inv_shift_rows(&mut state);
-                inv_sub_bytes(&mut state);
-                add_round_key(&mut state, &key_expanded[0..4*4].try_into().unwrap());
-
-                // copy state into plaintext
-                for i in 0..4 {
-                    for j in 0..4 {
-                        plaintext[4*i+j] = state[j][i];
-                    }
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let key_expanded = $key_expansion_name(key);
+                for block in blocks.iter_mut() {
+                    let mut state = block_to_state(block);
+                    decipher_state(&mut state, &key_expanded, $nr);
+                    *block = state_to_block(&state);
                 }
-
-                return Ok(());
+                Ok(())
             }
         }
     }
@@ -352,5 +376,50 @@ mod tests_aes {
         AES256::decipher(&mut plain, &ciphertext, &key).expect("Error during AES256 execution");
         assert_eq!(plain, expected);
     }
+
+    #[test]
+    fn aes128_encrypt_blocks() {
+        // 4 blocks processed together + 2 remaining blocks
+        let mut blocks = [
+            [0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A],
+            [0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51],
+            [0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF],
+            [0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10],
+            [0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A],
+            [0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51],
+        ];
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let expected = [
+            [0x3A, 0xD7, 0x7B, 0xB4, 0x0D, 0x7A, 0x36, 0x60, 0xA8, 0x9E, 0xCA, 0xF3, 0x24, 0x66, 0xEF, 0x97],
+            [0xF5, 0xD3, 0xD5, 0x85, 0x03, 0xB9, 0x69, 0x9D, 0xE7, 0x85, 0x89, 0x5A, 0x96, 0xFD, 0xBA, 0xAF],
+            [0x43, 0xB1, 0xCD, 0x7F, 0x59, 0x8E, 0xCE, 0x23, 0x88, 0x1B, 0x00, 0xE3, 0xED, 0x03, 0x06, 0x88],
+            [0x7B, 0x0C, 0x78, 0x5E, 0x27, 0xE8, 0xAD, 0x3F, 0x82, 0x23, 0x20, 0x71, 0x04, 0x72, 0x5D, 0xD4],
+            [0x3A, 0xD7, 0x7B, 0xB4, 0x0D, 0x7A, 0x36, 0x60, 0xA8, 0x9E, 0xCA, 0xF3, 0x24, 0x66, 0xEF, 0x97],
+            [0xF5, 0xD3, 0xD5, 0x85, 0x03, 0xB9, 0x69, 0x9D, 0xE7, 0x85, 0x89, 0x5A, 0x96, 0xFD, 0xBA, 0xAF],
+        ];
+        AES128::cipher_blocks(&mut blocks, &key).expect("Error during AES128 execution");
+        assert_eq!(blocks, expected);
+    }
+
+    #[test]
+    fn aes256_decrypt_blocks() {
+        let mut blocks = [
+            [0xF3, 0xEE, 0xD1, 0xBD, 0xB5, 0xD2, 0xA0, 0x3C, 0x06, 0x4B, 0x5A, 0x7E, 0x3D, 0xB1, 0x81, 0xF8],
+            [0x59, 0x1C, 0xCB, 0x10, 0xD4, 0x10, 0xED, 0x26, 0xDC, 0x5B, 0xA7, 0x4A, 0x31, 0x36, 0x28, 0x70],
+            [0xB6, 0xED, 0x21, 0xB9, 0x9C, 0xA6, 0xF4, 0xF9, 0xF1, 0x53, 0xE7, 0xB1, 0xBE, 0xAF, 0xED, 0x1D],
+            [0x23, 0x30, 0x4B, 0x7A, 0x39, 0xF9, 0xF3, 0xFF, 0x06, 0x7D, 0x8D, 0x8F, 0x9E, 0x24, 0xEC, 0xC7],
+            [0xF3, 0xEE, 0xD1, 0xBD, 0xB5, 0xD2, 0xA0, 0x3C, 0x06, 0x4B, 0x5A, 0x7E, 0x3D, 0xB1, 0x81, 0xF8],
+        ];
+        let key = [0x60, 0x3D, 0xEB, 0x10, 0x15, 0xCA, 0x71, 0xBE, 0x2B, 0x73, 0xAE, 0xF0, 0x85, 0x7D, 0x77, 0x81, 0x1F, 0x35, 0x2C, 0x07, 0x3B, 0x61, 0x08, 0xD7, 0x2D, 0x98, 0x10, 0xA3, 0x09, 0x14, 0xDF, 0xF4];
+        let expected = [
+            [0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A],
+            [0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51],
+            [0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF],
+            [0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10],
+            [0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A],
+        ];
+        AES256::decipher_blocks(&mut blocks, &key).expect("Error during AES256 execution");
+        assert_eq!(blocks, expected);
+    }
 }
 
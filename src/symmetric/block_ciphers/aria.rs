@@ -0,0 +1,283 @@
+//! Definition of ARIA128, ARIA192 and ARIA256 (RFC 5794)
+//!
+//! ARIA is a substitution-permutation network of 12, 14 or 16 rounds on a 128-bit state:
+//!```text
+//!   P1 = FO(P, ek1),  P2 = FE(P1, ek2),  ...,  P(n-1) = FO(P(n-2), ek(n-1))
+//!   C  = SL2(P(n-1) xor ek(n)) xor ek(n+1)
+//!
+//!   FO(D, RK) = A(SL1(D xor RK)),  FE(D, RK) = A(SL2(D xor RK))
+//! ```
+//! `SL1` and `SL2` are substitution layers made of 4 S-boxes (2 of them are the AES S-box and its
+//! inverse), and `A` is an involutive binary diffusion layer. Decryption is the same function with
+//! the round keys in reverse order, passed through `A`.
+
+use crate::symmetric::block_ciphers::aes::{INV_SBOX as SB3, SBOX as SB1};
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+const SB2: [u8; 256] = [
+    0xE2, 0x4E, 0x54, 0xFC, 0x94, 0xC2, 0x4A, 0xCC, 0x62, 0x0D, 0x6A, 0x46, 0x3C, 0x4D, 0x8B, 0xD1,
+    0x5E, 0xFA, 0x64, 0xCB, 0xB4, 0x97, 0xBE, 0x2B, 0xBC, 0x77, 0x2E, 0x03, 0xD3, 0x19, 0x59, 0xC1,
+    0x1D, 0x06, 0x41, 0x6B, 0x55, 0xF0, 0x99, 0x69, 0xEA, 0x9C, 0x18, 0xAE, 0x63, 0xDF, 0xE7, 0xBB,
+    0x00, 0x73, 0x66, 0xFB, 0x96, 0x4C, 0x85, 0xE4, 0x3A, 0x09, 0x45, 0xAA, 0x0F, 0xEE, 0x10, 0xEB,
+    0x2D, 0x7F, 0xF4, 0x29, 0xAC, 0xCF, 0xAD, 0x91, 0x8D, 0x78, 0xC8, 0x95, 0xF9, 0x2F, 0xCE, 0xCD,
+    0x08, 0x7A, 0x88, 0x38, 0x5C, 0x83, 0x2A, 0x28, 0x47, 0xDB, 0xB8, 0xC7, 0x93, 0xA4, 0x12, 0x53,
+    0xFF, 0x87, 0x0E, 0x31, 0x36, 0x21, 0x58, 0x48, 0x01, 0x8E, 0x37, 0x74, 0x32, 0xCA, 0xE9, 0xB1,
+    0xB7, 0xAB, 0x0C, 0xD7, 0xC4, 0x56, 0x42, 0x26, 0x07, 0x98, 0x60, 0xD9, 0xB6, 0xB9, 0x11, 0x40,
+    0xEC, 0x20, 0x8C, 0xBD, 0xA0, 0xC9, 0x84, 0x04, 0x49, 0x23, 0xF1, 0x4F, 0x50, 0x1F, 0x13, 0xDC,
+    0xD8, 0xC0, 0x9E, 0x57, 0xE3, 0xC3, 0x7B, 0x65, 0x3B, 0x02, 0x8F, 0x3E, 0xE8, 0x25, 0x92, 0xE5,
+    0x15, 0xDD, 0xFD, 0x17, 0xA9, 0xBF, 0xD4, 0x9A, 0x7E, 0xC5, 0x39, 0x67, 0xFE, 0x76, 0x9D, 0x43,
+    0xA7, 0xE1, 0xD0, 0xF5, 0x68, 0xF2, 0x1B, 0x34, 0x70, 0x05, 0xA3, 0x8A, 0xD5, 0x79, 0x86, 0xA8,
+    0x30, 0xC6, 0x51, 0x4B, 0x1E, 0xA6, 0x27, 0xF6, 0x35, 0xD2, 0x6E, 0x24, 0x16, 0x82, 0x5F, 0xDA,
+    0xE6, 0x75, 0xA2, 0xEF, 0x2C, 0xB2, 0x1C, 0x9F, 0x5D, 0x6F, 0x80, 0x0A, 0x72, 0x44, 0x9B, 0x6C,
+    0x90, 0x0B, 0x5B, 0x33, 0x7D, 0x5A, 0x52, 0xF3, 0x61, 0xA1, 0xF7, 0xB0, 0xD6, 0x3F, 0x7C, 0x6D,
+    0xED, 0x14, 0xE0, 0xA5, 0x3D, 0x22, 0xB3, 0xF8, 0x89, 0xDE, 0x71, 0x1A, 0xAF, 0xBA, 0xB5, 0x81,
+];
+
+const SB4: [u8; 256] = [
+    0x30, 0x68, 0x99, 0x1B, 0x87, 0xB9, 0x21, 0x78, 0x50, 0x39, 0xDB, 0xE1, 0x72, 0x09, 0x62, 0x3C,
+    0x3E, 0x7E, 0x5E, 0x8E, 0xF1, 0xA0, 0xCC, 0xA3, 0x2A, 0x1D, 0xFB, 0xB6, 0xD6, 0x20, 0xC4, 0x8D,
+    0x81, 0x65, 0xF5, 0x89, 0xCB, 0x9D, 0x77, 0xC6, 0x57, 0x43, 0x56, 0x17, 0xD4, 0x40, 0x1A, 0x4D,
+    0xC0, 0x63, 0x6C, 0xE3, 0xB7, 0xC8, 0x64, 0x6A, 0x53, 0xAA, 0x38, 0x98, 0x0C, 0xF4, 0x9B, 0xED,
+    0x7F, 0x22, 0x76, 0xAF, 0xDD, 0x3A, 0x0B, 0x58, 0x67, 0x88, 0x06, 0xC3, 0x35, 0x0D, 0x01, 0x8B,
+    0x8C, 0xC2, 0xE6, 0x5F, 0x02, 0x24, 0x75, 0x93, 0x66, 0x1E, 0xE5, 0xE2, 0x54, 0xD8, 0x10, 0xCE,
+    0x7A, 0xE8, 0x08, 0x2C, 0x12, 0x97, 0x32, 0xAB, 0xB4, 0x27, 0x0A, 0x23, 0xDF, 0xEF, 0xCA, 0xD9,
+    0xB8, 0xFA, 0xDC, 0x31, 0x6B, 0xD1, 0xAD, 0x19, 0x49, 0xBD, 0x51, 0x96, 0xEE, 0xE4, 0xA8, 0x41,
+    0xDA, 0xFF, 0xCD, 0x55, 0x86, 0x36, 0xBE, 0x61, 0x52, 0xF8, 0xBB, 0x0E, 0x82, 0x48, 0x69, 0x9A,
+    0xE0, 0x47, 0x9E, 0x5C, 0x04, 0x4B, 0x34, 0x15, 0x79, 0x26, 0xA7, 0xDE, 0x29, 0xAE, 0x92, 0xD7,
+    0x84, 0xE9, 0xD2, 0xBA, 0x5D, 0xF3, 0xC5, 0xB0, 0xBF, 0xA4, 0x3B, 0x71, 0x44, 0x46, 0x2B, 0xFC,
+    0xEB, 0x6F, 0xD5, 0xF6, 0x14, 0xFE, 0x7C, 0x70, 0x5A, 0x7D, 0xFD, 0x2F, 0x18, 0x83, 0x16, 0xA5,
+    0x91, 0x1F, 0x05, 0x95, 0x74, 0xA9, 0xC1, 0x5B, 0x4A, 0x85, 0x6D, 0x13, 0x07, 0x4F, 0x4E, 0x45,
+    0xB2, 0x0F, 0xC9, 0x1C, 0xA6, 0xBC, 0xEC, 0x73, 0x90, 0x7B, 0xCF, 0x59, 0x8F, 0xA1, 0xF9, 0x2D,
+    0xF2, 0xB1, 0x00, 0x94, 0x37, 0x9F, 0xD0, 0x2E, 0x9C, 0x6E, 0x28, 0x3F, 0x80, 0xF0, 0x3D, 0xD3,
+    0x25, 0x8A, 0xB5, 0xE7, 0x42, 0xB3, 0xC7, 0xEA, 0xF7, 0x4C, 0x11, 0x33, 0x03, 0xA2, 0xAC, 0x60,
+];
+
+const C: [u128; 3] = [
+    0x517CC1B727220A94FE13ABE8FA9A6EE0,
+    0x6DB14ACC9E21C820FF28B1D5EF5DE2B0,
+    0xDB92371D2126E9700324977504E8C90E,
+];
+
+// substitution layer of odd rounds
+fn sl1(x: u128) -> u128 {
+    let mut y = x.to_be_bytes();
+    for i in (0..16).step_by(4) {
+        y[i] = SB1[y[i] as usize];
+        y[i + 1] = SB2[y[i + 1] as usize];
+        y[i + 2] = SB3[y[i + 2] as usize];
+        y[i + 3] = SB4[y[i + 3] as usize];
+    }
+    u128::from_be_bytes(y)
+}
+
+// substitution layer of even rounds (inverse of `sl1`)
+fn sl2(x: u128) -> u128 {
+    let mut y = x.to_be_bytes();
+    for i in (0..16).step_by(4) {
+        y[i] = SB3[y[i] as usize];
+        y[i + 1] = SB4[y[i + 1] as usize];
+        y[i + 2] = SB1[y[i + 2] as usize];
+        y[i + 3] = SB2[y[i + 3] as usize];
+    }
+    u128::from_be_bytes(y)
+}
+
+// diffusion layer
+fn a(x: u128) -> u128 {
+    let x = x.to_be_bytes();
+    u128::from_be_bytes([
+        x[3] ^ x[4] ^ x[6] ^ x[8] ^ x[9] ^ x[13] ^ x[14],
+        x[2] ^ x[5] ^ x[7] ^ x[8] ^ x[9] ^ x[12] ^ x[15],
+        x[1] ^ x[4] ^ x[6] ^ x[10] ^ x[11] ^ x[12] ^ x[15],
+        x[0] ^ x[5] ^ x[7] ^ x[10] ^ x[11] ^ x[13] ^ x[14],
+        x[0] ^ x[2] ^ x[5] ^ x[8] ^ x[11] ^ x[14] ^ x[15],
+        x[1] ^ x[3] ^ x[4] ^ x[9] ^ x[10] ^ x[14] ^ x[15],
+        x[0] ^ x[2] ^ x[7] ^ x[9] ^ x[10] ^ x[12] ^ x[13],
+        x[1] ^ x[3] ^ x[6] ^ x[8] ^ x[11] ^ x[12] ^ x[13],
+        x[0] ^ x[1] ^ x[4] ^ x[7] ^ x[10] ^ x[13] ^ x[15],
+        x[0] ^ x[1] ^ x[5] ^ x[6] ^ x[11] ^ x[12] ^ x[14],
+        x[2] ^ x[3] ^ x[5] ^ x[6] ^ x[8] ^ x[13] ^ x[15],
+        x[2] ^ x[3] ^ x[4] ^ x[7] ^ x[9] ^ x[12] ^ x[14],
+        x[1] ^ x[2] ^ x[6] ^ x[7] ^ x[9] ^ x[11] ^ x[12],
+        x[0] ^ x[3] ^ x[6] ^ x[7] ^ x[8] ^ x[10] ^ x[13],
+        x[0] ^ x[3] ^ x[4] ^ x[5] ^ x[9] ^ x[11] ^ x[14],
+        x[1] ^ x[2] ^ x[4] ^ x[5] ^ x[8] ^ x[10] ^ x[15],
+    ])
+}
+
+fn fo(d: u128, rk: u128) -> u128 {
+    a(sl1(d ^ rk))
+}
+
+fn fe(d: u128, rk: u128) -> u128 {
+    a(sl2(d ^ rk))
+}
+
+// Returns the `rounds + 1` encryption round keys (the other ones are left to zero).
+fn key_expansion(key: &[u8], rounds: usize) -> [u128; 17] {
+    let kl = u128::from_be_bytes(key[..16].try_into().unwrap());
+    let mut kr_bytes = [0; 16];
+    kr_bytes[..key.len() - 16].copy_from_slice(&key[16..]);
+    let kr = u128::from_be_bytes(kr_bytes);
+
+    // the order of the constants depends on the key size
+    let ck_offset = (key.len() - 16) / 8;
+    let ck = |i: usize| C[(ck_offset + i) % 3];
+
+    let w0 = kl;
+    let w1 = fo(w0, ck(0)) ^ kr;
+    let w2 = fe(w1, ck(1)) ^ w0;
+    let w3 = fo(w2, ck(2)) ^ w1;
+    let w = [w0, w1, w2, w3];
+
+    let mut round_keys = [0; 17];
+    for i in 0..=rounds {
+        let x = w[i % 4];
+        let y = w[(i + 1) % 4];
+        round_keys[i] = x ^ match i / 4 {
+            0 => y.rotate_right(19),
+            1 => y.rotate_right(31),
+            2 => y.rotate_left(61),
+            3 => y.rotate_left(31),
+            _ => y.rotate_left(19),
+        };
+    }
+    round_keys
+}
+
+// Decryption round keys: reversed encryption round keys, with the diffusion layer applied on the inner ones.
+fn reverse_round_keys(round_keys: &mut [u128; 17], rounds: usize) {
+    round_keys[..=rounds].reverse();
+    for rk in round_keys[1..rounds].iter_mut() {
+        *rk = a(*rk);
+    }
+}
+
+// Serves as encrypt AND as decrypt function. The choice is made by setting the encryption or decryption round keys.
+fn generic_aria(input: &[u8; 16], output: &mut [u8; 16], round_keys: &[u128; 17], rounds: usize) {
+    let mut state = u128::from_be_bytes(*input);
+
+    // rounds
+    for (i, rk) in round_keys[..rounds - 1].iter().enumerate() {
+        state = if i % 2 == 0 { fo(state, *rk) } else { fe(state, *rk) };
+    }
+
+    // final round
+    state = sl2(state ^ round_keys[rounds - 1]) ^ round_keys[rounds];
+
+    *output = state.to_be_bytes();
+}
+
+macro_rules! define_aria {
+    ( $aria_name:ident, $key_length:expr, $rounds:expr) => {
+        pub struct $aria_name {}
+
+        impl BlockCipher for $aria_name {
+            const KEY_SIZE: usize = $key_length;
+            const BLOCK_SIZE: usize = 16;
+
+            type KeyType = [u8; $key_length];
+            type BlockType = [u8; 16];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $rounds);
+                generic_aria(plaintext, ciphertext, &round_keys, $rounds);
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let mut round_keys = key_expansion(key, $rounds);
+                reverse_round_keys(&mut round_keys, $rounds);
+                generic_aria(ciphertext, plaintext, &round_keys, $rounds);
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $rounds);
+                for block in blocks.iter_mut() {
+                    let plaintext = *block;
+                    generic_aria(&plaintext, block, &round_keys, $rounds);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let mut round_keys = key_expansion(key, $rounds);
+                reverse_round_keys(&mut round_keys, $rounds);
+                for block in blocks.iter_mut() {
+                    let ciphertext = *block;
+                    generic_aria(&ciphertext, block, &round_keys, $rounds);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+define_aria!(ARIA128, 16, 12);
+define_aria!(ARIA192, 24, 14);
+define_aria!(ARIA256, 32, 16);
+
+#[cfg(test)]
+mod tests_aria {
+    use super::*;
+
+    // RFC 5794, appendix A
+    const PLAIN: [u8; 16] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+    const KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+    ];
+
+    #[test]
+    fn aria128_encrypt_decrypt() {
+        let key = KEY[..16].try_into().unwrap();
+        let expected = [0xD7, 0x18, 0xFB, 0xD6, 0xAB, 0x64, 0x4C, 0x73, 0x9D, 0xA9, 0x5F, 0x3B, 0xE6, 0x45, 0x17, 0x78];
+        let mut ciphertext = [0; 16];
+        ARIA128::cipher(&PLAIN, &mut ciphertext, &key).expect("Error during ARIA128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        ARIA128::decipher(&mut plain, &ciphertext, &key).expect("Error during ARIA128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn aria192_encrypt_decrypt() {
+        let key = KEY[..24].try_into().unwrap();
+        let expected = [0x26, 0x44, 0x9C, 0x18, 0x05, 0xDB, 0xE7, 0xAA, 0x25, 0xA4, 0x68, 0xCE, 0x26, 0x3A, 0x9E, 0x79];
+        let mut ciphertext = [0; 16];
+        ARIA192::cipher(&PLAIN, &mut ciphertext, &key).expect("Error during ARIA192 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        ARIA192::decipher(&mut plain, &ciphertext, &key).expect("Error during ARIA192 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn aria256_encrypt_decrypt() {
+        let expected = [0xF9, 0x2B, 0xD7, 0xC7, 0x9F, 0xB7, 0x2E, 0x2F, 0x2B, 0x8F, 0x80, 0xC1, 0x97, 0x2D, 0x24, 0xFC];
+        let mut ciphertext = [0; 16];
+        ARIA256::cipher(&PLAIN, &mut ciphertext, &KEY).expect("Error during ARIA256 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        ARIA256::decipher(&mut plain, &ciphertext, &KEY).expect("Error during ARIA256 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn aria256_encrypt_blocks() {
+        let mut blocks = [PLAIN, [0; 16]];
+        let expected = [
+            [0xF9, 0x2B, 0xD7, 0xC7, 0x9F, 0xB7, 0x2E, 0x2F, 0x2B, 0x8F, 0x80, 0xC1, 0x97, 0x2D, 0x24, 0xFC],
+            [0x62, 0x9D, 0xC7, 0xDD, 0x36, 0x63, 0x01, 0xB8, 0x5C, 0x65, 0xAD, 0x70, 0x83, 0x27, 0x24, 0xFA],
+        ];
+        ARIA256::cipher_blocks(&mut blocks, &KEY).expect("Error during ARIA256 execution");
+        assert_eq!(blocks, expected);
+
+        ARIA256::decipher_blocks(&mut blocks, &KEY).expect("Error during ARIA256 execution");
+        assert_eq!(blocks, [PLAIN, [0; 16]]);
+    }
+}
@@ -0,0 +1,286 @@
+//! Definition of Camellia128, Camellia192 and Camellia256 (RFC 3713)
+//!
+//! Camellia is a Feistel network on two 64-bit halves, with 18 rounds (128-bit keys) or 24 rounds
+//! (192 and 256-bit keys). An `FL`/`FL^-1` layer is inserted every 6 rounds:
+//!```text
+//!   D1 || D2 = P xor (kw1 || kw2)
+//!   6 rounds:  D2 = D2 xor F(D1, k1),  D1 = D1 xor F(D2, k2),  ...
+//!   D1 = FL(D1, ke1),  D2 = FL^-1(D2, ke2)
+//!   6 rounds ...
+//!   C = (D2 || D1) xor (kw3 || kw4)
+//! ```
+//! Decryption is the same function with the subkeys in reverse order.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+const SBOX1: [u8; 256] = [
+    0x70, 0x82, 0x2C, 0xEC, 0xB3, 0x27, 0xC0, 0xE5, 0xE4, 0x85, 0x57, 0x35, 0xEA, 0x0C, 0xAE, 0x41,
+    0x23, 0xEF, 0x6B, 0x93, 0x45, 0x19, 0xA5, 0x21, 0xED, 0x0E, 0x4F, 0x4E, 0x1D, 0x65, 0x92, 0xBD,
+    0x86, 0xB8, 0xAF, 0x8F, 0x7C, 0xEB, 0x1F, 0xCE, 0x3E, 0x30, 0xDC, 0x5F, 0x5E, 0xC5, 0x0B, 0x1A,
+    0xA6, 0xE1, 0x39, 0xCA, 0xD5, 0x47, 0x5D, 0x3D, 0xD9, 0x01, 0x5A, 0xD6, 0x51, 0x56, 0x6C, 0x4D,
+    0x8B, 0x0D, 0x9A, 0x66, 0xFB, 0xCC, 0xB0, 0x2D, 0x74, 0x12, 0x2B, 0x20, 0xF0, 0xB1, 0x84, 0x99,
+    0xDF, 0x4C, 0xCB, 0xC2, 0x34, 0x7E, 0x76, 0x05, 0x6D, 0xB7, 0xA9, 0x31, 0xD1, 0x17, 0x04, 0xD7,
+    0x14, 0x58, 0x3A, 0x61, 0xDE, 0x1B, 0x11, 0x1C, 0x32, 0x0F, 0x9C, 0x16, 0x53, 0x18, 0xF2, 0x22,
+    0xFE, 0x44, 0xCF, 0xB2, 0xC3, 0xB5, 0x7A, 0x91, 0x24, 0x08, 0xE8, 0xA8, 0x60, 0xFC, 0x69, 0x50,
+    0xAA, 0xD0, 0xA0, 0x7D, 0xA1, 0x89, 0x62, 0x97, 0x54, 0x5B, 0x1E, 0x95, 0xE0, 0xFF, 0x64, 0xD2,
+    0x10, 0xC4, 0x00, 0x48, 0xA3, 0xF7, 0x75, 0xDB, 0x8A, 0x03, 0xE6, 0xDA, 0x09, 0x3F, 0xDD, 0x94,
+    0x87, 0x5C, 0x83, 0x02, 0xCD, 0x4A, 0x90, 0x33, 0x73, 0x67, 0xF6, 0xF3, 0x9D, 0x7F, 0xBF, 0xE2,
+    0x52, 0x9B, 0xD8, 0x26, 0xC8, 0x37, 0xC6, 0x3B, 0x81, 0x96, 0x6F, 0x4B, 0x13, 0xBE, 0x63, 0x2E,
+    0xE9, 0x79, 0xA7, 0x8C, 0x9F, 0x6E, 0xBC, 0x8E, 0x29, 0xF5, 0xF9, 0xB6, 0x2F, 0xFD, 0xB4, 0x59,
+    0x78, 0x98, 0x06, 0x6A, 0xE7, 0x46, 0x71, 0xBA, 0xD4, 0x25, 0xAB, 0x42, 0x88, 0xA2, 0x8D, 0xFA,
+    0x72, 0x07, 0xB9, 0x55, 0xF8, 0xEE, 0xAC, 0x0A, 0x36, 0x49, 0x2A, 0x68, 0x3C, 0x38, 0xF1, 0xA4,
+    0x40, 0x28, 0xD3, 0x7B, 0xBB, 0xC9, 0x43, 0xC1, 0x15, 0xE3, 0xAD, 0xF4, 0x77, 0xC7, 0x80, 0x9E,
+];
+
+const SIGMA: [u64; 6] = [
+    0xA09E667F3BCC908B,
+    0xB67AE8584CAA73B2,
+    0xC6EF372FE94F82BE,
+    0x54FF53A5F1D36F1C,
+    0x10E527FADE682D1D,
+    0xB05688C2B3E6C1FD,
+];
+
+// SBOX2, SBOX3 and SBOX4 are derived from SBOX1
+fn sbox2(x: u8) -> u8 {
+    SBOX1[x as usize].rotate_left(1)
+}
+
+fn sbox3(x: u8) -> u8 {
+    SBOX1[x as usize].rotate_left(7)
+}
+
+fn sbox4(x: u8) -> u8 {
+    SBOX1[x.rotate_left(1) as usize]
+}
+
+fn function_f(input: u64, key: u64) -> u64 {
+    let x = (input ^ key).to_be_bytes();
+    let t = [
+        SBOX1[x[0] as usize], sbox2(x[1]), sbox3(x[2]), sbox4(x[3]),
+        sbox2(x[4]), sbox3(x[5]), sbox4(x[6]), SBOX1[x[7] as usize],
+    ];
+
+    u64::from_be_bytes([
+        t[0] ^ t[2] ^ t[3] ^ t[5] ^ t[6] ^ t[7],
+        t[0] ^ t[1] ^ t[3] ^ t[4] ^ t[6] ^ t[7],
+        t[0] ^ t[1] ^ t[2] ^ t[4] ^ t[5] ^ t[7],
+        t[1] ^ t[2] ^ t[3] ^ t[4] ^ t[5] ^ t[6],
+        t[0] ^ t[1] ^ t[5] ^ t[6] ^ t[7],
+        t[1] ^ t[2] ^ t[4] ^ t[6] ^ t[7],
+        t[2] ^ t[3] ^ t[4] ^ t[5] ^ t[7],
+        t[0] ^ t[3] ^ t[4] ^ t[5] ^ t[6],
+    ])
+}
+
+fn function_fl(input: u64, key: u64) -> u64 {
+    let (mut x1, mut x2) = ((input >> 32) as u32, input as u32);
+    let (k1, k2) = ((key >> 32) as u32, key as u32);
+    x2 ^= (x1 & k1).rotate_left(1);
+    x1 ^= x2 | k2;
+    (x1 as u64) << 32 | x2 as u64
+}
+
+fn function_fl_inv(input: u64, key: u64) -> u64 {
+    let (mut y1, mut y2) = ((input >> 32) as u32, input as u32);
+    let (k1, k2) = ((key >> 32) as u32, key as u32);
+    y1 ^= y2 | k2;
+    y2 ^= (y1 & k1).rotate_left(1);
+    (y1 as u64) << 32 | y2 as u64
+}
+
+// Subkeys of the longest schedule (256-bit keys). 128-bit keys only use the beginning of `k` and `ke`.
+struct SubKeys {
+    kw: [u64; 4],
+    k: [u64; 24],
+    ke: [u64; 6],
+}
+
+impl SubKeys {
+    // subkeys used for decryption, with `groups` groups of 6 rounds
+    fn reversed(mut self, groups: usize) -> Self {
+        self.kw = [self.kw[2], self.kw[3], self.kw[0], self.kw[1]];
+        self.k[..6 * groups].reverse();
+        self.ke[..2 * (groups - 1)].reverse();
+        self
+    }
+}
+
+fn key_schedule(key: &[u8]) -> SubKeys {
+    let kl = u128::from_be_bytes(key[..16].try_into().unwrap());
+    let kr = match key.len() {
+        16 => 0,
+        24 => {
+            let right = u64::from_be_bytes(key[16..24].try_into().unwrap());
+            (right as u128) << 64 | !right as u128
+        }
+        _ => u128::from_be_bytes(key[16..32].try_into().unwrap()),
+    };
+
+    // compute KA and KB
+    let feistel = |d: u128, sigma1: u64, sigma2: u64| {
+        let (mut d1, mut d2) = ((d >> 64) as u64, d as u64);
+        d2 ^= function_f(d1, sigma1);
+        d1 ^= function_f(d2, sigma2);
+        (d1 as u128) << 64 | d2 as u128
+    };
+    let ka = feistel(feistel(kl ^ kr, SIGMA[0], SIGMA[1]) ^ kl, SIGMA[2], SIGMA[3]);
+    let kb = feistel(ka ^ kr, SIGMA[4], SIGMA[5]);
+
+    // left and right halves of a rotated 128-bit key
+    let rot = |x: u128, n: u32| {
+        let y = x.rotate_left(n);
+        [(y >> 64) as u64, y as u64]
+    };
+
+    let mut subkeys = SubKeys { kw: [0; 4], k: [0; 24], ke: [0; 6] };
+    if key.len() == 16 {
+        subkeys.kw.copy_from_slice(&[rot(kl, 0), rot(ka, 111)].concat());
+        subkeys.k[..18].copy_from_slice(&[
+            rot(ka, 0), rot(kl, 15), rot(ka, 15), rot(kl, 45), [rot(ka, 45)[0], rot(kl, 60)[1]],
+            rot(ka, 60), rot(kl, 94), rot(ka, 94), rot(kl, 111),
+        ].concat());
+        subkeys.ke[..4].copy_from_slice(&[rot(ka, 30), rot(kl, 77)].concat());
+    } else {
+        subkeys.kw.copy_from_slice(&[rot(kl, 0), rot(kb, 111)].concat());
+        subkeys.k.copy_from_slice(&[
+            rot(kb, 0), rot(kr, 15), rot(ka, 15), rot(kb, 30), rot(kl, 45), rot(ka, 45),
+            rot(kr, 60), rot(kb, 60), rot(kl, 77), rot(kr, 94), rot(ka, 94), rot(kl, 111),
+        ].concat());
+        subkeys.ke.copy_from_slice(&[rot(kr, 30), rot(kl, 60), rot(ka, 77)].concat());
+    }
+    subkeys
+}
+
+// Serves as encrypt AND as decrypt function. The choice is made by setting the subkeys in normal or reversed order.
+fn generic_camellia(input: &[u8; 16], output: &mut [u8; 16], subkeys: &SubKeys, groups: usize) {
+    let mut d1 = u64::from_be_bytes(input[..8].try_into().unwrap()) ^ subkeys.kw[0];
+    let mut d2 = u64::from_be_bytes(input[8..].try_into().unwrap()) ^ subkeys.kw[1];
+
+    for group in 0..groups {
+        if group > 0 {
+            d1 = function_fl(d1, subkeys.ke[2 * group - 2]);
+            d2 = function_fl_inv(d2, subkeys.ke[2 * group - 1]);
+        }
+        for round in subkeys.k[6 * group..6 * group + 6].chunks_exact(2) {
+            d2 ^= function_f(d1, round[0]);
+            d1 ^= function_f(d2, round[1]);
+        }
+    }
+
+    output[..8].copy_from_slice(&(d2 ^ subkeys.kw[2]).to_be_bytes());
+    output[8..].copy_from_slice(&(d1 ^ subkeys.kw[3]).to_be_bytes());
+}
+
+macro_rules! define_camellia {
+    ( $camellia_name:ident, $key_length:expr, $groups:expr) => {
+        pub struct $camellia_name {}
+
+        impl BlockCipher for $camellia_name {
+            const KEY_SIZE: usize = $key_length;
+            const BLOCK_SIZE: usize = 16;
+
+            type KeyType = [u8; $key_length];
+            type BlockType = [u8; 16];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let subkeys = key_schedule(key);
+                generic_camellia(plaintext, ciphertext, &subkeys, $groups);
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let subkeys = key_schedule(key).reversed($groups);
+                generic_camellia(ciphertext, plaintext, &subkeys, $groups);
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let subkeys = key_schedule(key);
+                for block in blocks.iter_mut() {
+                    let plaintext = *block;
+                    generic_camellia(&plaintext, block, &subkeys, $groups);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let subkeys = key_schedule(key).reversed($groups);
+                for block in blocks.iter_mut() {
+                    let ciphertext = *block;
+                    generic_camellia(&ciphertext, block, &subkeys, $groups);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+define_camellia!(Camellia128, 16, 3);
+define_camellia!(Camellia192, 24, 4);
+define_camellia!(Camellia256, 32, 4);
+
+#[cfg(test)]
+mod tests_camellia {
+    use super::*;
+
+    // RFC 3713, appendix A
+    const PLAIN: [u8; 16] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+    const KEY: [u8; 32] = [
+        0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10,
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF,
+    ];
+
+    #[test]
+    fn camellia128_encrypt_decrypt() {
+        let key = KEY[..16].try_into().unwrap();
+        let expected = [0x67, 0x67, 0x31, 0x38, 0x54, 0x96, 0x69, 0x73, 0x08, 0x57, 0x06, 0x56, 0x48, 0xEA, 0xBE, 0x43];
+        let mut ciphertext = [0; 16];
+        Camellia128::cipher(&PLAIN, &mut ciphertext, &key).expect("Error during Camellia128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        Camellia128::decipher(&mut plain, &ciphertext, &key).expect("Error during Camellia128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn camellia192_encrypt_decrypt() {
+        let key = KEY[..24].try_into().unwrap();
+        let expected = [0xB4, 0x99, 0x34, 0x01, 0xB3, 0xE9, 0x96, 0xF8, 0x4E, 0xE5, 0xCE, 0xE7, 0xD7, 0x9B, 0x09, 0xB9];
+        let mut ciphertext = [0; 16];
+        Camellia192::cipher(&PLAIN, &mut ciphertext, &key).expect("Error during Camellia192 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        Camellia192::decipher(&mut plain, &ciphertext, &key).expect("Error during Camellia192 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn camellia256_encrypt_decrypt() {
+        let expected = [0x9A, 0xCC, 0x23, 0x7D, 0xFF, 0x16, 0xD7, 0x6C, 0x20, 0xEF, 0x7C, 0x91, 0x9E, 0x3A, 0x75, 0x09];
+        let mut ciphertext = [0; 16];
+        Camellia256::cipher(&PLAIN, &mut ciphertext, &KEY).expect("Error during Camellia256 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0; 16];
+        Camellia256::decipher(&mut plain, &ciphertext, &KEY).expect("Error during Camellia256 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn camellia128_encrypt_blocks() {
+        let key = KEY[..16].try_into().unwrap();
+        let mut blocks = [PLAIN, [0; 16]];
+        let expected = [
+            [0x67, 0x67, 0x31, 0x38, 0x54, 0x96, 0x69, 0x73, 0x08, 0x57, 0x06, 0x56, 0x48, 0xEA, 0xBE, 0x43],
+            [0xA6, 0x6B, 0x04, 0x40, 0x1E, 0xD5, 0xF1, 0xAA, 0x85, 0xDD, 0x78, 0xEF, 0x5A, 0x31, 0xAE, 0xB8],
+        ];
+        Camellia128::cipher_blocks(&mut blocks, &key).expect("Error during Camellia128 execution");
+        assert_eq!(blocks, expected);
+
+        Camellia128::decipher_blocks(&mut blocks, &key).expect("Error during Camellia128 execution");
+        assert_eq!(blocks, [PLAIN, [0; 16]]);
+    }
+}
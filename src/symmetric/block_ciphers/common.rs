@@ -8,13 +8,36 @@
 /// - `decipher`: decrypts the `ciphertext` and put the result in `plaintext`
 ///
 /// These methods can only treat a single block.
+///
+/// 2 other static methods treat several independent blocks, in place:
+/// - `cipher_blocks`: encrypts each block of `blocks`
+/// - `decipher_blocks`: decrypts each block of `blocks`
+///
+/// By default, they call `cipher`/`decipher` on each block. Ciphers can override them to compute the
+/// key schedule only once.
 pub trait BlockCipher {
     const KEY_SIZE: usize;
     const BLOCK_SIZE: usize;
-    
+
     type KeyType;  // &[u8; xxx]
-    type BlockType;  // &[u8; xxx]
-    
+    type BlockType: Copy;  // &[u8; xxx]
+
     fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str>;
     fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str>;
-}
\ No newline at end of file
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            Self::cipher(&plaintext, block, key)?;
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            Self::decipher(block, &ciphertext, key)?;
+        }
+        Ok(())
+    }
+}
@@ -258,6 +258,15 @@ fn generic_des(input: &[u8;8], output: &mut [u8; 8], expanded_key: &[[u8; 48]; 1
     return Ok(());
 }
 
+// Applies `generic_des` on each block, the key being expanded only once by the caller.
+fn generic_des_blocks(blocks: &mut [[u8; 8]], expanded_key: &[[u8; 48]; 16]) -> Result<(), &'static str> {
+    for block in blocks.iter_mut() {
+        let input = *block;
+        generic_des(&input, block, expanded_key)?;
+    }
+    Ok(())
+}
+
 impl BlockCipher for DES{
     const KEY_SIZE: usize = 8;
     const BLOCK_SIZE: usize = 8;
@@ -275,6 +284,17 @@ impl BlockCipher for DES{
         expanded_key.reverse();
         return generic_des(ciphertext, plaintext, &expanded_key);
     }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let expanded_key = key_expansion(key);
+        generic_des_blocks(blocks, &expanded_key)
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let mut expanded_key = key_expansion(key);
+        expanded_key.reverse();
+        generic_des_blocks(blocks, &expanded_key)
+    }
 }
 
 
@@ -302,4 +322,23 @@ mod tests_des {
         DES::decipher(&mut plain, &ciphertext, &key).expect("Error during DES execution");
         assert_eq!(plain, expected);
     }
+
+    #[test]
+    fn des_encrypt_blocks() {
+        // 4 blocks processed together + 1 remaining block
+        let mut blocks = [[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]; 5];
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+        let expected = [[0x85, 0xE8, 0x13, 0x54, 0x0F, 0x0A, 0xB4, 0x05]; 5];
+        DES::cipher_blocks(&mut blocks, &key).expect("Error during DES execution");
+        assert_eq!(blocks, expected);
+    }
+
+    #[test]
+    fn des_decrypt_blocks() {
+        let mut blocks = [[0x85, 0xE8, 0x13, 0x54, 0x0F, 0x0A, 0xB4, 0x05]; 6];
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+        let expected = [[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]; 6];
+        DES::decipher_blocks(&mut blocks, &key).expect("Error during DES execution");
+        assert_eq!(blocks, expected);
+    }
 }
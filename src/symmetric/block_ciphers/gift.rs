@@ -0,0 +1,202 @@
+//! Definition of GIFT-64 and GIFT-128 (Banik et al., CHES 2017), a lightweight SPN derived from PRESENT
+//!
+//!```text
+//!   for i in 0..rounds:
+//!     state = SubCells(state)      4-bit S-box on each nibble
+//!     state = PermBits(state)      bit permutation, keeping each bit in its position inside a nibble
+//!     state = state xor RK(i) xor constants(i)
+//! ```
+//! The round key is made of 2 bits per nibble, extracted from the 128-bit key state which is updated by
+//! rotating its 16-bit words. The round constants come from a 6-bit LFSR.
+//!
+//! GIFT is generic over the block size and the number of rounds, for cryptanalysis experiments.
+//! [GIFT64] (28 rounds) and [GIFT128] (40 rounds) are the standard versions.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct GIFT<const BLOCK_BITS: usize, const ROUNDS: usize> {}
+
+pub type GIFT64 = GIFT<64, 28>;
+pub type GIFT128 = GIFT<128, 40>;
+
+const SBOX: [u8; 16] = [0x1, 0xA, 0x4, 0xC, 0x6, 0xF, 0x3, 0x9, 0x2, 0xD, 0xB, 0x7, 0x5, 0x0, 0x8, 0xE];
+const INV_SBOX: [u8; 16] = [0xD, 0x0, 0x8, 0x6, 0x2, 0xC, 0x4, 0xB, 0xE, 0x7, 0x1, 0xA, 0x3, 0x9, 0xF, 0x5];
+
+// both variants store their state in a u128, GIFT-64 only using the 64 least significant bits
+
+fn sub_cells(state: u128, sbox: &[u8; 16], block_bits: usize) -> u128 {
+    (0..block_bits / 4).fold(0, |acc, i| acc | (sbox[(state >> (4 * i) & 0xF) as usize] as u128) << (4 * i))
+}
+
+// bit i moves to position P(i)
+fn permutation(i: usize, block_bits: usize) -> usize {
+    4 * (i / 16) + block_bits / 4 * ((3 * (i % 16 / 4) + i % 4) % 4) + i % 4
+}
+
+fn perm_bits(state: u128, block_bits: usize) -> u128 {
+    (0..block_bits).fold(0, |acc, i| acc | (state >> i & 1) << permutation(i, block_bits))
+}
+
+fn inv_perm_bits(state: u128, block_bits: usize) -> u128 {
+    (0..block_bits).fold(0, |acc, i| acc | (state >> permutation(i, block_bits) & 1) << i)
+}
+
+// returns, for each round, the value xored to the state (round key and round constant)
+fn key_expansion(key: &[u8; 16], block_bits: usize, rounds: usize) -> Vec<u128> {
+    // k[0] is the least significant word of the key
+    let mut k: [u16; 8] = core::array::from_fn(|i| u16::from_be_bytes([key[14 - 2 * i], key[15 - 2 * i]]));
+    let mut constant = 0u8;
+
+    let mut round_keys = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let mut rk = 0;
+        if block_bits == 64 {
+            for i in 0..16 {
+                rk |= ((k[1] >> i & 1) as u128) << (4 * i + 1) | ((k[0] >> i & 1) as u128) << (4 * i);
+            }
+        } else {
+            let u = (k[5] as u32) << 16 | k[4] as u32;
+            let v = (k[1] as u32) << 16 | k[0] as u32;
+            for i in 0..32 {
+                rk |= ((u >> i & 1) as u128) << (4 * i + 2) | ((v >> i & 1) as u128) << (4 * i + 1);
+            }
+        }
+
+        constant = (constant << 1 | ((constant >> 5) ^ (constant >> 4) ^ 1) & 1) & 0x3F;
+        rk ^= 1 << (block_bits - 1);
+        for j in 0..6 {
+            rk ^= ((constant >> j & 1) as u128) << (4 * j + 3);
+        }
+        round_keys.push(rk);
+
+        k = [k[2], k[3], k[4], k[5], k[6], k[7], k[0].rotate_right(12), k[1].rotate_right(2)];
+    }
+    round_keys
+}
+
+fn encrypt_state(mut state: u128, round_keys: &[u128], block_bits: usize) -> u128 {
+    for rk in round_keys {
+        state = perm_bits(sub_cells(state, &SBOX, block_bits), block_bits) ^ rk;
+    }
+    state
+}
+
+fn decrypt_state(mut state: u128, round_keys: &[u128], block_bits: usize) -> u128 {
+    for rk in round_keys.iter().rev() {
+        state = sub_cells(inv_perm_bits(state ^ rk, block_bits), &INV_SBOX, block_bits);
+    }
+    state
+}
+
+macro_rules! define_gift {
+    ( $block_bits:expr, $uint:ty ) => {
+        impl<const ROUNDS: usize> BlockCipher for GIFT<$block_bits, ROUNDS> {
+            const KEY_SIZE: usize = 16;
+            const BLOCK_SIZE: usize = $block_bits / 8;
+
+            type KeyType = [u8; 16];
+            type BlockType = [u8; $block_bits / 8];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits, ROUNDS);
+                let state = encrypt_state(<$uint>::from_be_bytes(*plaintext) as u128, &round_keys, $block_bits);
+                *ciphertext = (state as $uint).to_be_bytes();
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits, ROUNDS);
+                let state = decrypt_state(<$uint>::from_be_bytes(*ciphertext) as u128, &round_keys, $block_bits);
+                *plaintext = (state as $uint).to_be_bytes();
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let state = encrypt_state(<$uint>::from_be_bytes(*block) as u128, &round_keys, $block_bits);
+                    *block = (state as $uint).to_be_bytes();
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let state = decrypt_state(<$uint>::from_be_bytes(*block) as u128, &round_keys, $block_bits);
+                    *block = (state as $uint).to_be_bytes();
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+define_gift!(64, u64);
+define_gift!(128, u128);
+
+#[cfg(test)]
+mod tests_gift {
+    use super::*;
+
+    // test vectors from the designers' reference implementation
+    const KEY: [u8; 16] = [0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+
+    #[test]
+    fn gift64_encrypt_decrypt() {
+        let vectors = [
+            ([0x00; 16], [0x00; 8], [0xF6, 0x2B, 0xC3, 0xEF, 0x34, 0xF7, 0x75, 0xAC]),
+            (KEY, [0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10], [0xC1, 0xB7, 0x1F, 0x66, 0x16, 0x0F, 0xF5, 0x87]),
+            (
+                [0xBD, 0x91, 0x73, 0x1E, 0xB6, 0xBC, 0x27, 0x13, 0xA1, 0xF9, 0xF6, 0xFF, 0xC7, 0x50, 0x44, 0xE7],
+                [0xC4, 0x50, 0xC7, 0x72, 0x7A, 0x9B, 0x8A, 0x7D],
+                [0xE3, 0x27, 0x28, 0x85, 0xFA, 0x94, 0xBA, 0x8B],
+            ),
+        ];
+
+        for (key, plain, expected) in vectors {
+            let mut ciphertext = [0; 8];
+            GIFT64::cipher(&plain, &mut ciphertext, &key).expect("Error during GIFT64 execution");
+            assert_eq!(ciphertext, expected);
+
+            let mut decrypted = [0; 8];
+            GIFT64::decipher(&mut decrypted, &ciphertext, &key).expect("Error during GIFT64 execution");
+            assert_eq!(decrypted, plain);
+        }
+    }
+
+    #[test]
+    fn gift128_encrypt_decrypt() {
+        let vectors = [
+            ([0x00; 16], [0x00; 16], [0xCD, 0x0B, 0xD7, 0x38, 0x38, 0x8A, 0xD3, 0xF6, 0x68, 0xB1, 0x5A, 0x36, 0xCE, 0xB6, 0xFF, 0x92]),
+            (KEY, KEY, [0x84, 0x22, 0x24, 0x1A, 0x6D, 0xBF, 0x5A, 0x93, 0x46, 0xAF, 0x46, 0x84, 0x09, 0xEE, 0x01, 0x52]),
+            (
+                [0xD0, 0xF5, 0xC5, 0x9A, 0x77, 0x00, 0xD3, 0xE7, 0x99, 0x02, 0x8F, 0xA9, 0xF9, 0x0A, 0xD8, 0x37],
+                [0xE3, 0x9C, 0x14, 0x1F, 0xA5, 0x7D, 0xBA, 0x43, 0xF0, 0x8A, 0x85, 0xB6, 0xA9, 0x1F, 0x86, 0xC1],
+                [0x13, 0xED, 0xE6, 0x7C, 0xBD, 0xCC, 0x3D, 0xBF, 0x40, 0x0A, 0x62, 0xD6, 0x97, 0x72, 0x65, 0xEA],
+            ),
+        ];
+
+        for (key, plain, expected) in vectors {
+            let mut blocks = [plain];
+            GIFT128::cipher_blocks(&mut blocks, &key).expect("Error during GIFT128 execution");
+            assert_eq!(blocks, [expected]);
+
+            GIFT128::decipher_blocks(&mut blocks, &key).expect("Error during GIFT128 execution");
+            assert_eq!(blocks, [plain]);
+        }
+    }
+
+    #[test]
+    fn gift_reduced_rounds() {
+        let plain = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+
+        let mut ciphertext = [0; 8];
+        GIFT::<64, 10>::cipher(&plain, &mut ciphertext, &KEY).expect("Error during GIFT execution");
+        assert_ne!(ciphertext, plain);
+
+        let mut decrypted = [0; 8];
+        GIFT::<64, 10>::decipher(&mut decrypted, &ciphertext, &KEY).expect("Error during GIFT execution");
+        assert_eq!(decrypted, plain);
+    }
+}
@@ -0,0 +1,181 @@
+//! Definition of IDEA (Lai and Massey, 1991)
+//!
+//! IDEA works on four 16-bit words and mixes three incompatible group operations: xor, addition modulo
+//! 2^16 (+) and multiplication modulo 2^16 + 1 (*, where 0 stands for 2^16). Each of the 8 rounds uses
+//! six subkeys:
+//!```text
+//!   a = X1 * Z1,   b = X2 + Z2,   c = X3 + Z3,   d = X4 * Z4
+//!   t = ((a xor c) * Z5 + (b xor d)) * Z6
+//!   u = (a xor c) * Z5 + t
+//!   X1, X2, X3, X4 = a xor t, c xor t, b xor u, d xor u
+//!
+//!   output: X1 * Z1,  X3 + Z2,  X2 + Z3,  X4 * Z4
+//! ```
+//! The 52 subkeys are taken 8 by 8 from the 128-bit key, rotated left by 25 bits between each batch.
+//! Decryption is the same function, with the inverted subkeys in reverse order.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct IDEA {}
+
+type SubKeys = [[u16; 6]; 9];
+
+// multiplication modulo 2^16 + 1, 0 stands for 2^16
+fn mul(a: u16, b: u16) -> u16 {
+    let a = if a == 0 { 0x10000 } else { a as u64 };
+    let b = if b == 0 { 0x10000 } else { b as u64 };
+    (a * b % 0x10001) as u16
+}
+
+// multiplicative inverse modulo 2^16 + 1, computed as x^(2^16 - 1)
+fn mul_inv(x: u16) -> u16 {
+    let mut ret = 1;
+    let mut base = x;
+    let mut e = 0xFFFFu32;
+    while e > 0 {
+        if e & 1 == 1 {
+            ret = mul(ret, base);
+        }
+        base = mul(base, base);
+        e >>= 1;
+    }
+    ret
+}
+
+// the last round only uses the first 4 subkeys
+fn key_expansion(key: &[u8; 16]) -> SubKeys {
+    let mut k = u128::from_be_bytes(*key);
+    let mut z = [0; 52];
+    for (i, zi) in z.iter_mut().enumerate() {
+        if i > 0 && i % 8 == 0 {
+            k = k.rotate_left(25);
+        }
+        *zi = (k >> (112 - 16 * (i % 8))) as u16;
+    }
+
+    let mut subkeys = [[0; 6]; 9];
+    for (i, zi) in z.iter().enumerate() {
+        subkeys[i / 6][i % 6] = *zi;
+    }
+    subkeys
+}
+
+fn invert_subkeys(z: &SubKeys) -> SubKeys {
+    let mut dk = [[0; 6]; 9];
+    for r in 0..9 {
+        let ek = &z[8 - r];
+        // the additive subkeys are swapped for all rounds but the first and the last
+        let (k1, k2) = if r == 0 || r == 8 { (ek[1], ek[2]) } else { (ek[2], ek[1]) };
+        dk[r][0] = mul_inv(ek[0]);
+        dk[r][1] = k1.wrapping_neg();
+        dk[r][2] = k2.wrapping_neg();
+        dk[r][3] = mul_inv(ek[3]);
+        if r < 8 {
+            dk[r][4] = z[7 - r][4];
+            dk[r][5] = z[7 - r][5];
+        }
+    }
+    dk
+}
+
+// Serves as encrypt AND as decrypt function. The choice is made by giving the normal or inverted subkeys.
+fn generic_idea(input: &[u8; 8], output: &mut [u8; 8], subkeys: &SubKeys) {
+    let mut x: [u16; 4] = core::array::from_fn(|i| u16::from_be_bytes([input[2 * i], input[2 * i + 1]]));
+
+    for k in &subkeys[..8] {
+        let a = mul(x[0], k[0]);
+        let b = x[1].wrapping_add(k[1]);
+        let c = x[2].wrapping_add(k[2]);
+        let d = mul(x[3], k[3]);
+        let e = mul(a ^ c, k[4]);
+        let t = mul(e.wrapping_add(b ^ d), k[5]);
+        let u = e.wrapping_add(t);
+        x = [a ^ t, c ^ t, b ^ u, d ^ u];
+    }
+
+    // output transformation, undoes the last swap
+    let k = &subkeys[8];
+    let y = [mul(x[0], k[0]), x[2].wrapping_add(k[1]), x[1].wrapping_add(k[2]), mul(x[3], k[3])];
+    for i in 0..4 {
+        output[2 * i..2 * i + 2].copy_from_slice(&y[i].to_be_bytes());
+    }
+}
+
+impl BlockCipher for IDEA {
+    const KEY_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 8;
+
+    type KeyType = [u8; 16];
+    type BlockType = [u8; 8];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        generic_idea(plaintext, ciphertext, &key_expansion(key));
+        Ok(())
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        generic_idea(ciphertext, plaintext, &invert_subkeys(&key_expansion(key)));
+        Ok(())
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let subkeys = key_expansion(key);
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            generic_idea(&plaintext, block, &subkeys);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let subkeys = invert_subkeys(&key_expansion(key));
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            generic_idea(&ciphertext, block, &subkeys);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_idea {
+    use super::*;
+
+    // vector from the original paper
+    const KEY: [u8; 16] = [0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, 0x00, 0x05, 0x00, 0x06, 0x00, 0x07, 0x00, 0x08];
+    const PLAIN: [u8; 8] = [0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03];
+    const CIPHER: [u8; 8] = [0x11, 0xFB, 0xED, 0x2B, 0x01, 0x98, 0x6D, 0xE5];
+
+    #[test]
+    fn idea_encrypt() {
+        let mut ciphertext = [0; 8];
+        IDEA::cipher(&PLAIN, &mut ciphertext, &KEY).expect("Error during IDEA execution");
+        assert_eq!(ciphertext, CIPHER);
+
+        // checked with Python cryptography
+        IDEA::cipher(&[0; 8], &mut ciphertext, &[0; 16]).expect("Error during IDEA execution");
+        assert_eq!(ciphertext, [0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn idea_decrypt() {
+        let mut plain = [0; 8];
+        IDEA::decipher(&mut plain, &CIPHER, &KEY).expect("Error during IDEA execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn idea_encrypt_decrypt_blocks() {
+        // checked with Python cryptography
+        let key = [0x2B, 0xD6, 0x45, 0x9F, 0x82, 0xC5, 0xB3, 0x00, 0x95, 0x2C, 0x49, 0x10, 0x48, 0x81, 0xFF, 0x48];
+        let plain = [[0xEA, 0x02, 0x47, 0x14, 0xAD, 0x5C, 0x4D, 0x84], PLAIN];
+        let expected = [[0xC8, 0xFB, 0x51, 0xD3, 0x51, 0x66, 0x27, 0xA8], [0x10, 0x84, 0xD4, 0x47, 0x27, 0x84, 0x4A, 0x95]];
+
+        let mut blocks = plain;
+        IDEA::cipher_blocks(&mut blocks, &key).expect("Error during IDEA execution");
+        assert_eq!(blocks, expected);
+
+        IDEA::decipher_blocks(&mut blocks, &key).expect("Error during IDEA execution");
+        assert_eq!(blocks, plain);
+    }
+}
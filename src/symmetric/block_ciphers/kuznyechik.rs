@@ -0,0 +1,240 @@
+//! Definition of Kuznyechik (GOST R 34.12-2015, RFC 7801), the 128-bit block cipher of the Russian standard
+//!
+//! Kuznyechik is an SPN of 9 rounds, plus a final key addition:
+//!```text
+//!   for i in 1..=9:  a = L(S(a xor K(i)))
+//!   a = a xor K(10)
+//!
+//!   S: 8-bit S-box pi on each byte
+//!   L: 16 iterations of R(a15..a0) = l(a15..a0) || a15..a1, l being a linear form over GF(2^8)
+//! ```
+//! The first two round keys are the halves of the 256-bit key, the others are computed by a Feistel
+//! network using the same round function, with the constants L(1) .. L(32).
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct Kuznyechik {}
+
+// also used by Streebog
+pub(crate) const PI: [u8; 256] = [
+    0xFC, 0xEE, 0xDD, 0x11, 0xCF, 0x6E, 0x31, 0x16, 0xFB, 0xC4, 0xFA, 0xDA, 0x23, 0xC5, 0x04, 0x4D,
+    0xE9, 0x77, 0xF0, 0xDB, 0x93, 0x2E, 0x99, 0xBA, 0x17, 0x36, 0xF1, 0xBB, 0x14, 0xCD, 0x5F, 0xC1,
+    0xF9, 0x18, 0x65, 0x5A, 0xE2, 0x5C, 0xEF, 0x21, 0x81, 0x1C, 0x3C, 0x42, 0x8B, 0x01, 0x8E, 0x4F,
+    0x05, 0x84, 0x02, 0xAE, 0xE3, 0x6A, 0x8F, 0xA0, 0x06, 0x0B, 0xED, 0x98, 0x7F, 0xD4, 0xD3, 0x1F,
+    0xEB, 0x34, 0x2C, 0x51, 0xEA, 0xC8, 0x48, 0xAB, 0xF2, 0x2A, 0x68, 0xA2, 0xFD, 0x3A, 0xCE, 0xCC,
+    0xB5, 0x70, 0x0E, 0x56, 0x08, 0x0C, 0x76, 0x12, 0xBF, 0x72, 0x13, 0x47, 0x9C, 0xB7, 0x5D, 0x87,
+    0x15, 0xA1, 0x96, 0x29, 0x10, 0x7B, 0x9A, 0xC7, 0xF3, 0x91, 0x78, 0x6F, 0x9D, 0x9E, 0xB2, 0xB1,
+    0x32, 0x75, 0x19, 0x3D, 0xFF, 0x35, 0x8A, 0x7E, 0x6D, 0x54, 0xC6, 0x80, 0xC3, 0xBD, 0x0D, 0x57,
+    0xDF, 0xF5, 0x24, 0xA9, 0x3E, 0xA8, 0x43, 0xC9, 0xD7, 0x79, 0xD6, 0xF6, 0x7C, 0x22, 0xB9, 0x03,
+    0xE0, 0x0F, 0xEC, 0xDE, 0x7A, 0x94, 0xB0, 0xBC, 0xDC, 0xE8, 0x28, 0x50, 0x4E, 0x33, 0x0A, 0x4A,
+    0xA7, 0x97, 0x60, 0x73, 0x1E, 0x00, 0x62, 0x44, 0x1A, 0xB8, 0x38, 0x82, 0x64, 0x9F, 0x26, 0x41,
+    0xAD, 0x45, 0x46, 0x92, 0x27, 0x5E, 0x55, 0x2F, 0x8C, 0xA3, 0xA5, 0x7D, 0x69, 0xD5, 0x95, 0x3B,
+    0x07, 0x58, 0xB3, 0x40, 0x86, 0xAC, 0x1D, 0xF7, 0x30, 0x37, 0x6B, 0xE4, 0x88, 0xD9, 0xE7, 0x89,
+    0xE1, 0x1B, 0x83, 0x49, 0x4C, 0x3F, 0xF8, 0xFE, 0x8D, 0x53, 0xAA, 0x90, 0xCA, 0xD8, 0x85, 0x61,
+    0x20, 0x71, 0x67, 0xA4, 0x2D, 0x2B, 0x09, 0x5B, 0xCB, 0x9B, 0x25, 0xD0, 0xBE, 0xE5, 0x6C, 0x52,
+    0x59, 0xA6, 0x74, 0xD2, 0xE6, 0xF4, 0xB4, 0xC0, 0xD1, 0x66, 0xAF, 0xC2, 0x39, 0x4B, 0x63, 0xB6,
+];
+
+const INV_PI: [u8; 256] = [
+    0xA5, 0x2D, 0x32, 0x8F, 0x0E, 0x30, 0x38, 0xC0, 0x54, 0xE6, 0x9E, 0x39, 0x55, 0x7E, 0x52, 0x91,
+    0x64, 0x03, 0x57, 0x5A, 0x1C, 0x60, 0x07, 0x18, 0x21, 0x72, 0xA8, 0xD1, 0x29, 0xC6, 0xA4, 0x3F,
+    0xE0, 0x27, 0x8D, 0x0C, 0x82, 0xEA, 0xAE, 0xB4, 0x9A, 0x63, 0x49, 0xE5, 0x42, 0xE4, 0x15, 0xB7,
+    0xC8, 0x06, 0x70, 0x9D, 0x41, 0x75, 0x19, 0xC9, 0xAA, 0xFC, 0x4D, 0xBF, 0x2A, 0x73, 0x84, 0xD5,
+    0xC3, 0xAF, 0x2B, 0x86, 0xA7, 0xB1, 0xB2, 0x5B, 0x46, 0xD3, 0x9F, 0xFD, 0xD4, 0x0F, 0x9C, 0x2F,
+    0x9B, 0x43, 0xEF, 0xD9, 0x79, 0xB6, 0x53, 0x7F, 0xC1, 0xF0, 0x23, 0xE7, 0x25, 0x5E, 0xB5, 0x1E,
+    0xA2, 0xDF, 0xA6, 0xFE, 0xAC, 0x22, 0xF9, 0xE2, 0x4A, 0xBC, 0x35, 0xCA, 0xEE, 0x78, 0x05, 0x6B,
+    0x51, 0xE1, 0x59, 0xA3, 0xF2, 0x71, 0x56, 0x11, 0x6A, 0x89, 0x94, 0x65, 0x8C, 0xBB, 0x77, 0x3C,
+    0x7B, 0x28, 0xAB, 0xD2, 0x31, 0xDE, 0xC4, 0x5F, 0xCC, 0xCF, 0x76, 0x2C, 0xB8, 0xD8, 0x2E, 0x36,
+    0xDB, 0x69, 0xB3, 0x14, 0x95, 0xBE, 0x62, 0xA1, 0x3B, 0x16, 0x66, 0xE9, 0x5C, 0x6C, 0x6D, 0xAD,
+    0x37, 0x61, 0x4B, 0xB9, 0xE3, 0xBA, 0xF1, 0xA0, 0x85, 0x83, 0xDA, 0x47, 0xC5, 0xB0, 0x33, 0xFA,
+    0x96, 0x6F, 0x6E, 0xC2, 0xF6, 0x50, 0xFF, 0x5D, 0xA9, 0x8E, 0x17, 0x1B, 0x97, 0x7D, 0xEC, 0x58,
+    0xF7, 0x1F, 0xFB, 0x7C, 0x09, 0x0D, 0x7A, 0x67, 0x45, 0x87, 0xDC, 0xE8, 0x4F, 0x1D, 0x4E, 0x04,
+    0xEB, 0xF8, 0xF3, 0x3E, 0x3D, 0xBD, 0x8A, 0x88, 0xDD, 0xCD, 0x0B, 0x13, 0x98, 0x02, 0x93, 0x80,
+    0x90, 0xD0, 0x24, 0x34, 0xCB, 0xED, 0xF4, 0xCE, 0x99, 0x10, 0x44, 0x40, 0x92, 0x3A, 0x01, 0x26,
+    0x12, 0x1A, 0x48, 0x68, 0xF5, 0x81, 0x8B, 0xC7, 0xD6, 0x20, 0x0A, 0x08, 0x00, 0x4C, 0xD7, 0x74,
+];
+
+// coefficients of the linear form l, from a15 to a0
+const LC: [u8; 16] = [148, 32, 133, 16, 194, 192, 1, 251, 1, 192, 194, 16, 133, 32, 148, 1];
+
+type Block = [u8; 16];
+
+// multiplication in GF(2^8) modulo x^8 + x^7 + x^6 + x + 1
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut ret = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            ret ^= a;
+        }
+        a = if a & 0x80 != 0 { (a << 1) ^ 0xC3 } else { a << 1 };
+        b >>= 1;
+    }
+    ret
+}
+
+fn l(a: &Block) -> u8 {
+    a.iter().zip(LC).fold(0, |acc, (&x, c)| acc ^ gf_mul(x, c))
+}
+
+fn s(a: &mut Block) {
+    a.iter_mut().for_each(|x| *x = PI[*x as usize]);
+}
+
+fn s_inv(a: &mut Block) {
+    a.iter_mut().for_each(|x| *x = INV_PI[*x as usize]);
+}
+
+// the byte a15 is stored first, as in the standard's notation
+fn linear(a: &mut Block) {
+    for _ in 0..16 {
+        let b = l(a);
+        a.copy_within(0..15, 1);
+        a[0] = b;
+    }
+}
+
+fn linear_inv(a: &mut Block) {
+    for _ in 0..16 {
+        a.rotate_left(1);
+        a[15] = l(a);
+    }
+}
+
+fn xor(a: &mut Block, b: &Block) {
+    a.iter_mut().zip(b).for_each(|(x, y)| *x ^= y);
+}
+
+fn key_expansion(key: &[u8; 32]) -> [Block; 10] {
+    let mut round_keys = [[0; 16]; 10];
+    let mut a1: Block = key[..16].try_into().unwrap();
+    let mut a0: Block = key[16..].try_into().unwrap();
+    round_keys[0] = a1;
+    round_keys[1] = a0;
+
+    for i in 0..32 {
+        let mut c = [0; 16];
+        c[15] = i as u8 + 1;
+        linear(&mut c);
+
+        // Feistel round F[C](a1, a0) = (LSX[C](a1) xor a0, a1)
+        let mut f = a1;
+        xor(&mut f, &c);
+        s(&mut f);
+        linear(&mut f);
+        xor(&mut f, &a0);
+        (a1, a0) = (f, a1);
+
+        if i % 8 == 7 {
+            round_keys[2 + 2 * (i / 8)] = a1;
+            round_keys[3 + 2 * (i / 8)] = a0;
+        }
+    }
+    round_keys
+}
+
+fn encrypt_block(input: &Block, output: &mut Block, round_keys: &[Block; 10]) {
+    *output = *input;
+    for k in &round_keys[..9] {
+        xor(output, k);
+        s(output);
+        linear(output);
+    }
+    xor(output, &round_keys[9]);
+}
+
+fn decrypt_block(input: &Block, output: &mut Block, round_keys: &[Block; 10]) {
+    *output = *input;
+    xor(output, &round_keys[9]);
+    for k in round_keys[..9].iter().rev() {
+        linear_inv(output);
+        s_inv(output);
+        xor(output, k);
+    }
+}
+
+impl BlockCipher for Kuznyechik {
+    const KEY_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 16;
+
+    type KeyType = [u8; 32];
+    type BlockType = [u8; 16];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        encrypt_block(plaintext, ciphertext, &key_expansion(key));
+        Ok(())
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        decrypt_block(ciphertext, plaintext, &key_expansion(key));
+        Ok(())
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let round_keys = key_expansion(key);
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            encrypt_block(&plaintext, block, &round_keys);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let round_keys = key_expansion(key);
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            decrypt_block(&ciphertext, block, &round_keys);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_kuznyechik {
+    use super::*;
+
+    // RFC 7801, section 5
+    const KEY: [u8; 32] = [
+        0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+        0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF,
+    ];
+    const PLAIN: [u8; 16] = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x00, 0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA, 0x99, 0x88];
+    const CIPHER: [u8; 16] = [0x7F, 0x67, 0x9D, 0x90, 0xBE, 0xBC, 0x24, 0x30, 0x5A, 0x46, 0x8D, 0x42, 0xB9, 0xD4, 0xED, 0xCD];
+
+    #[test]
+    fn kuznyechik_encrypt() {
+        let mut ciphertext = [0; 16];
+        Kuznyechik::cipher(&PLAIN, &mut ciphertext, &KEY).expect("Error during Kuznyechik execution");
+        assert_eq!(ciphertext, CIPHER);
+    }
+
+    #[test]
+    fn kuznyechik_decrypt() {
+        let mut plain = [0; 16];
+        Kuznyechik::decipher(&mut plain, &CIPHER, &KEY).expect("Error during Kuznyechik execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn kuznyechik_encrypt_decrypt_blocks() {
+        // GOST R 34.13-2015, appendix A.1.1 (ECB mode)
+        let plain = [
+            PLAIN,
+            [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xEE, 0xFF, 0x0A],
+            [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xEE, 0xFF, 0x0A, 0x00],
+            [0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xEE, 0xFF, 0x0A, 0x00, 0x11],
+        ];
+        let expected = [
+            CIPHER,
+            [0xB4, 0x29, 0x91, 0x2C, 0x6E, 0x00, 0x32, 0xF9, 0x28, 0x54, 0x52, 0xD7, 0x67, 0x18, 0xD0, 0x8B],
+            [0xF0, 0xCA, 0x33, 0x54, 0x9D, 0x24, 0x7C, 0xEE, 0xF3, 0xF5, 0xA5, 0x31, 0x3B, 0xD4, 0xB1, 0x57],
+            [0xD0, 0xB0, 0x9C, 0xCD, 0xE8, 0x30, 0xB9, 0xEB, 0x3A, 0x02, 0xC4, 0xC5, 0xAA, 0x8A, 0xDA, 0x98],
+        ];
+
+        let mut blocks = plain;
+        Kuznyechik::cipher_blocks(&mut blocks, &KEY).expect("Error during Kuznyechik execution");
+        assert_eq!(blocks, expected);
+
+        Kuznyechik::decipher_blocks(&mut blocks, &KEY).expect("Error during Kuznyechik execution");
+        assert_eq!(blocks, plain);
+    }
+}
@@ -0,0 +1,146 @@
+//! Definition of Magma (GOST R 34.12-2015, RFC 8891), the 64-bit block cipher of the Russian standard
+//!
+//! Magma is the former GOST 28147-89, with fixed S-boxes. It is a 32-round Feistel network on two 32-bit
+//! halves (a1, a0):
+//!```text
+//!   (a1, a0) = (a0, g(K(i), a0) xor a1)       for the first 31 rounds, without the swap for the last one
+//!   g(k, a)  = t(a + k) <<< 11                t applies a 4-bit S-box on each nibble
+//! ```
+//! The round keys are the eight 32-bit words of the key, taken three times in order, then in reverse
+//! order. Decryption is the same function, with the round keys in reverse order.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct Magma {}
+
+// id-tc26-gost-28147-param-Z, PI[0] applies to the least significant nibble
+const PI: [[u8; 16]; 8] = [
+    [0xC, 0x4, 0x6, 0x2, 0xA, 0x5, 0xB, 0x9, 0xE, 0x8, 0xD, 0x7, 0x0, 0x3, 0xF, 0x1],
+    [0x6, 0x8, 0x2, 0x3, 0x9, 0xA, 0x5, 0xC, 0x1, 0xE, 0x4, 0x7, 0xB, 0xD, 0x0, 0xF],
+    [0xB, 0x3, 0x5, 0x8, 0x2, 0xF, 0xA, 0xD, 0xE, 0x1, 0x7, 0x4, 0xC, 0x9, 0x6, 0x0],
+    [0xC, 0x8, 0x2, 0x1, 0xD, 0x4, 0xF, 0x6, 0x7, 0x0, 0xA, 0x5, 0x3, 0xE, 0x9, 0xB],
+    [0x7, 0xF, 0x5, 0xA, 0x8, 0x1, 0x6, 0xD, 0x0, 0x9, 0x3, 0xE, 0xB, 0x4, 0x2, 0xC],
+    [0x5, 0xD, 0xF, 0x6, 0x9, 0x2, 0xC, 0xA, 0xB, 0x7, 0x8, 0x1, 0x4, 0x3, 0xE, 0x0],
+    [0x8, 0xE, 0x2, 0x5, 0x6, 0x9, 0x1, 0xC, 0xF, 0x4, 0xB, 0x0, 0xD, 0xA, 0x3, 0x7],
+    [0x1, 0x7, 0xE, 0xD, 0x0, 0x5, 0x8, 0x3, 0x4, 0xF, 0xA, 0x6, 0x9, 0xC, 0xB, 0x2],
+];
+
+fn t(a: u32) -> u32 {
+    (0..8).fold(0, |acc, i| acc | (PI[i][(a >> (4 * i) & 0xF) as usize] as u32) << (4 * i))
+}
+
+fn g(k: u32, a: u32) -> u32 {
+    t(a.wrapping_add(k)).rotate_left(11)
+}
+
+fn key_expansion(key: &[u8; 32]) -> [u32; 32] {
+    let mut round_keys = [0; 32];
+    for i in 0..24 {
+        round_keys[i] = u32::from_be_bytes(key[(i % 8) * 4..(i % 8) * 4 + 4].try_into().unwrap());
+    }
+    for i in 24..32 {
+        round_keys[i] = round_keys[31 - i];
+    }
+    round_keys
+}
+
+// Serves as encrypt AND as decrypt function. The choice is made by setting the round keys in normal or reversed order.
+fn generic_magma(input: &[u8; 8], output: &mut [u8; 8], round_keys: &[u32; 32]) {
+    let mut a1 = u32::from_be_bytes(input[..4].try_into().unwrap());
+    let mut a0 = u32::from_be_bytes(input[4..].try_into().unwrap());
+    for k in &round_keys[..31] {
+        (a1, a0) = (a0, g(*k, a0) ^ a1);
+    }
+    output[..4].copy_from_slice(&(g(round_keys[31], a0) ^ a1).to_be_bytes());
+    output[4..].copy_from_slice(&a0.to_be_bytes());
+}
+
+impl BlockCipher for Magma {
+    const KEY_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 8;
+
+    type KeyType = [u8; 32];
+    type BlockType = [u8; 8];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        generic_magma(plaintext, ciphertext, &key_expansion(key));
+        Ok(())
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        let mut round_keys = key_expansion(key);
+        round_keys.reverse();
+        generic_magma(ciphertext, plaintext, &round_keys);
+        Ok(())
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let round_keys = key_expansion(key);
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            generic_magma(&plaintext, block, &round_keys);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let mut round_keys = key_expansion(key);
+        round_keys.reverse();
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            generic_magma(&ciphertext, block, &round_keys);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_magma {
+    use super::*;
+
+    // RFC 8891, appendix A
+    const KEY: [u8; 32] = [
+        0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00,
+        0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF,
+    ];
+    const PLAIN: [u8; 8] = [0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+    const CIPHER: [u8; 8] = [0x4E, 0xE9, 0x01, 0xE5, 0xC2, 0xD8, 0xCA, 0x3D];
+
+    #[test]
+    fn magma_encrypt() {
+        let mut ciphertext = [0; 8];
+        Magma::cipher(&PLAIN, &mut ciphertext, &KEY).expect("Error during Magma execution");
+        assert_eq!(ciphertext, CIPHER);
+    }
+
+    #[test]
+    fn magma_decrypt() {
+        let mut plain = [0; 8];
+        Magma::decipher(&mut plain, &CIPHER, &KEY).expect("Error during Magma execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn magma_encrypt_decrypt_blocks() {
+        // GOST R 34.13-2015, appendix A.2.1 (ECB mode)
+        let plain = [
+            [0x92, 0xDE, 0xF0, 0x6B, 0x3C, 0x13, 0x0A, 0x59],
+            [0xDB, 0x54, 0xC7, 0x04, 0xF8, 0x18, 0x9D, 0x20],
+            [0x4A, 0x98, 0xFB, 0x2E, 0x67, 0xA8, 0x02, 0x4C],
+            [0x89, 0x12, 0x40, 0x9B, 0x17, 0xB5, 0x7E, 0x41],
+        ];
+        let expected = [
+            [0x2B, 0x07, 0x3F, 0x04, 0x94, 0xF3, 0x72, 0xA0],
+            [0xDE, 0x70, 0xE7, 0x15, 0xD3, 0x55, 0x6E, 0x48],
+            [0x11, 0xD8, 0xD9, 0xE9, 0xEA, 0xCF, 0xBC, 0x1E],
+            [0x7C, 0x68, 0x26, 0x09, 0x96, 0xC6, 0x7E, 0xFB],
+        ];
+
+        let mut blocks = plain;
+        Magma::cipher_blocks(&mut blocks, &KEY).expect("Error during Magma execution");
+        assert_eq!(blocks, expected);
+
+        Magma::decipher_blocks(&mut blocks, &KEY).expect("Error during Magma execution");
+        assert_eq!(blocks, plain);
+    }
+}
@@ -3,6 +3,24 @@
 //! Currently, these ciphers are implemented:
 //! - AES128 / AES192 / AES256
 //! - DES / Triple-DES 2K / Triple-DES 3K
+//! - SM4
+//! - Camellia128 / Camellia192 / Camellia256
+//! - ARIA128 / ARIA192 / ARIA256
+//! - Blowfish (32 to 448-bit keys)
+//! - Twofish128 / Twofish192 / Twofish256
+//! - Serpent128 / Serpent192 / Serpent256
+//! - IDEA
+//! - CAST5 (40 to 128-bit keys)
+//! - RC2 (8 to 1024-bit keys)
+//! - PRESENT80 / PRESENT128
+//! - GIFT64 / GIFT128
+//! - SIMON and SPECK (all block and key sizes, e.g. Simon64_128 or Speck128_256)
+//! - TEA / XTEA / XXTEA
+//! - Magma
+//! - Kuznyechik
+//!
+//! The lightweight ciphers (PRESENT, GIFT, SIMON, SPECK and the TEA family) are generic over their number
+//! of rounds, for cryptanalysis experiments: `PRESENT<80, 8>` is PRESENT80 reduced to 8 rounds.
 //!
 //! Each cipher exposes the `cipher` and `decipher` static methods to
 //! treat a single block of data (see [common::BlockCipher] for more details).
@@ -26,4 +44,20 @@
 pub mod aes;
 pub mod des;
 pub mod triple_des;
+pub mod sm4;
+pub mod camellia;
+pub mod aria;
+pub mod blowfish;
+pub mod twofish;
+pub mod serpent;
+pub mod idea;
+pub mod cast5;
+pub mod rc2;
+pub mod present;
+pub mod gift;
+pub mod simon;
+pub mod speck;
+pub mod tea;
+pub mod magma;
+pub mod kuznyechik;
 pub mod common;
\ No newline at end of file
@@ -0,0 +1,200 @@
+//! Definition of PRESENT (Bogdanov et al., CHES 2007), an ultra-lightweight SPN on 64-bit blocks
+//!
+//!```text
+//!   for i in 1..=31:
+//!     state = state xor K(i)
+//!     state = sBoxLayer(state)    4-bit S-box on each nibble
+//!     state = pLayer(state)       bit j moves to position 16 * j mod 63 (bit 63 is fixed)
+//!   state = state xor K(32)
+//! ```
+//! Round keys are the 64 leftmost bits of the key register, which is updated between rounds by a
+//! rotation, one or two S-box lookups and the addition of the round counter.
+//!
+//! PRESENT is generic over the number of rounds (from 1 to 31), for cryptanalysis experiments.
+//! [PRESENT80] and [PRESENT128] are the standard 31-round versions.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct PRESENT<const KEY_BITS: usize, const ROUNDS: usize> {}
+
+pub type PRESENT80 = PRESENT<80, 31>;
+pub type PRESENT128 = PRESENT<128, 31>;
+
+const SBOX: [u8; 16] = [0xC, 0x5, 0x6, 0xB, 0x9, 0x0, 0xA, 0xD, 0x3, 0xE, 0xF, 0x8, 0x4, 0x7, 0x1, 0x2];
+const INV_SBOX: [u8; 16] = [0x5, 0xE, 0xF, 0x8, 0xC, 0x1, 0x2, 0xD, 0xB, 0x4, 0x6, 0x3, 0x0, 0x7, 0x9, 0xA];
+
+fn check_rounds(rounds: usize) -> Result<(), &'static str> {
+    if !(1..=31).contains(&rounds) {
+        return Err("PRESENT round number must be between 1 and 31");
+    }
+    Ok(())
+}
+
+fn s_box_layer(state: u64, sbox: &[u8; 16]) -> u64 {
+    (0..16).fold(0, |acc, i| acc | (sbox[(state >> (4 * i) & 0xF) as usize] as u64) << (4 * i))
+}
+
+fn p_layer(state: u64) -> u64 {
+    (0..64).fold(0, |acc, i| acc | (state >> i & 1) << if i == 63 { 63 } else { 16 * i % 63 })
+}
+
+fn inv_p_layer(state: u64) -> u64 {
+    (0..64).fold(0, |acc, i| acc | (state >> if i == 63 { 63 } else { 16 * i % 63 } & 1) << i)
+}
+
+// the 80-bit key register is stored in the 80 most significant bits of a u128
+fn key_expansion_80(key: &[u8; 10], rounds: usize) -> Vec<u64> {
+    let mut k = [0; 16];
+    k[..10].copy_from_slice(key);
+    let mut register = u128::from_be_bytes(k);
+
+    let mut round_keys = Vec::with_capacity(rounds + 1);
+    for counter in 1..=rounds as u128 {
+        round_keys.push((register >> 64) as u64);
+        register = (register << 61 | register >> 19) & u128::MAX << 48;
+        register = register & !(0xF << 124) | (SBOX[(register >> 124) as usize] as u128) << 124;
+        register ^= counter << 63;
+    }
+    round_keys.push((register >> 64) as u64);
+    round_keys
+}
+
+fn key_expansion_128(key: &[u8; 16], rounds: usize) -> Vec<u64> {
+    let mut register = u128::from_be_bytes(*key);
+
+    let mut round_keys = Vec::with_capacity(rounds + 1);
+    for counter in 1..=rounds as u128 {
+        round_keys.push((register >> 64) as u64);
+        register = register.rotate_left(61);
+        register = register & !(0xFF << 120)
+            | (SBOX[(register >> 124) as usize] as u128) << 124
+            | (SBOX[(register >> 120 & 0xF) as usize] as u128) << 120;
+        register ^= counter << 62;
+    }
+    round_keys.push((register >> 64) as u64);
+    round_keys
+}
+
+fn encrypt_block(input: &[u8; 8], output: &mut [u8; 8], round_keys: &[u64]) {
+    let (last_key, keys) = round_keys.split_last().unwrap();
+    let mut state = u64::from_be_bytes(*input);
+    for k in keys {
+        state = p_layer(s_box_layer(state ^ k, &SBOX));
+    }
+    *output = (state ^ last_key).to_be_bytes();
+}
+
+fn decrypt_block(input: &[u8; 8], output: &mut [u8; 8], round_keys: &[u64]) {
+    let (last_key, keys) = round_keys.split_last().unwrap();
+    let mut state = u64::from_be_bytes(*input) ^ last_key;
+    for k in keys.iter().rev() {
+        state = s_box_layer(inv_p_layer(state), &INV_SBOX) ^ k;
+    }
+    *output = state.to_be_bytes();
+}
+
+macro_rules! define_present {
+    ( $key_bits:expr, $key_length:expr, $key_expansion:ident ) => {
+        impl<const ROUNDS: usize> BlockCipher for PRESENT<$key_bits, ROUNDS> {
+            const KEY_SIZE: usize = $key_length;
+            const BLOCK_SIZE: usize = 8;
+
+            type KeyType = [u8; $key_length];
+            type BlockType = [u8; 8];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                check_rounds(ROUNDS)?;
+                encrypt_block(plaintext, ciphertext, &$key_expansion(key, ROUNDS));
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                check_rounds(ROUNDS)?;
+                decrypt_block(ciphertext, plaintext, &$key_expansion(key, ROUNDS));
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                check_rounds(ROUNDS)?;
+                let round_keys = $key_expansion(key, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let plaintext = *block;
+                    encrypt_block(&plaintext, block, &round_keys);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                check_rounds(ROUNDS)?;
+                let round_keys = $key_expansion(key, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let ciphertext = *block;
+                    decrypt_block(&ciphertext, block, &round_keys);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+define_present!(80, 10, key_expansion_80);
+define_present!(128, 16, key_expansion_128);
+
+#[cfg(test)]
+mod tests_present {
+    use super::*;
+
+    // test vectors from the original paper, appendix I
+    #[test]
+    fn present80_encrypt_decrypt() {
+        let vectors = [
+            ([0x00; 10], [0x00; 8], [0x55, 0x79, 0xC1, 0x38, 0x7B, 0x22, 0x84, 0x45]),
+            ([0xFF; 10], [0x00; 8], [0xE7, 0x2C, 0x46, 0xC0, 0xF5, 0x94, 0x50, 0x49]),
+            ([0x00; 10], [0xFF; 8], [0xA1, 0x12, 0xFF, 0xC7, 0x2F, 0x68, 0x41, 0x7B]),
+            ([0xFF; 10], [0xFF; 8], [0x33, 0x33, 0xDC, 0xD3, 0x21, 0x32, 0x10, 0xD2]),
+        ];
+
+        for (key, plain, expected) in vectors {
+            let mut ciphertext = [0; 8];
+            PRESENT80::cipher(&plain, &mut ciphertext, &key).expect("Error during PRESENT80 execution");
+            assert_eq!(ciphertext, expected);
+
+            let mut decrypted = [0; 8];
+            PRESENT80::decipher(&mut decrypted, &ciphertext, &key).expect("Error during PRESENT80 execution");
+            assert_eq!(decrypted, plain);
+        }
+    }
+
+    #[test]
+    fn present128_encrypt_decrypt() {
+        let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+        let plain = [[0x00; 8], [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]];
+        let expected = [[0x96, 0xDB, 0x70, 0x2A, 0x2E, 0x69, 0x00, 0xAF], [0x0E, 0x9D, 0x28, 0x68, 0x5E, 0x67, 0x1D, 0xD6]];
+
+        let mut ciphertext = [0; 8];
+        PRESENT128::cipher(&plain[0], &mut ciphertext, &[0; 16]).expect("Error during PRESENT128 execution");
+        assert_eq!(ciphertext, expected[0]);
+
+        let mut blocks = [plain[1]];
+        PRESENT128::cipher_blocks(&mut blocks, &key).expect("Error during PRESENT128 execution");
+        assert_eq!(blocks, [expected[1]]);
+
+        PRESENT128::decipher_blocks(&mut blocks, &key).expect("Error during PRESENT128 execution");
+        assert_eq!(blocks, [plain[1]]);
+    }
+
+    #[test]
+    fn present_reduced_rounds() {
+        let key = [0x0F; 10];
+        let plain = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+
+        let mut ciphertext = [0; 8];
+        PRESENT::<80, 5>::cipher(&plain, &mut ciphertext, &key).expect("Error during PRESENT execution");
+        let mut decrypted = [0; 8];
+        PRESENT::<80, 5>::decipher(&mut decrypted, &ciphertext, &key).expect("Error during PRESENT execution");
+        assert_eq!(decrypted, plain);
+
+        assert!(PRESENT::<80, 0>::cipher(&plain, &mut ciphertext, &key).is_err());
+        assert!(PRESENT::<128, 32>::cipher(&plain, &mut ciphertext, &[0; 16]).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Definition of RC2 (RFC 2268)
+//!
+//! RC2 works on four 16-bit words R0..R3 and is made of 16 MIXING rounds, with a MASHING round after the
+//! 5th and the 11th ones:
+//!```text
+//!   MIX:   for i in 0..4:  R(i) = (R(i) + K(j) + (R(i-1) & R(i-2)) + (~R(i-1) & R(i-3))) <<< s(i),  j = j + 1
+//!   MASH:  for i in 0..4:  R(i) = R(i) + K(R(i-1) & 63)
+//!
+//!   s = [1, 2, 3, 5]
+//! ```
+//! The key schedule expands keys from 1 to 128 bytes into 64 subkeys, and limits the search space to an
+//! "effective key length" of 1 to 1024 bits. RC2 is generic over both: `RC2<16, 128>` takes a 128-bit
+//! key with 128 effective bits.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct RC2<const N: usize, const EFFECTIVE_BITS: usize> {}
+
+const PITABLE: [u8; 256] = [
+    0xD9, 0x78, 0xF9, 0xC4, 0x19, 0xDD, 0xB5, 0xED, 0x28, 0xE9, 0xFD, 0x79, 0x4A, 0xA0, 0xD8, 0x9D,
+    0xC6, 0x7E, 0x37, 0x83, 0x2B, 0x76, 0x53, 0x8E, 0x62, 0x4C, 0x64, 0x88, 0x44, 0x8B, 0xFB, 0xA2,
+    0x17, 0x9A, 0x59, 0xF5, 0x87, 0xB3, 0x4F, 0x13, 0x61, 0x45, 0x6D, 0x8D, 0x09, 0x81, 0x7D, 0x32,
+    0xBD, 0x8F, 0x40, 0xEB, 0x86, 0xB7, 0x7B, 0x0B, 0xF0, 0x95, 0x21, 0x22, 0x5C, 0x6B, 0x4E, 0x82,
+    0x54, 0xD6, 0x65, 0x93, 0xCE, 0x60, 0xB2, 0x1C, 0x73, 0x56, 0xC0, 0x14, 0xA7, 0x8C, 0xF1, 0xDC,
+    0x12, 0x75, 0xCA, 0x1F, 0x3B, 0xBE, 0xE4, 0xD1, 0x42, 0x3D, 0xD4, 0x30, 0xA3, 0x3C, 0xB6, 0x26,
+    0x6F, 0xBF, 0x0E, 0xDA, 0x46, 0x69, 0x07, 0x57, 0x27, 0xF2, 0x1D, 0x9B, 0xBC, 0x94, 0x43, 0x03,
+    0xF8, 0x11, 0xC7, 0xF6, 0x90, 0xEF, 0x3E, 0xE7, 0x06, 0xC3, 0xD5, 0x2F, 0xC8, 0x66, 0x1E, 0xD7,
+    0x08, 0xE8, 0xEA, 0xDE, 0x80, 0x52, 0xEE, 0xF7, 0x84, 0xAA, 0x72, 0xAC, 0x35, 0x4D, 0x6A, 0x2A,
+    0x96, 0x1A, 0xD2, 0x71, 0x5A, 0x15, 0x49, 0x74, 0x4B, 0x9F, 0xD0, 0x5E, 0x04, 0x18, 0xA4, 0xEC,
+    0xC2, 0xE0, 0x41, 0x6E, 0x0F, 0x51, 0xCB, 0xCC, 0x24, 0x91, 0xAF, 0x50, 0xA1, 0xF4, 0x70, 0x39,
+    0x99, 0x7C, 0x3A, 0x85, 0x23, 0xB8, 0xB4, 0x7A, 0xFC, 0x02, 0x36, 0x5B, 0x25, 0x55, 0x97, 0x31,
+    0x2D, 0x5D, 0xFA, 0x98, 0xE3, 0x8A, 0x92, 0xAE, 0x05, 0xDF, 0x29, 0x10, 0x67, 0x6C, 0xBA, 0xC9,
+    0xD3, 0x00, 0xE6, 0xCF, 0xE1, 0x9E, 0xA8, 0x2C, 0x63, 0x16, 0x01, 0x3F, 0x58, 0xE2, 0x89, 0xA9,
+    0x0D, 0x38, 0x34, 0x1B, 0xAB, 0x33, 0xFF, 0xB0, 0xBB, 0x48, 0x0C, 0x5F, 0xB9, 0xB1, 0xCD, 0x2E,
+    0xC5, 0xF3, 0xDB, 0x47, 0xE5, 0xA5, 0x9C, 0x77, 0x0A, 0xA6, 0x20, 0x68, 0xFE, 0x7F, 0xC1, 0xAD,
+];
+
+const S: [u32; 4] = [1, 2, 3, 5];
+
+fn check_parameters(key_length: usize, effective_bits: usize) -> Result<(), &'static str> {
+    if !(1..=128).contains(&key_length) {
+        return Err("RC2 key must be between 1 and 128 bytes long");
+    }
+    if !(1..=1024).contains(&effective_bits) {
+        return Err("RC2 effective key length must be between 1 and 1024 bits");
+    }
+    Ok(())
+}
+
+fn key_expansion(key: &[u8], effective_bits: usize) -> [u16; 64] {
+    let t = key.len();
+    let mut l = [0u8; 128];
+    l[..t].copy_from_slice(key);
+    for i in t..128 {
+        l[i] = PITABLE[l[i - 1].wrapping_add(l[i - t]) as usize];
+    }
+
+    // reduce the key to its effective length
+    let t8 = effective_bits.div_ceil(8);
+    let tm = (0xFF >> (8 * t8 - effective_bits)) as u8;
+    l[128 - t8] = PITABLE[(l[128 - t8] & tm) as usize];
+    for i in (0..128 - t8).rev() {
+        l[i] = PITABLE[(l[i + 1] ^ l[i + t8]) as usize];
+    }
+
+    core::array::from_fn(|i| u16::from_le_bytes([l[2 * i], l[2 * i + 1]]))
+}
+
+fn mix(r: &mut [u16; 4], k: &[u16; 64], j: &mut usize) {
+    for i in 0..4 {
+        let f = (r[(i + 3) % 4] & r[(i + 2) % 4]).wrapping_add(!r[(i + 3) % 4] & r[(i + 1) % 4]);
+        r[i] = r[i].wrapping_add(k[*j]).wrapping_add(f).rotate_left(S[i]);
+        *j += 1;
+    }
+}
+
+fn mash(r: &mut [u16; 4], k: &[u16; 64]) {
+    for i in 0..4 {
+        r[i] = r[i].wrapping_add(k[(r[(i + 3) % 4] & 63) as usize]);
+    }
+}
+
+fn r_mix(r: &mut [u16; 4], k: &[u16; 64], j: &mut usize) {
+    for i in (0..4).rev() {
+        *j -= 1;
+        let f = (r[(i + 3) % 4] & r[(i + 2) % 4]).wrapping_add(!r[(i + 3) % 4] & r[(i + 1) % 4]);
+        r[i] = r[i].rotate_right(S[i]).wrapping_sub(k[*j]).wrapping_sub(f);
+    }
+}
+
+fn r_mash(r: &mut [u16; 4], k: &[u16; 64]) {
+    for i in (0..4).rev() {
+        r[i] = r[i].wrapping_sub(k[(r[(i + 3) % 4] & 63) as usize]);
+    }
+}
+
+fn encrypt_block(input: &[u8; 8], output: &mut [u8; 8], k: &[u16; 64]) {
+    let mut r: [u16; 4] = core::array::from_fn(|i| u16::from_le_bytes([input[2 * i], input[2 * i + 1]]));
+    let mut j = 0;
+    for round in 0..16 {
+        mix(&mut r, k, &mut j);
+        if round == 4 || round == 10 {
+            mash(&mut r, k);
+        }
+    }
+    for i in 0..4 {
+        output[2 * i..2 * i + 2].copy_from_slice(&r[i].to_le_bytes());
+    }
+}
+
+fn decrypt_block(input: &[u8; 8], output: &mut [u8; 8], k: &[u16; 64]) {
+    let mut r: [u16; 4] = core::array::from_fn(|i| u16::from_le_bytes([input[2 * i], input[2 * i + 1]]));
+    let mut j = 64;
+    for round in 0..16 {
+        r_mix(&mut r, k, &mut j);
+        if round == 4 || round == 10 {
+            r_mash(&mut r, k);
+        }
+    }
+    for i in 0..4 {
+        output[2 * i..2 * i + 2].copy_from_slice(&r[i].to_le_bytes());
+    }
+}
+
+impl<const N: usize, const EFFECTIVE_BITS: usize> BlockCipher for RC2<N, EFFECTIVE_BITS> {
+    const KEY_SIZE: usize = N;
+    const BLOCK_SIZE: usize = 8;
+
+    type KeyType = [u8; N];
+    type BlockType = [u8; 8];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        check_parameters(N, EFFECTIVE_BITS)?;
+        encrypt_block(plaintext, ciphertext, &key_expansion(key, EFFECTIVE_BITS));
+        Ok(())
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        check_parameters(N, EFFECTIVE_BITS)?;
+        decrypt_block(ciphertext, plaintext, &key_expansion(key, EFFECTIVE_BITS));
+        Ok(())
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        check_parameters(N, EFFECTIVE_BITS)?;
+        let k = key_expansion(key, EFFECTIVE_BITS);
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            encrypt_block(&plaintext, block, &k);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        check_parameters(N, EFFECTIVE_BITS)?;
+        let k = key_expansion(key, EFFECTIVE_BITS);
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            decrypt_block(&ciphertext, block, &k);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_rc2 {
+    use super::*;
+
+    // RFC 2268, section 5
+    #[test]
+    fn rc2_encrypt() {
+        let mut ciphertext = [0; 8];
+        RC2::<8, 63>::cipher(&[0; 8], &mut ciphertext, &[0; 8]).expect("Error during RC2 execution");
+        assert_eq!(ciphertext, [0xEB, 0xB7, 0x73, 0xF9, 0x93, 0x27, 0x8E, 0xFF]);
+
+        RC2::<8, 64>::cipher(&[0xFF; 8], &mut ciphertext, &[0xFF; 8]).expect("Error during RC2 execution");
+        assert_eq!(ciphertext, [0x27, 0x8B, 0x27, 0xE4, 0x2E, 0x2F, 0x0D, 0x49]);
+
+        let plain = [0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+        let key = [0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        RC2::<8, 64>::cipher(&plain, &mut ciphertext, &key).expect("Error during RC2 execution");
+        assert_eq!(ciphertext, [0x30, 0x64, 0x9E, 0xDF, 0x9B, 0xE7, 0xD2, 0xC2]);
+
+        RC2::<1, 64>::cipher(&[0; 8], &mut ciphertext, &[0x88]).expect("Error during RC2 execution");
+        assert_eq!(ciphertext, [0x61, 0xA8, 0xA2, 0x44, 0xAD, 0xAC, 0xCC, 0xF0]);
+
+        let key = [0x88, 0xBC, 0xA9, 0x0E, 0x90, 0x87, 0x5A, 0x7F, 0x0F, 0x79, 0xC3, 0x84, 0x62, 0x7B, 0xAF, 0xB2];
+        RC2::<16, 128>::cipher(&[0; 8], &mut ciphertext, &key).expect("Error during RC2 execution");
+        assert_eq!(ciphertext, [0x22, 0x69, 0x55, 0x2A, 0xB0, 0xF8, 0x5C, 0xA6]);
+    }
+
+    #[test]
+    fn rc2_decrypt() {
+        let mut plain = [0; 8];
+        RC2::<8, 64>::decipher(&mut plain, &[0x27, 0x8B, 0x27, 0xE4, 0x2E, 0x2F, 0x0D, 0x49], &[0xFF; 8]).expect("Error during RC2 execution");
+        assert_eq!(plain, [0xFF; 8]);
+    }
+
+    #[test]
+    fn rc2_encrypt_decrypt_blocks() {
+        // checked with Nettle
+        let key: [u8; 33] = core::array::from_fn(|i| i as u8);
+        let plain = [[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77], [0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]];
+        let expected = [[0x74, 0x05, 0x6D, 0x57, 0x01, 0x81, 0x79, 0x97], [0xA2, 0x41, 0x7F, 0xBB, 0x58, 0x1B, 0xEC, 0xBF]];
+
+        let mut blocks = plain;
+        RC2::<33, 1024>::cipher_blocks(&mut blocks, &key).expect("Error during RC2 execution");
+        assert_eq!(blocks, expected);
+
+        RC2::<33, 1024>::decipher_blocks(&mut blocks, &key).expect("Error during RC2 execution");
+        assert_eq!(blocks, plain);
+    }
+
+    #[test]
+    fn rc2_errors() {
+        let mut ciphertext = [0; 8];
+        assert!(RC2::<0, 64>::cipher(&[0; 8], &mut ciphertext, &[]).is_err());
+        assert!(RC2::<129, 64>::cipher(&[0; 8], &mut ciphertext, &[0; 129]).is_err());
+        assert!(RC2::<8, 0>::cipher(&[0; 8], &mut ciphertext, &[0; 8]).is_err());
+        assert!(RC2::<8, 1025>::cipher(&[0; 8], &mut ciphertext, &[0; 8]).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Definition of Serpent128, Serpent192 and Serpent256 (Anderson, Biham and Knudsen, 1998)
+//!
+//! Serpent is a 32-round substitution-permutation network on four 32-bit words (little endian):
+//!```text
+//!   for r in 0..31:  X = LT(S(r mod 8)(X xor K(r)))
+//!   C = S7(X xor K31) xor K32
+//! ```
+//! The 4-bit S-boxes are applied in bitslice mode: the i-th bits of the 4 words form one S-box input.
+//! Shorter keys are padded to 256 bits with a single 1 bit followed by zeroes, so all key sizes share
+//! the same key schedule.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+const SBOX: [[u8; 16]; 8] = [
+    [ 3,  8, 15,  1, 10,  6,  5, 11, 14, 13,  4,  2,  7,  0,  9, 12],
+    [15, 12,  2,  7,  9,  0,  5, 10,  1, 11, 14,  8,  6, 13,  3,  4],
+    [ 8,  6,  7,  9,  3, 12, 10, 15, 13,  1, 14,  4,  0, 11,  5,  2],
+    [ 0, 15, 11,  8, 12,  9,  6,  3, 13,  1,  2,  4, 10,  7,  5, 14],
+    [ 1, 15,  8,  3, 12,  0, 11,  6,  2,  5,  4, 10,  9, 14,  7, 13],
+    [15,  5,  2, 11,  4, 10,  9, 12,  0,  3, 14,  8, 13,  6,  7,  1],
+    [ 7,  2, 12,  5,  8,  4,  6, 11, 14,  9,  1, 15, 13,  3, 10,  0],
+    [ 1, 13, 15,  0, 14,  8,  2, 11,  7,  4, 12, 10,  9,  3,  5,  6],
+];
+
+const INV_SBOX: [[u8; 16]; 8] = [
+    [13,  3, 11,  0, 10,  6,  5, 12,  1, 14,  4,  7, 15,  9,  8,  2],
+    [ 5,  8,  2, 14, 15,  6, 12,  3, 11,  4,  7,  9,  1, 13, 10,  0],
+    [12,  9, 15,  4, 11, 14,  1,  2,  0,  3,  6, 13,  5,  8, 10,  7],
+    [ 0,  9, 10,  7, 11, 14,  6, 13,  3,  5, 12,  2,  4,  8, 15,  1],
+    [ 5,  0,  8,  3, 10,  9,  7, 14,  2, 12, 11,  6,  4, 15, 13,  1],
+    [ 8, 15,  2,  9,  4,  1, 13, 14, 11,  6,  5,  3,  7, 12, 10,  0],
+    [15, 10,  1, 13,  5,  3,  6,  0,  4,  9, 14,  7,  2, 12,  8, 11],
+    [ 3,  0,  6, 13,  9, 14, 15,  8,  5, 12, 11,  7, 10,  1,  4,  2],
+];
+
+const PHI: u32 = 0x9E3779B9;
+
+type StateSerpent = [u32; 4];
+
+// applies the 4-bit S-box in bitslice mode
+fn substitute(x: &StateSerpent, sbox: &[u8; 16]) -> StateSerpent {
+    let mut y = [0; 4];
+    for bit in 0..32 {
+        let input = (0..4).fold(0, |acc, i| acc | ((x[i] >> bit) & 1) << i);
+        let output = sbox[input as usize] as u32;
+        for (i, yi) in y.iter_mut().enumerate() {
+            *yi |= ((output >> i) & 1) << bit;
+        }
+    }
+    y
+}
+
+fn linear_transformation(x: &mut StateSerpent) {
+    x[0] = x[0].rotate_left(13);
+    x[2] = x[2].rotate_left(3);
+    x[1] ^= x[0] ^ x[2];
+    x[3] ^= x[2] ^ (x[0] << 3);
+    x[1] = x[1].rotate_left(1);
+    x[3] = x[3].rotate_left(7);
+    x[0] ^= x[1] ^ x[3];
+    x[2] ^= x[3] ^ (x[1] << 7);
+    x[0] = x[0].rotate_left(5);
+    x[2] = x[2].rotate_left(22);
+}
+
+fn inv_linear_transformation(x: &mut StateSerpent) {
+    x[2] = x[2].rotate_right(22);
+    x[0] = x[0].rotate_right(5);
+    x[2] ^= x[3] ^ (x[1] << 7);
+    x[0] ^= x[1] ^ x[3];
+    x[3] = x[3].rotate_right(7);
+    x[1] = x[1].rotate_right(1);
+    x[3] ^= x[2] ^ (x[0] << 3);
+    x[1] ^= x[0] ^ x[2];
+    x[2] = x[2].rotate_right(3);
+    x[0] = x[0].rotate_right(13);
+}
+
+fn xor_state(x: &mut StateSerpent, k: &StateSerpent) {
+    for i in 0..4 {
+        x[i] ^= k[i];
+    }
+}
+
+fn key_expansion(key: &[u8]) -> [StateSerpent; 33] {
+    // pad the key to 256 bits
+    let mut padded = [0; 32];
+    padded[..key.len()].copy_from_slice(key);
+    if key.len() < 32 {
+        padded[key.len()] = 1;
+    }
+
+    // prekeys
+    let mut w = [0u32; 140];
+    for i in 0..8 {
+        w[i] = u32::from_le_bytes(padded[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+    for i in 8..140 {
+        w[i] = (w[i - 8] ^ w[i - 5] ^ w[i - 3] ^ w[i - 1] ^ PHI ^ (i as u32 - 8)).rotate_left(11);
+    }
+
+    // round keys
+    let mut round_keys = [[0; 4]; 33];
+    for (i, round_key) in round_keys.iter_mut().enumerate() {
+        let prekey = [w[8 + 4 * i], w[9 + 4 * i], w[10 + 4 * i], w[11 + 4 * i]];
+        *round_key = substitute(&prekey, &SBOX[(35 - i) % 8]);
+    }
+    round_keys
+}
+
+fn block_to_state(block: &[u8; 16]) -> StateSerpent {
+    core::array::from_fn(|i| u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap()))
+}
+
+fn state_to_block(state: &StateSerpent) -> [u8; 16] {
+    let mut block = [0; 16];
+    for i in 0..4 {
+        block[i * 4..i * 4 + 4].copy_from_slice(&state[i].to_le_bytes());
+    }
+    block
+}
+
+fn encrypt_block(input: &[u8; 16], round_keys: &[StateSerpent; 33]) -> [u8; 16] {
+    let mut x = block_to_state(input);
+    for r in 0..32 {
+        xor_state(&mut x, &round_keys[r]);
+        x = substitute(&x, &SBOX[r % 8]);
+        if r < 31 {
+            linear_transformation(&mut x);
+        }
+    }
+    xor_state(&mut x, &round_keys[32]);
+    state_to_block(&x)
+}
+
+fn decrypt_block(input: &[u8; 16], round_keys: &[StateSerpent; 33]) -> [u8; 16] {
+    let mut x = block_to_state(input);
+    xor_state(&mut x, &round_keys[32]);
+    for r in (0..32).rev() {
+        if r < 31 {
+            inv_linear_transformation(&mut x);
+        }
+        x = substitute(&x, &INV_SBOX[r % 8]);
+        xor_state(&mut x, &round_keys[r]);
+    }
+    state_to_block(&x)
+}
+
+macro_rules! define_serpent {
+    ( $serpent_name:ident, $key_length:expr) => {
+        pub struct $serpent_name {}
+
+        impl BlockCipher for $serpent_name {
+            const KEY_SIZE: usize = $key_length;
+            const BLOCK_SIZE: usize = 16;
+
+            type KeyType = [u8; $key_length];
+            type BlockType = [u8; 16];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *ciphertext = encrypt_block(plaintext, &key_expansion(key));
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *plaintext = decrypt_block(ciphertext, &key_expansion(key));
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key);
+                for block in blocks.iter_mut() {
+                    *block = encrypt_block(block, &round_keys);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key);
+                for block in blocks.iter_mut() {
+                    *block = decrypt_block(block, &round_keys);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+define_serpent!(Serpent128, 16);
+define_serpent!(Serpent192, 24);
+define_serpent!(Serpent256, 32);
+
+#[cfg(test)]
+mod tests_serpent {
+    use super::*;
+
+    #[test]
+    fn serpent128_encrypt_decrypt() {
+        // NESSIE, set 1, vector 0
+        let mut key = [0; 16];
+        key[0] = 0x80;
+        let expected = [0x26, 0x4E, 0x54, 0x81, 0xEF, 0xF4, 0x2A, 0x46, 0x06, 0xAB, 0xDA, 0x06, 0xC0, 0xBF, 0xDA, 0x3D];
+        let mut ciphertext = [0; 16];
+        Serpent128::cipher(&[0; 16], &mut ciphertext, &key).expect("Error during Serpent128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0xFF; 16];
+        Serpent128::decipher(&mut plain, &ciphertext, &key).expect("Error during Serpent128 execution");
+        assert_eq!(plain, [0; 16]);
+    }
+
+    #[test]
+    fn serpent192_encrypt_decrypt() {
+        // checked with Nettle
+        let key: [u8; 24] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let expected = [0x75, 0x3D, 0x5B, 0x42, 0xD8, 0x66, 0x72, 0xFB, 0x29, 0x07, 0x0C, 0x4F, 0xE4, 0xEA, 0xAF, 0x4C];
+        let mut ciphertext = [0; 16];
+        Serpent192::cipher(&plain, &mut ciphertext, &key).expect("Error during Serpent192 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut decrypted = [0; 16];
+        Serpent192::decipher(&mut decrypted, &ciphertext, &key).expect("Error during Serpent192 execution");
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn serpent256_encrypt_decrypt() {
+        // checked with Nettle
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let expected = [0xDE, 0x26, 0x9F, 0xF8, 0x33, 0xE4, 0x32, 0xB8, 0x5B, 0x2E, 0x88, 0xD2, 0x70, 0x1C, 0xE7, 0x5C];
+        let mut ciphertext = [0; 16];
+        Serpent256::cipher(&plain, &mut ciphertext, &key).expect("Error during Serpent256 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut decrypted = [0; 16];
+        Serpent256::decipher(&mut decrypted, &ciphertext, &key).expect("Error during Serpent256 execution");
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn serpent128_encrypt_decrypt_blocks() {
+        // checked with Nettle
+        let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let plain: [[u8; 16]; 2] = [core::array::from_fn(|i| i as u8), core::array::from_fn(|i| 16 + i as u8)];
+        let expected = [
+            [0x4C, 0x7D, 0x8A, 0x32, 0x80, 0x72, 0xA2, 0x2C, 0x82, 0x3E, 0x4A, 0x1F, 0x3A, 0xCD, 0xA1, 0x6D],
+            [0x1F, 0xE3, 0x1A, 0xBA, 0x98, 0x24, 0xFB, 0x1B, 0x33, 0xB4, 0x25, 0x82, 0x29, 0x13, 0x88, 0xDB],
+        ];
+
+        let mut blocks = plain;
+        Serpent128::cipher_blocks(&mut blocks, &key).expect("Error during Serpent128 execution");
+        assert_eq!(blocks, expected);
+
+        Serpent128::decipher_blocks(&mut blocks, &key).expect("Error during Serpent128 execution");
+        assert_eq!(blocks, plain);
+    }
+}
@@ -0,0 +1,209 @@
+//! Definition of SIMON (Beaulieu et al., NSA, 2013), a Feistel cipher built on AND, xor and rotations
+//!
+//! The block is made of two n-bit words (x, y), and the key of m n-bit words (n = 16, 24, 32, 48 or 64):
+//!```text
+//!   x, y = y xor f(x) xor k(i), x
+//!   f(x) = ((x <<< 1) & (x <<< 8)) xor (x <<< 2)
+//! ```
+//! The key schedule is linear, and uses one of five constant sequences z0..z4 to break the symmetry
+//! between the rounds.
+//!
+//! SIMON is generic over the block size, the key size and the number of rounds, for cryptanalysis
+//! experiments. The standard versions are defined as [Simon32_64], [Simon64_128], [Simon128_256], etc.
+//! Words are read as for [crate::symmetric::block_ciphers::speck].
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers::speck::{from_words, mask, rotl, rotr, to_words};
+
+pub struct Simon<const BLOCK_BITS: usize, const KEY_BITS: usize, const ROUNDS: usize> {}
+
+// the 62-bit sequences z0 to z4, first bit in the least significant position
+const Z: [u64; 5] = [0x19C3522FB386A45F, 0x16864FB8AD0C9F71, 0x3369F885192C0EF5, 0x3C2CE51207A635DB, 0x3DC94C3A046D678B];
+
+fn f(x: u64, n: u32) -> u64 {
+    (rotl(x, 1, n) & rotl(x, 8, n)) ^ rotl(x, 2, n)
+}
+
+fn key_expansion(key: &[u8], n: u32, z: u64, rounds: usize) -> Vec<u64> {
+    let mut k = to_words(key, n);
+    let m = k.len();
+    for i in m..rounds {
+        let mut tmp = rotr(k[i - 1], 3, n);
+        if m == 4 {
+            tmp ^= k[i - 3];
+        }
+        tmp ^= rotr(tmp, 1, n);
+        k.push(k[i - m] ^ mask(n) ^ 3 ^ (z >> ((i - m) % 62) & 1) ^ tmp);
+    }
+    k.truncate(rounds);
+    k
+}
+
+fn encrypt_block(input: &[u8], output: &mut [u8], round_keys: &[u64], n: u32) {
+    let [mut y, mut x] = to_words(input, n)[..] else { unreachable!() };
+    for k in round_keys {
+        (x, y) = (y ^ f(x, n) ^ k, x);
+    }
+    from_words(&[y, x], output, n);
+}
+
+fn decrypt_block(input: &[u8], output: &mut [u8], round_keys: &[u64], n: u32) {
+    let [mut y, mut x] = to_words(input, n)[..] else { unreachable!() };
+    for k in round_keys.iter().rev() {
+        (x, y) = (y, x ^ f(y, n) ^ k);
+    }
+    from_words(&[y, x], output, n);
+}
+
+macro_rules! define_simon {
+    ( $simon_name:ident, $block_bits:expr, $key_bits:expr, $nb_rounds:expr, $z:expr ) => {
+        pub type $simon_name = Simon<$block_bits, $key_bits, $nb_rounds>;
+
+        impl<const ROUNDS: usize> BlockCipher for Simon<$block_bits, $key_bits, ROUNDS> {
+            const KEY_SIZE: usize = $key_bits / 8;
+            const BLOCK_SIZE: usize = $block_bits / 8;
+
+            type KeyType = [u8; $key_bits / 8];
+            type BlockType = [u8; $block_bits / 8];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, Z[$z], ROUNDS);
+                encrypt_block(plaintext, ciphertext, &round_keys, $block_bits / 2);
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, Z[$z], ROUNDS);
+                decrypt_block(ciphertext, plaintext, &round_keys, $block_bits / 2);
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, Z[$z], ROUNDS);
+                for block in blocks.iter_mut() {
+                    let plaintext = *block;
+                    encrypt_block(&plaintext, block, &round_keys, $block_bits / 2);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, Z[$z], ROUNDS);
+                for block in blocks.iter_mut() {
+                    let ciphertext = *block;
+                    decrypt_block(&ciphertext, block, &round_keys, $block_bits / 2);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+define_simon!(Simon32_64, 32, 64, 32, 0);
+define_simon!(Simon48_72, 48, 72, 36, 0);
+define_simon!(Simon48_96, 48, 96, 36, 1);
+define_simon!(Simon64_96, 64, 96, 42, 2);
+define_simon!(Simon64_128, 64, 128, 44, 3);
+define_simon!(Simon96_96, 96, 96, 52, 2);
+define_simon!(Simon96_144, 96, 144, 54, 3);
+define_simon!(Simon128_128, 128, 128, 68, 2);
+define_simon!(Simon128_192, 128, 192, 69, 3);
+define_simon!(Simon128_256, 128, 256, 72, 4);
+
+#[cfg(test)]
+mod tests_simon {
+    use super::*;
+
+    // test vectors from the SIMON and SPECK paper, appendix B, converted to bytes
+
+    // the key words are 0x..0100, 0x..0908, 0x..1110, etc.
+    fn paper_key<const N: usize>(word_bytes: usize) -> [u8; N] {
+        core::array::from_fn(|i| (i / word_bytes * 8 + i % word_bytes) as u8)
+    }
+
+    macro_rules! check_simon {
+        ( $simon_name:ident, $word_bytes:expr, $plain:expr, $expected:expr ) => {
+            let key = paper_key($word_bytes);
+            let mut ciphertext = $plain;
+            $simon_name::cipher(&$plain, &mut ciphertext, &key).expect("Error during Simon execution");
+            assert_eq!(ciphertext, $expected);
+
+            let mut decrypted = $plain;
+            $simon_name::decipher(&mut decrypted, &ciphertext, &key).expect("Error during Simon execution");
+            assert_eq!(decrypted, $plain);
+        };
+    }
+
+    #[test]
+    fn simon32() {
+        check_simon!(Simon32_64, 2, [0x77, 0x68, 0x65, 0x65], [0xBB, 0xE9, 0x9B, 0xC6]);
+    }
+
+    #[test]
+    fn simon48() {
+        check_simon!(Simon48_72, 3, [0x6C, 0x69, 0x6E, 0x67, 0x20, 0x61], [0xAC, 0x2C, 0x29, 0xAC, 0xE5, 0xDA]);
+        check_simon!(Simon48_96, 3, [0x6E, 0x64, 0x20, 0x63, 0x69, 0x72], [0x56, 0xF1, 0xAC, 0xA5, 0x06, 0x6E]);
+    }
+
+    #[test]
+    fn simon64() {
+        check_simon!(Simon64_96, 4, [0x63, 0x6C, 0x69, 0x6E, 0x67, 0x20, 0x72, 0x6F], [0xC8, 0x8F, 0x1A, 0x11, 0x7F, 0xE2, 0xA2, 0x5C]);
+        check_simon!(Simon64_128, 4, [0x75, 0x6E, 0x64, 0x20, 0x6C, 0x69, 0x6B, 0x65], [0x7A, 0xA0, 0xDF, 0xB9, 0x20, 0xFC, 0xC8, 0x44]);
+    }
+
+    #[test]
+    fn simon96() {
+        check_simon!(
+            Simon96_96,
+            6,
+            [0x20, 0x74, 0x68, 0x65, 0x20, 0x70, 0x69, 0x6C, 0x6C, 0x61, 0x72, 0x20],
+            [0x82, 0xF0, 0x8F, 0x3D, 0x06, 0x69, 0xB4, 0x62, 0xA4, 0x07, 0x28, 0x60]
+        );
+        check_simon!(
+            Simon96_144,
+            6,
+            [0x6F, 0x66, 0x20, 0x64, 0x75, 0x73, 0x74, 0x20, 0x74, 0x68, 0x61, 0x74],
+            [0xE9, 0x1A, 0xDB, 0xC5, 0x59, 0x3F, 0x1E, 0x45, 0x6C, 0x1C, 0xAD, 0xEC]
+        );
+    }
+
+    #[test]
+    fn simon128() {
+        check_simon!(
+            Simon128_128,
+            8,
+            [0x20, 0x74, 0x72, 0x61, 0x76, 0x65, 0x6C, 0x6C, 0x65, 0x72, 0x73, 0x20, 0x64, 0x65, 0x73, 0x63],
+            [0xBC, 0x0B, 0x4E, 0xF8, 0x2A, 0x83, 0xAA, 0x65, 0x3F, 0xFE, 0x54, 0x1E, 0x1E, 0x1B, 0x68, 0x49]
+        );
+        check_simon!(
+            Simon128_192,
+            8,
+            [0x72, 0x69, 0x62, 0x65, 0x20, 0x77, 0x68, 0x65, 0x6E, 0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20],
+            [0x5B, 0xB8, 0x97, 0x25, 0x6E, 0x8D, 0x9C, 0x6C, 0x4F, 0x0D, 0xDC, 0xFC, 0xEF, 0x61, 0xAC, 0xC4]
+        );
+        check_simon!(
+            Simon128_256,
+            8,
+            [0x69, 0x73, 0x20, 0x61, 0x20, 0x73, 0x69, 0x6D, 0x6F, 0x6F, 0x6D, 0x20, 0x69, 0x6E, 0x20, 0x74],
+            [0x68, 0xB8, 0xE7, 0xEF, 0x87, 0x2A, 0xF7, 0x3B, 0xA0, 0xA3, 0xC8, 0xAF, 0x79, 0x55, 0x2B, 0x8D]
+        );
+    }
+
+    #[test]
+    fn simon_reduced_rounds() {
+        let key = paper_key(4);
+        let plain = [0x75, 0x6E, 0x64, 0x20, 0x6C, 0x69, 0x6B, 0x65];
+
+        // fewer rounds than key words: the round keys are the key words themselves
+        let mut blocks = [plain; 2];
+        Simon::<64, 128, 3>::cipher_blocks(&mut blocks, &key).expect("Error during Simon execution");
+        assert_ne!(blocks[0], plain);
+
+        Simon::<64, 128, 3>::decipher_blocks(&mut blocks, &key).expect("Error during Simon execution");
+        assert_eq!(blocks, [plain; 2]);
+
+        Simon::<64, 128, 12>::cipher_blocks(&mut blocks, &key).expect("Error during Simon execution");
+        Simon::<64, 128, 12>::decipher_blocks(&mut blocks, &key).expect("Error during Simon execution");
+        assert_eq!(blocks, [plain; 2]);
+    }
+}
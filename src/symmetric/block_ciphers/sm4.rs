@@ -0,0 +1,191 @@
+//! Definition of SM4 (GB/T 32907-2016), the Chinese national standard block cipher
+//!
+//! SM4 is an unbalanced Feistel network of 32 rounds on four 32-bit words:
+//!```text
+//!   X(i+4) = X(i) xor T(X(i+1) xor X(i+2) xor X(i+3) xor rk(i))
+//!   T(x)   = L(S(x)),   L(b) = b xor (b <<< 2) xor (b <<< 10) xor (b <<< 18) xor (b <<< 24)
+//!
+//!   C = X35 || X34 || X33 || X32
+//! ```
+//! Decryption is the same function with the round keys in reverse order.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct SM4 {}
+
+const SBOX: [u8; 256] = [
+    0xD6, 0x90, 0xE9, 0xFE, 0xCC, 0xE1, 0x3D, 0xB7, 0x16, 0xB6, 0x14, 0xC2, 0x28, 0xFB, 0x2C, 0x05,
+    0x2B, 0x67, 0x9A, 0x76, 0x2A, 0xBE, 0x04, 0xC3, 0xAA, 0x44, 0x13, 0x26, 0x49, 0x86, 0x06, 0x99,
+    0x9C, 0x42, 0x50, 0xF4, 0x91, 0xEF, 0x98, 0x7A, 0x33, 0x54, 0x0B, 0x43, 0xED, 0xCF, 0xAC, 0x62,
+    0xE4, 0xB3, 0x1C, 0xA9, 0xC9, 0x08, 0xE8, 0x95, 0x80, 0xDF, 0x94, 0xFA, 0x75, 0x8F, 0x3F, 0xA6,
+    0x47, 0x07, 0xA7, 0xFC, 0xF3, 0x73, 0x17, 0xBA, 0x83, 0x59, 0x3C, 0x19, 0xE6, 0x85, 0x4F, 0xA8,
+    0x68, 0x6B, 0x81, 0xB2, 0x71, 0x64, 0xDA, 0x8B, 0xF8, 0xEB, 0x0F, 0x4B, 0x70, 0x56, 0x9D, 0x35,
+    0x1E, 0x24, 0x0E, 0x5E, 0x63, 0x58, 0xD1, 0xA2, 0x25, 0x22, 0x7C, 0x3B, 0x01, 0x21, 0x78, 0x87,
+    0xD4, 0x00, 0x46, 0x57, 0x9F, 0xD3, 0x27, 0x52, 0x4C, 0x36, 0x02, 0xE7, 0xA0, 0xC4, 0xC8, 0x9E,
+    0xEA, 0xBF, 0x8A, 0xD2, 0x40, 0xC7, 0x38, 0xB5, 0xA3, 0xF7, 0xF2, 0xCE, 0xF9, 0x61, 0x15, 0xA1,
+    0xE0, 0xAE, 0x5D, 0xA4, 0x9B, 0x34, 0x1A, 0x55, 0xAD, 0x93, 0x32, 0x30, 0xF5, 0x8C, 0xB1, 0xE3,
+    0x1D, 0xF6, 0xE2, 0x2E, 0x82, 0x66, 0xCA, 0x60, 0xC0, 0x29, 0x23, 0xAB, 0x0D, 0x53, 0x4E, 0x6F,
+    0xD5, 0xDB, 0x37, 0x45, 0xDE, 0xFD, 0x8E, 0x2F, 0x03, 0xFF, 0x6A, 0x72, 0x6D, 0x6C, 0x5B, 0x51,
+    0x8D, 0x1B, 0xAF, 0x92, 0xBB, 0xDD, 0xBC, 0x7F, 0x11, 0xD9, 0x5C, 0x41, 0x1F, 0x10, 0x5A, 0xD8,
+    0x0A, 0xC1, 0x31, 0x88, 0xA5, 0xCD, 0x7B, 0xBD, 0x2D, 0x74, 0xD0, 0x12, 0xB8, 0xE5, 0xB4, 0xB0,
+    0x89, 0x69, 0x97, 0x4A, 0x0C, 0x96, 0x77, 0x7E, 0x65, 0xB9, 0xF1, 0x09, 0xC5, 0x6E, 0xC6, 0x84,
+    0x18, 0xF0, 0x7D, 0xEC, 0x3A, 0xDC, 0x4D, 0x20, 0x79, 0xEE, 0x5F, 0x3E, 0xD7, 0xCB, 0x39, 0x48,
+];
+
+const FK: [u32; 4] = [0xA3B1BAC6, 0x56AA3350, 0x677D9197, 0xB27022DC];
+
+const CK: [u32; 32] = [
+    0x00070E15, 0x1C232A31, 0x383F464D, 0x545B6269, 0x70777E85, 0x8C939AA1, 0xA8AFB6BD, 0xC4CBD2D9,
+    0xE0E7EEF5, 0xFC030A11, 0x181F262D, 0x343B4249, 0x50575E65, 0x6C737A81, 0x888F969D, 0xA4ABB2B9,
+    0xC0C7CED5, 0xDCE3EAF1, 0xF8FF060D, 0x141B2229, 0x30373E45, 0x4C535A61, 0x686F767D, 0x848B9299,
+    0xA0A7AEB5, 0xBCC3CAD1, 0xD8DFE6ED, 0xF4FB0209, 0x10171E25, 0x2C333A41, 0x484F565D, 0x646B7279,
+];
+
+// non-linear transformation tau: S-box applied on each byte
+fn tau(a: u32) -> u32 {
+    u32::from_be_bytes(a.to_be_bytes().map(|b| SBOX[b as usize]))
+}
+
+// round function transformation T
+fn t(a: u32) -> u32 {
+    let b = tau(a);
+    b ^ b.rotate_left(2) ^ b.rotate_left(10) ^ b.rotate_left(18) ^ b.rotate_left(24)
+}
+
+// key schedule transformation T'
+fn t_prime(a: u32) -> u32 {
+    let b = tau(a);
+    b ^ b.rotate_left(13) ^ b.rotate_left(23)
+}
+
+fn key_expansion(key: &[u8; 16]) -> [u32; 32] {
+    let mut k = [0; 4];
+    for i in 0..4 {
+        k[i] = u32::from_be_bytes(key[i * 4..i * 4 + 4].try_into().unwrap()) ^ FK[i];
+    }
+
+    let mut round_keys = [0; 32];
+    for i in 0..32 {
+        round_keys[i] = k[0] ^ t_prime(k[1] ^ k[2] ^ k[3] ^ CK[i]);
+        k = [k[1], k[2], k[3], round_keys[i]];
+    }
+    round_keys
+}
+
+// Serves as encrypt AND as decrypt function. The choice is made by setting the round keys in normal or reversed order.
+fn generic_sm4(input: &[u8; 16], output: &mut [u8; 16], round_keys: &[u32; 32]) {
+    let mut x = [0; 4];
+    for i in 0..4 {
+        x[i] = u32::from_be_bytes(input[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+
+    // rounds
+    for rk in round_keys {
+        x = [x[1], x[2], x[3], x[0] ^ t(x[1] ^ x[2] ^ x[3] ^ rk)];
+    }
+
+    // final reverse transformation
+    for i in 0..4 {
+        output[i * 4..i * 4 + 4].copy_from_slice(&x[3 - i].to_be_bytes());
+    }
+}
+
+impl BlockCipher for SM4 {
+    const KEY_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 16;
+
+    type KeyType = [u8; 16];
+    type BlockType = [u8; 16];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        let round_keys = key_expansion(key);
+        generic_sm4(plaintext, ciphertext, &round_keys);
+        Ok(())
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        let mut round_keys = key_expansion(key);
+        round_keys.reverse();
+        generic_sm4(ciphertext, plaintext, &round_keys);
+        Ok(())
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let round_keys = key_expansion(key);
+        for block in blocks.iter_mut() {
+            let plaintext = *block;
+            generic_sm4(&plaintext, block, &round_keys);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let mut round_keys = key_expansion(key);
+        round_keys.reverse();
+        for block in blocks.iter_mut() {
+            let ciphertext = *block;
+            generic_sm4(&ciphertext, block, &round_keys);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_sm4 {
+    use super::*;
+    use crate::symmetric::block_ciphers_modes::cbc::CBC;
+    use crate::symmetric::block_ciphers_modes::common::BlockChainingWithIV;
+
+    // GB/T 32907-2016, appendix A
+    const KEY: [u8; 16] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10];
+    const CIPHER: [u8; 16] = [0x68, 0x1E, 0xDF, 0x34, 0xD2, 0x06, 0x96, 0x5E, 0x86, 0xB3, 0xE9, 0x4F, 0x53, 0x6E, 0x42, 0x46];
+
+    #[test]
+    fn sm4_encrypt() {
+        let mut ciphertext = [0; 16];
+        SM4::cipher(&KEY, &mut ciphertext, &KEY).expect("Error during SM4 execution");
+        assert_eq!(ciphertext, CIPHER);
+    }
+
+    #[test]
+    fn sm4_decrypt() {
+        let mut plain = [0; 16];
+        SM4::decipher(&mut plain, &CIPHER, &KEY).expect("Error during SM4 execution");
+        assert_eq!(plain, KEY);
+    }
+
+    #[test]
+    fn sm4_encrypt_decrypt_blocks() {
+        let plain = [KEY, [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F]];
+        let expected = [CIPHER, [0x06, 0x98, 0x9C, 0x61, 0x3D, 0xA6, 0x68, 0xAD, 0x2A, 0x8D, 0xF7, 0x82, 0xE1, 0xA8, 0xF9, 0x6A]];
+
+        let mut blocks = plain;
+        SM4::cipher_blocks(&mut blocks, &KEY).expect("Error during SM4 execution");
+        assert_eq!(blocks, expected);
+
+        SM4::decipher_blocks(&mut blocks, &KEY).expect("Error during SM4 execution");
+        assert_eq!(blocks, plain);
+    }
+
+    #[test]
+    fn sm4_cbc() {
+        let iv = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let plain = [
+            0xAA, 0xAA, 0xAA, 0xAA, 0xBB, 0xBB, 0xBB, 0xBB, 0xCC, 0xCC, 0xCC, 0xCC, 0xDD, 0xDD, 0xDD, 0xDD,
+            0xEE, 0xEE, 0xEE, 0xEE, 0xFF, 0xFF, 0xFF, 0xFF, 0xAA, 0xAA, 0xAA, 0xAA, 0xBB, 0xBB, 0xBB, 0xBB,
+        ];
+        // cf. draft-ribose-cfrg-sm4, CBC example
+        let expected = [
+            0x78, 0xEB, 0xB1, 0x1C, 0xC4, 0x0B, 0x0A, 0x48, 0x31, 0x2A, 0xAE, 0xB2, 0x04, 0x02, 0x44, 0xCB,
+            0x4C, 0xB7, 0x01, 0x69, 0x51, 0x90, 0x92, 0x26, 0x97, 0x9B, 0x0D, 0x15, 0xDC, 0x6A, 0x8F, 0x6D,
+        ];
+
+        let mut ciphertext = [0; 32];
+        <CBC as BlockChainingWithIV<SM4>>::cipher(&plain, &mut ciphertext, &KEY, &iv).expect("Error during CBC_SM4 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut decrypted = [0; 32];
+        <CBC as BlockChainingWithIV<SM4>>::decipher(&mut decrypted, &ciphertext, &KEY, &iv).expect("Error during CBC_SM4 execution");
+        assert_eq!(decrypted, plain);
+    }
+}
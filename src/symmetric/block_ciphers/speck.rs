@@ -0,0 +1,239 @@
+//! Definition of SPECK (Beaulieu et al., NSA, 2013), an ARX Feistel-like cipher
+//!
+//! The block is made of two n-bit words (x, y), and the key of m n-bit words (n = 16, 24, 32, 48 or 64):
+//!```text
+//!   x = ((x >>> a) + y) xor k(i)
+//!   y = (y <<< b) xor x                 (a, b) = (7, 2) if n = 16, (8, 3) otherwise
+//! ```
+//! The key schedule reuses the round function, with the round number as the round key.
+//!
+//! SPECK is generic over the block size, the key size and the number of rounds, for cryptanalysis
+//! experiments. The standard versions are defined as [Speck32_64], [Speck64_128], [Speck128_256], etc.
+//! Words are read in little-endian order, the block being `y || x` and the key `k0 || l0 || l1 ...`, as in
+//! the designers' implementation guide.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct Speck<const BLOCK_BITS: usize, const KEY_BITS: usize, const ROUNDS: usize> {}
+
+// n-bit words helpers, shared with SIMON
+
+#[inline(always)]
+pub(crate) fn mask(n: u32) -> u64 {
+    u64::MAX >> (64 - n)
+}
+
+#[inline(always)]
+pub(crate) fn rotl(x: u64, r: u32, n: u32) -> u64 {
+    (x << r | x >> (n - r)) & mask(n)
+}
+
+#[inline(always)]
+pub(crate) fn rotr(x: u64, r: u32, n: u32) -> u64 {
+    (x >> r | x << (n - r)) & mask(n)
+}
+
+fn rotations(n: u32) -> (u32, u32) {
+    if n == 16 {
+        (7, 2)
+    } else {
+        (8, 3)
+    }
+}
+
+pub(crate) fn to_words(bytes: &[u8], n: u32) -> Vec<u64> {
+    bytes.chunks(n as usize / 8).map(|c| c.iter().rev().fold(0, |acc, &b| acc << 8 | b as u64)).collect()
+}
+
+pub(crate) fn from_words(words: &[u64], bytes: &mut [u8], n: u32) {
+    for (c, w) in bytes.chunks_mut(n as usize / 8).zip(words) {
+        for (i, b) in c.iter_mut().enumerate() {
+            *b = (w >> (8 * i)) as u8;
+        }
+    }
+}
+
+fn key_expansion(key: &[u8], n: u32, rounds: usize) -> Vec<u64> {
+    let (a, b) = rotations(n);
+    let words = to_words(key, n);
+    let mut k = words[0];
+    let mut l = words[1..].to_vec();
+
+    let mut round_keys = Vec::with_capacity(rounds);
+    for i in 0..rounds {
+        round_keys.push(k);
+        let new_l = (rotr(l[i], a, n).wrapping_add(k) & mask(n)) ^ i as u64;
+        k = rotl(k, b, n) ^ new_l;
+        l.push(new_l);
+    }
+    round_keys
+}
+
+fn encrypt_block(input: &[u8], output: &mut [u8], round_keys: &[u64], n: u32) {
+    let (a, b) = rotations(n);
+    let [mut y, mut x] = to_words(input, n)[..] else { unreachable!() };
+    for k in round_keys {
+        x = (rotr(x, a, n).wrapping_add(y) & mask(n)) ^ k;
+        y = rotl(y, b, n) ^ x;
+    }
+    from_words(&[y, x], output, n);
+}
+
+fn decrypt_block(input: &[u8], output: &mut [u8], round_keys: &[u64], n: u32) {
+    let (a, b) = rotations(n);
+    let [mut y, mut x] = to_words(input, n)[..] else { unreachable!() };
+    for k in round_keys.iter().rev() {
+        y = rotr(y ^ x, b, n);
+        x = rotl((x ^ k).wrapping_sub(y) & mask(n), a, n);
+    }
+    from_words(&[y, x], output, n);
+}
+
+macro_rules! define_speck {
+    ( $speck_name:ident, $block_bits:expr, $key_bits:expr, $nb_rounds:expr ) => {
+        pub type $speck_name = Speck<$block_bits, $key_bits, $nb_rounds>;
+
+        impl<const ROUNDS: usize> BlockCipher for Speck<$block_bits, $key_bits, ROUNDS> {
+            const KEY_SIZE: usize = $key_bits / 8;
+            const BLOCK_SIZE: usize = $block_bits / 8;
+
+            type KeyType = [u8; $key_bits / 8];
+            type BlockType = [u8; $block_bits / 8];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, ROUNDS);
+                encrypt_block(plaintext, ciphertext, &round_keys, $block_bits / 2);
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, ROUNDS);
+                decrypt_block(ciphertext, plaintext, &round_keys, $block_bits / 2);
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let plaintext = *block;
+                    encrypt_block(&plaintext, block, &round_keys, $block_bits / 2);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let round_keys = key_expansion(key, $block_bits / 2, ROUNDS);
+                for block in blocks.iter_mut() {
+                    let ciphertext = *block;
+                    decrypt_block(&ciphertext, block, &round_keys, $block_bits / 2);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+define_speck!(Speck32_64, 32, 64, 22);
+define_speck!(Speck48_72, 48, 72, 22);
+define_speck!(Speck48_96, 48, 96, 23);
+define_speck!(Speck64_96, 64, 96, 26);
+define_speck!(Speck64_128, 64, 128, 27);
+define_speck!(Speck96_96, 96, 96, 28);
+define_speck!(Speck96_144, 96, 144, 29);
+define_speck!(Speck128_128, 128, 128, 32);
+define_speck!(Speck128_192, 128, 192, 33);
+define_speck!(Speck128_256, 128, 256, 34);
+
+#[cfg(test)]
+mod tests_speck {
+    use super::*;
+
+    // test vectors from the SIMON and SPECK paper, appendix C, converted to bytes
+
+    // the key words are 0x..0100, 0x..0908, 0x..1110, etc.
+    fn paper_key<const N: usize>(word_bytes: usize) -> [u8; N] {
+        core::array::from_fn(|i| (i / word_bytes * 8 + i % word_bytes) as u8)
+    }
+
+    macro_rules! check_speck {
+        ( $speck_name:ident, $word_bytes:expr, $plain:expr, $expected:expr ) => {
+            let key = paper_key($word_bytes);
+            let mut ciphertext = $plain;
+            $speck_name::cipher(&$plain, &mut ciphertext, &key).expect("Error during Speck execution");
+            assert_eq!(ciphertext, $expected);
+
+            let mut decrypted = $plain;
+            $speck_name::decipher(&mut decrypted, &ciphertext, &key).expect("Error during Speck execution");
+            assert_eq!(decrypted, $plain);
+        };
+    }
+
+    #[test]
+    fn speck32() {
+        check_speck!(Speck32_64, 2, [0x4C, 0x69, 0x74, 0x65], [0xF2, 0x42, 0x68, 0xA8]);
+    }
+
+    #[test]
+    fn speck48() {
+        check_speck!(Speck48_72, 3, [0x72, 0x61, 0x6C, 0x6C, 0x79, 0x20], [0xDC, 0x5A, 0x38, 0xA5, 0x49, 0xC0]);
+        check_speck!(Speck48_96, 3, [0x74, 0x68, 0x69, 0x73, 0x20, 0x6D], [0x5D, 0x44, 0xB6, 0x10, 0x5E, 0x73]);
+    }
+
+    #[test]
+    fn speck64() {
+        check_speck!(Speck64_96, 4, [0x65, 0x61, 0x6E, 0x73, 0x20, 0x46, 0x61, 0x74], [0x6C, 0x94, 0x75, 0x41, 0xEC, 0x52, 0x79, 0x9F]);
+        check_speck!(Speck64_128, 4, [0x2D, 0x43, 0x75, 0x74, 0x74, 0x65, 0x72, 0x3B], [0x8B, 0x02, 0x4E, 0x45, 0x48, 0xA5, 0x6F, 0x8C]);
+    }
+
+    #[test]
+    fn speck96() {
+        check_speck!(
+            Speck96_96,
+            6,
+            [0x20, 0x75, 0x73, 0x61, 0x67, 0x65, 0x2C, 0x20, 0x68, 0x6F, 0x77, 0x65],
+            [0xAA, 0x79, 0x8F, 0xDE, 0xBD, 0x62, 0x78, 0x71, 0xAB, 0x09, 0x4D, 0x9E]
+        );
+        check_speck!(
+            Speck96_144,
+            6,
+            [0x76, 0x65, 0x72, 0x2C, 0x20, 0x69, 0x6E, 0x20, 0x74, 0x69, 0x6D, 0x65],
+            [0xE6, 0x2E, 0x25, 0x40, 0xE4, 0x7A, 0x8A, 0x22, 0x72, 0x10, 0xF3, 0x2B]
+        );
+    }
+
+    #[test]
+    fn speck128() {
+        check_speck!(
+            Speck128_128,
+            8,
+            [0x20, 0x6D, 0x61, 0x64, 0x65, 0x20, 0x69, 0x74, 0x20, 0x65, 0x71, 0x75, 0x69, 0x76, 0x61, 0x6C],
+            [0x18, 0x0D, 0x57, 0x5C, 0xDF, 0xFE, 0x60, 0x78, 0x65, 0x32, 0x78, 0x79, 0x51, 0x98, 0x5D, 0xA6]
+        );
+        check_speck!(
+            Speck128_192,
+            8,
+            [0x65, 0x6E, 0x74, 0x20, 0x74, 0x6F, 0x20, 0x43, 0x68, 0x69, 0x65, 0x66, 0x20, 0x48, 0x61, 0x72],
+            [0x86, 0x18, 0x3C, 0xE0, 0x5D, 0x18, 0xBC, 0xF9, 0x66, 0x55, 0x13, 0x13, 0x3A, 0xCF, 0xE4, 0x1B]
+        );
+        check_speck!(
+            Speck128_256,
+            8,
+            [0x70, 0x6F, 0x6F, 0x6E, 0x65, 0x72, 0x2E, 0x20, 0x49, 0x6E, 0x20, 0x74, 0x68, 0x6F, 0x73, 0x65],
+            [0x43, 0x8F, 0x18, 0x9C, 0x8D, 0xB4, 0xEE, 0x4E, 0x3E, 0xF5, 0xC0, 0x05, 0x04, 0x01, 0x09, 0x41]
+        );
+    }
+
+    #[test]
+    fn speck_reduced_rounds() {
+        let key = paper_key(4);
+        let plain = [0x2D, 0x43, 0x75, 0x74, 0x74, 0x65, 0x72, 0x3B];
+
+        let mut blocks = [plain; 2];
+        Speck::<64, 128, 10>::cipher_blocks(&mut blocks, &key).expect("Error during Speck execution");
+        assert_ne!(blocks[0], plain);
+        assert_eq!(blocks[0], blocks[1]);
+
+        Speck::<64, 128, 10>::decipher_blocks(&mut blocks, &key).expect("Error during Speck execution");
+        assert_eq!(blocks, [plain; 2]);
+    }
+}
@@ -0,0 +1,287 @@
+//! Definition of TEA, XTEA and XXTEA (Wheeler and Needham, 1994, 1997 and 1998)
+//!
+//! TEA is a Feistel network on two 32-bit words with a 128-bit key k0..k3, using only additions, xors
+//! and shifts. Each cycle is made of two Feistel rounds:
+//!```text
+//!   sum = sum + delta                                          delta = 0x9E3779B9
+//!   v0 = v0 + (((v1 << 4) + k0) xor (v1 + sum) xor ((v1 >> 5) + k1))
+//!   v1 = v1 + (((v0 << 4) + k2) xor (v0 + sum) xor ((v0 >> 5) + k3))
+//! ```
+//! XTEA fixes the related-key weaknesses of TEA by selecting the key words with the sum. XXTEA (corrected
+//! Block TEA) works on blocks of any number of 32-bit words (at least 2), and by default does
+//! 6 + 52 / words cycles.
+//!
+//! The three ciphers are generic over the number of cycles, for cryptanalysis experiments. [TEA32] and
+//! [XTEA32] are the standard 32-cycle versions, [XXTEA64] and [XXTEA128] the standard XXTEA versions for
+//! 64 and 128-bit blocks. Words are read in big-endian order.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+pub struct TEA<const CYCLES: usize> {}
+pub struct XTEA<const CYCLES: usize> {}
+pub struct XXTEA<const N: usize, const CYCLES: usize> {}
+
+pub type TEA32 = TEA<32>;
+pub type XTEA32 = XTEA<32>;
+pub type XXTEA64 = XXTEA<8, 32>;
+pub type XXTEA128 = XXTEA<16, 19>;
+
+const DELTA: u32 = 0x9E3779B9;
+
+fn to_words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    core::array::from_fn(|i| u32::from_be_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+fn from_words(words: &[u32], bytes: &mut [u8]) {
+    for (chunk, w) in bytes.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&w.to_be_bytes());
+    }
+}
+
+fn tea_encrypt(block: &mut [u8; 8], k: &[u32; 4], cycles: usize) {
+    let [mut v0, mut v1] = to_words(block);
+    let mut sum = 0u32;
+    for _ in 0..cycles {
+        sum = sum.wrapping_add(DELTA);
+        v0 = v0.wrapping_add((v1 << 4).wrapping_add(k[0]) ^ v1.wrapping_add(sum) ^ (v1 >> 5).wrapping_add(k[1]));
+        v1 = v1.wrapping_add((v0 << 4).wrapping_add(k[2]) ^ v0.wrapping_add(sum) ^ (v0 >> 5).wrapping_add(k[3]));
+    }
+    from_words(&[v0, v1], block);
+}
+
+fn tea_decrypt(block: &mut [u8; 8], k: &[u32; 4], cycles: usize) {
+    let [mut v0, mut v1] = to_words(block);
+    let mut sum = DELTA.wrapping_mul(cycles as u32);
+    for _ in 0..cycles {
+        v1 = v1.wrapping_sub((v0 << 4).wrapping_add(k[2]) ^ v0.wrapping_add(sum) ^ (v0 >> 5).wrapping_add(k[3]));
+        v0 = v0.wrapping_sub((v1 << 4).wrapping_add(k[0]) ^ v1.wrapping_add(sum) ^ (v1 >> 5).wrapping_add(k[1]));
+        sum = sum.wrapping_sub(DELTA);
+    }
+    from_words(&[v0, v1], block);
+}
+
+fn xtea_encrypt(block: &mut [u8; 8], k: &[u32; 4], cycles: usize) {
+    let [mut v0, mut v1] = to_words(block);
+    let mut sum = 0u32;
+    for _ in 0..cycles {
+        v0 = v0.wrapping_add(((v1 << 4) ^ (v1 >> 5)).wrapping_add(v1) ^ sum.wrapping_add(k[(sum & 3) as usize]));
+        sum = sum.wrapping_add(DELTA);
+        v1 = v1.wrapping_add(((v0 << 4) ^ (v0 >> 5)).wrapping_add(v0) ^ sum.wrapping_add(k[(sum >> 11 & 3) as usize]));
+    }
+    from_words(&[v0, v1], block);
+}
+
+fn xtea_decrypt(block: &mut [u8; 8], k: &[u32; 4], cycles: usize) {
+    let [mut v0, mut v1] = to_words(block);
+    let mut sum = DELTA.wrapping_mul(cycles as u32);
+    for _ in 0..cycles {
+        v1 = v1.wrapping_sub(((v0 << 4) ^ (v0 >> 5)).wrapping_add(v0) ^ sum.wrapping_add(k[(sum >> 11 & 3) as usize]));
+        sum = sum.wrapping_sub(DELTA);
+        v0 = v0.wrapping_sub(((v1 << 4) ^ (v1 >> 5)).wrapping_add(v1) ^ sum.wrapping_add(k[(sum & 3) as usize]));
+    }
+    from_words(&[v0, v1], block);
+}
+
+// XXTEA mixing function, for the word p with neighbours y (next) and z (previous)
+#[inline(always)]
+fn mx(sum: u32, y: u32, z: u32, p: usize, e: usize, k: &[u32; 4]) -> u32 {
+    ((z >> 5 ^ y << 2).wrapping_add(y >> 3 ^ z << 4)) ^ ((sum ^ y).wrapping_add(k[(p & 3) ^ e] ^ z))
+}
+
+fn xxtea_encrypt(v: &mut [u32], k: &[u32; 4], cycles: usize) {
+    let n = v.len();
+    let mut sum = 0u32;
+    let mut z = v[n - 1];
+    for _ in 0..cycles {
+        sum = sum.wrapping_add(DELTA);
+        let e = (sum >> 2 & 3) as usize;
+        for p in 0..n {
+            let y = v[(p + 1) % n];
+            v[p] = v[p].wrapping_add(mx(sum, y, z, p, e, k));
+            z = v[p];
+        }
+    }
+}
+
+fn xxtea_decrypt(v: &mut [u32], k: &[u32; 4], cycles: usize) {
+    let n = v.len();
+    let mut sum = DELTA.wrapping_mul(cycles as u32);
+    let mut y = v[0];
+    for _ in 0..cycles {
+        let e = (sum >> 2 & 3) as usize;
+        for p in (0..n).rev() {
+            let z = v[(p + n - 1) % n];
+            v[p] = v[p].wrapping_sub(mx(sum, y, z, p, e, k));
+            y = v[p];
+        }
+        sum = sum.wrapping_sub(DELTA);
+    }
+}
+
+macro_rules! define_tea {
+    ( $tea_name:ident, $encrypt:ident, $decrypt:ident ) => {
+        impl<const CYCLES: usize> BlockCipher for $tea_name<CYCLES> {
+            const KEY_SIZE: usize = 16;
+            const BLOCK_SIZE: usize = 8;
+
+            type KeyType = [u8; 16];
+            type BlockType = [u8; 8];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *ciphertext = *plaintext;
+                $encrypt(ciphertext, &to_words(key), CYCLES);
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *plaintext = *ciphertext;
+                $decrypt(plaintext, &to_words(key), CYCLES);
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let k = to_words(key);
+                for block in blocks.iter_mut() {
+                    $encrypt(block, &k, CYCLES);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let k = to_words(key);
+                for block in blocks.iter_mut() {
+                    $decrypt(block, &k, CYCLES);
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+define_tea!(TEA, tea_encrypt, tea_decrypt);
+define_tea!(XTEA, xtea_encrypt, xtea_decrypt);
+
+fn check_xxtea_block_size(size: usize) -> Result<(), &'static str> {
+    if size < 8 || !size.is_multiple_of(4) {
+        return Err("XXTEA block size must be a multiple of 4 bytes, and at least 8 bytes");
+    }
+    Ok(())
+}
+
+impl<const N: usize, const CYCLES: usize> BlockCipher for XXTEA<N, CYCLES> {
+    const KEY_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = N;
+
+    type KeyType = [u8; 16];
+    type BlockType = [u8; N];
+
+    fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        *ciphertext = *plaintext;
+        Self::cipher_blocks(core::slice::from_mut(ciphertext), key)
+    }
+
+    fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+        *plaintext = *ciphertext;
+        Self::decipher_blocks(core::slice::from_mut(plaintext), key)
+    }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        check_xxtea_block_size(N)?;
+        let k = to_words(key);
+        for block in blocks.iter_mut() {
+            let mut v: Vec<u32> = block.chunks_exact(4).map(|c| u32::from_be_bytes(c.try_into().unwrap())).collect();
+            xxtea_encrypt(&mut v, &k, CYCLES);
+            from_words(&v, block);
+        }
+        Ok(())
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        check_xxtea_block_size(N)?;
+        let k = to_words(key);
+        for block in blocks.iter_mut() {
+            let mut v: Vec<u32> = block.chunks_exact(4).map(|c| u32::from_be_bytes(c.try_into().unwrap())).collect();
+            xxtea_decrypt(&mut v, &k, CYCLES);
+            from_words(&v, block);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_tea {
+    use super::*;
+
+    // checked with the reference C code of the designers
+    const KEY: [u8; 16] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+
+    #[test]
+    fn tea_encrypt_decrypt() {
+        let vectors = [
+            ([0x00; 16], [0x00; 8], [0x41, 0xEA, 0x3A, 0x0A, 0x94, 0xBA, 0xA9, 0x40]),
+            (KEY, [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF], [0x14, 0xF0, 0xC7, 0x5D, 0x2B, 0xEB, 0xD9, 0x8D]),
+        ];
+
+        for (key, plain, expected) in vectors {
+            let mut ciphertext = [0; 8];
+            TEA32::cipher(&plain, &mut ciphertext, &key).expect("Error during TEA execution");
+            assert_eq!(ciphertext, expected);
+
+            let mut decrypted = [0; 8];
+            TEA32::decipher(&mut decrypted, &ciphertext, &key).expect("Error during TEA execution");
+            assert_eq!(decrypted, plain);
+        }
+    }
+
+    #[test]
+    fn xtea_encrypt_decrypt() {
+        let vectors = [
+            ([0x00; 16], [0x00; 8], [0xDE, 0xE9, 0xD4, 0xD8, 0xF7, 0x13, 0x1E, 0xD9]),
+            (KEY, *b"ABCDEFGH", [0x49, 0x7D, 0xF3, 0xD0, 0x72, 0x61, 0x2C, 0xB5]),
+        ];
+
+        for (key, plain, expected) in vectors {
+            let mut blocks = [plain];
+            XTEA32::cipher_blocks(&mut blocks, &key).expect("Error during XTEA execution");
+            assert_eq!(blocks, [expected]);
+
+            XTEA32::decipher_blocks(&mut blocks, &key).expect("Error during XTEA execution");
+            assert_eq!(blocks, [plain]);
+        }
+    }
+
+    #[test]
+    fn xxtea_encrypt_decrypt() {
+        let mut ciphertext = [0; 8];
+        XXTEA64::cipher(&[0; 8], &mut ciphertext, &[0; 16]).expect("Error during XXTEA execution");
+        assert_eq!(ciphertext, [0x05, 0x37, 0x04, 0xAB, 0x57, 0x5D, 0x8C, 0x80]);
+
+        let plain = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+        let mut ciphertext = [0; 16];
+        XXTEA128::cipher(&plain, &mut ciphertext, &KEY).expect("Error during XXTEA execution");
+        assert_eq!(ciphertext, [0x04, 0xAA, 0x55, 0xA8, 0xCE, 0x39, 0x89, 0xBA, 0x25, 0x72, 0x97, 0x0B, 0x8D, 0xEE, 0x76, 0x4F]);
+
+        let mut decrypted = [0; 16];
+        XXTEA128::decipher(&mut decrypted, &ciphertext, &KEY).expect("Error during XXTEA execution");
+        assert_eq!(decrypted, plain);
+
+        assert!(XXTEA::<4, 32>::cipher(&[0; 4], &mut [0; 4], &KEY).is_err());
+        assert!(XXTEA::<10, 32>::cipher(&[0; 10], &mut [0; 10], &KEY).is_err());
+    }
+
+    #[test]
+    fn tea_reduced_cycles() {
+        let plain = *b"ABCDEFGH";
+
+        let mut ciphertext = [0; 8];
+        XTEA::<8>::cipher(&plain, &mut ciphertext, &KEY).expect("Error during XTEA execution");
+        let mut decrypted = [0; 8];
+        XTEA::<8>::decipher(&mut decrypted, &ciphertext, &KEY).expect("Error during XTEA execution");
+        assert_eq!(decrypted, plain);
+
+        let mut blocks = [[0; 12]; 2];
+        XXTEA::<12, 3>::cipher_blocks(&mut blocks, &KEY).expect("Error during XXTEA execution");
+        XXTEA::<12, 3>::decipher_blocks(&mut blocks, &KEY).expect("Error during XXTEA execution");
+        assert_eq!(blocks, [[0; 12]; 2]);
+    }
+}
@@ -43,6 +43,24 @@ impl BlockCipher for TripleDES2K{
 
         Ok(())
     }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let k1 = extract_array_from_slice(key.as_slice(), 0)?;
+        let k2 = extract_array_from_slice(key.as_slice(), 8)?;
+
+        DES::cipher_blocks(blocks, &k1)?;
+        DES::decipher_blocks(blocks, &k2)?;
+        DES::cipher_blocks(blocks, &k1)
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let k1 = extract_array_from_slice(key.as_slice(), 0)?;
+        let k2 = extract_array_from_slice(key.as_slice(), 8)?;
+
+        DES::decipher_blocks(blocks, &k1)?;
+        DES::cipher_blocks(blocks, &k2)?;
+        DES::decipher_blocks(blocks, &k1)
+    }
 }
 
 impl BlockCipher for TripleDES3K{
@@ -83,6 +101,26 @@ impl BlockCipher for TripleDES3K{
 
         Ok(())
     }
+
+    fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let k1 = extract_array_from_slice(key.as_slice(), 0)?;
+        let k2 = extract_array_from_slice(key.as_slice(), 8)?;
+        let k3 = extract_array_from_slice(key.as_slice(), 16)?;
+
+        DES::cipher_blocks(blocks, &k1)?;
+        DES::decipher_blocks(blocks, &k2)?;
+        DES::cipher_blocks(blocks, &k3)
+    }
+
+    fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+        let k1 = extract_array_from_slice(key.as_slice(), 0)?;
+        let k2 = extract_array_from_slice(key.as_slice(), 8)?;
+        let k3 = extract_array_from_slice(key.as_slice(), 16)?;
+
+        DES::decipher_blocks(blocks, &k3)?;
+        DES::cipher_blocks(blocks, &k2)?;
+        DES::decipher_blocks(blocks, &k1)
+    }
 }
 
 
@@ -135,4 +173,25 @@ mod tests_triple_des {
         TripleDES3K::decipher(&mut plain, &ciphertext, &key).expect("Error during DES execution");
         assert_eq!(plain, expected);
     }
+
+    #[test]
+    fn triple_des_3k_encrypt_blocks() {
+        let mut blocks = [[0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96]; 5];
+        let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF,
+            0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01,
+            0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23];
+        let expected = [[0x71, 0x47, 0x72, 0xF3, 0x39, 0x84, 0x1D, 0x34]; 5];
+        TripleDES3K::cipher_blocks(&mut blocks, &key).expect("Error during DES execution");
+        assert_eq!(blocks, expected);
+    }
+
+    #[test]
+    fn triple_des_2k_decrypt_blocks() {
+        let mut blocks = [[0x06, 0xED, 0xE3, 0xD8, 0x28, 0x84, 0x09, 0x0A]; 5];
+        let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF,
+            0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01];
+        let expected = [[0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96]; 5];
+        TripleDES2K::decipher_blocks(&mut blocks, &key).expect("Error during DES execution");
+        assert_eq!(blocks, expected);
+    }
 }
@@ -0,0 +1,307 @@
+//! Definition of Twofish128, Twofish192 and Twofish256 (Schneier et al., 1998)
+//!
+//! Twofish is a 16-round Feistel network on four 32-bit words, with key-dependent S-boxes:
+//!```text
+//!   R0..R3 = P xor K0..K3
+//!   for r in 0..16:
+//!     T0 = g(R0),  T1 = g(R1 <<< 8)
+//!     R2 = (R2 xor (T0 + T1 + K(2r+8))) >>> 1
+//!     R3 = (R3 <<< 1) xor (T0 + 2*T1 + K(2r+9))
+//!     swap(R0 || R1, R2 || R3)
+//!   C = (R2 || R3 || R0 || R1) xor K4..K7
+//! ```
+//! `g` is made of the fixed permutations `q0`/`q1` xored with key material, followed by an MDS matrix.
+//! Words are little endian.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+const Q0: [u8; 256] = [
+    0xA9, 0x67, 0xB3, 0xE8, 0x04, 0xFD, 0xA3, 0x76, 0x9A, 0x92, 0x80, 0x78, 0xE4, 0xDD, 0xD1, 0x38,
+    0x0D, 0xC6, 0x35, 0x98, 0x18, 0xF7, 0xEC, 0x6C, 0x43, 0x75, 0x37, 0x26, 0xFA, 0x13, 0x94, 0x48,
+    0xF2, 0xD0, 0x8B, 0x30, 0x84, 0x54, 0xDF, 0x23, 0x19, 0x5B, 0x3D, 0x59, 0xF3, 0xAE, 0xA2, 0x82,
+    0x63, 0x01, 0x83, 0x2E, 0xD9, 0x51, 0x9B, 0x7C, 0xA6, 0xEB, 0xA5, 0xBE, 0x16, 0x0C, 0xE3, 0x61,
+    0xC0, 0x8C, 0x3A, 0xF5, 0x73, 0x2C, 0x25, 0x0B, 0xBB, 0x4E, 0x89, 0x6B, 0x53, 0x6A, 0xB4, 0xF1,
+    0xE1, 0xE6, 0xBD, 0x45, 0xE2, 0xF4, 0xB6, 0x66, 0xCC, 0x95, 0x03, 0x56, 0xD4, 0x1C, 0x1E, 0xD7,
+    0xFB, 0xC3, 0x8E, 0xB5, 0xE9, 0xCF, 0xBF, 0xBA, 0xEA, 0x77, 0x39, 0xAF, 0x33, 0xC9, 0x62, 0x71,
+    0x81, 0x79, 0x09, 0xAD, 0x24, 0xCD, 0xF9, 0xD8, 0xE5, 0xC5, 0xB9, 0x4D, 0x44, 0x08, 0x86, 0xE7,
+    0xA1, 0x1D, 0xAA, 0xED, 0x06, 0x70, 0xB2, 0xD2, 0x41, 0x7B, 0xA0, 0x11, 0x31, 0xC2, 0x27, 0x90,
+    0x20, 0xF6, 0x60, 0xFF, 0x96, 0x5C, 0xB1, 0xAB, 0x9E, 0x9C, 0x52, 0x1B, 0x5F, 0x93, 0x0A, 0xEF,
+    0x91, 0x85, 0x49, 0xEE, 0x2D, 0x4F, 0x8F, 0x3B, 0x47, 0x87, 0x6D, 0x46, 0xD6, 0x3E, 0x69, 0x64,
+    0x2A, 0xCE, 0xCB, 0x2F, 0xFC, 0x97, 0x05, 0x7A, 0xAC, 0x7F, 0xD5, 0x1A, 0x4B, 0x0E, 0xA7, 0x5A,
+    0x28, 0x14, 0x3F, 0x29, 0x88, 0x3C, 0x4C, 0x02, 0xB8, 0xDA, 0xB0, 0x17, 0x55, 0x1F, 0x8A, 0x7D,
+    0x57, 0xC7, 0x8D, 0x74, 0xB7, 0xC4, 0x9F, 0x72, 0x7E, 0x15, 0x22, 0x12, 0x58, 0x07, 0x99, 0x34,
+    0x6E, 0x50, 0xDE, 0x68, 0x65, 0xBC, 0xDB, 0xF8, 0xC8, 0xA8, 0x2B, 0x40, 0xDC, 0xFE, 0x32, 0xA4,
+    0xCA, 0x10, 0x21, 0xF0, 0xD3, 0x5D, 0x0F, 0x00, 0x6F, 0x9D, 0x36, 0x42, 0x4A, 0x5E, 0xC1, 0xE0,
+];
+
+const Q1: [u8; 256] = [
+    0x75, 0xF3, 0xC6, 0xF4, 0xDB, 0x7B, 0xFB, 0xC8, 0x4A, 0xD3, 0xE6, 0x6B, 0x45, 0x7D, 0xE8, 0x4B,
+    0xD6, 0x32, 0xD8, 0xFD, 0x37, 0x71, 0xF1, 0xE1, 0x30, 0x0F, 0xF8, 0x1B, 0x87, 0xFA, 0x06, 0x3F,
+    0x5E, 0xBA, 0xAE, 0x5B, 0x8A, 0x00, 0xBC, 0x9D, 0x6D, 0xC1, 0xB1, 0x0E, 0x80, 0x5D, 0xD2, 0xD5,
+    0xA0, 0x84, 0x07, 0x14, 0xB5, 0x90, 0x2C, 0xA3, 0xB2, 0x73, 0x4C, 0x54, 0x92, 0x74, 0x36, 0x51,
+    0x38, 0xB0, 0xBD, 0x5A, 0xFC, 0x60, 0x62, 0x96, 0x6C, 0x42, 0xF7, 0x10, 0x7C, 0x28, 0x27, 0x8C,
+    0x13, 0x95, 0x9C, 0xC7, 0x24, 0x46, 0x3B, 0x70, 0xCA, 0xE3, 0x85, 0xCB, 0x11, 0xD0, 0x93, 0xB8,
+    0xA6, 0x83, 0x20, 0xFF, 0x9F, 0x77, 0xC3, 0xCC, 0x03, 0x6F, 0x08, 0xBF, 0x40, 0xE7, 0x2B, 0xE2,
+    0x79, 0x0C, 0xAA, 0x82, 0x41, 0x3A, 0xEA, 0xB9, 0xE4, 0x9A, 0xA4, 0x97, 0x7E, 0xDA, 0x7A, 0x17,
+    0x66, 0x94, 0xA1, 0x1D, 0x3D, 0xF0, 0xDE, 0xB3, 0x0B, 0x72, 0xA7, 0x1C, 0xEF, 0xD1, 0x53, 0x3E,
+    0x8F, 0x33, 0x26, 0x5F, 0xEC, 0x76, 0x2A, 0x49, 0x81, 0x88, 0xEE, 0x21, 0xC4, 0x1A, 0xEB, 0xD9,
+    0xC5, 0x39, 0x99, 0xCD, 0xAD, 0x31, 0x8B, 0x01, 0x18, 0x23, 0xDD, 0x1F, 0x4E, 0x2D, 0xF9, 0x48,
+    0x4F, 0xF2, 0x65, 0x8E, 0x78, 0x5C, 0x58, 0x19, 0x8D, 0xE5, 0x98, 0x57, 0x67, 0x7F, 0x05, 0x64,
+    0xAF, 0x63, 0xB6, 0xFE, 0xF5, 0xB7, 0x3C, 0xA5, 0xCE, 0xE9, 0x68, 0x44, 0xE0, 0x4D, 0x43, 0x69,
+    0x29, 0x2E, 0xAC, 0x15, 0x59, 0xA8, 0x0A, 0x9E, 0x6E, 0x47, 0xDF, 0x34, 0x35, 0x6A, 0xCF, 0xDC,
+    0x22, 0xC9, 0xC0, 0x9B, 0x89, 0xD4, 0xED, 0xAB, 0x12, 0xA2, 0x0D, 0x52, 0xBB, 0x02, 0x2F, 0xA9,
+    0xD7, 0x61, 0x1E, 0xB4, 0x50, 0x04, 0xF6, 0xC2, 0x16, 0x25, 0x86, 0x56, 0x55, 0x09, 0xBE, 0x91,
+];
+
+const MDS: [[u8; 4]; 4] = [
+    [0x01, 0xEF, 0x5B, 0x5B],
+    [0x5B, 0xEF, 0xEF, 0x01],
+    [0xEF, 0x5B, 0x01, 0xEF],
+    [0xEF, 0x01, 0xEF, 0x5B],
+];
+
+const RS: [[u8; 8]; 4] = [
+    [0x01, 0xA4, 0x55, 0x87, 0x5A, 0x58, 0xDB, 0x9E],
+    [0xA4, 0x56, 0x82, 0xF3, 0x1E, 0xC6, 0x68, 0xE5],
+    [0x02, 0xA1, 0xFC, 0xC1, 0x47, 0xAE, 0x3D, 0x19],
+    [0xA4, 0x55, 0x87, 0x5A, 0x58, 0xDB, 0x9E, 0x03],
+];
+
+// multiplication in GF(2^8) with the given reduction polynomial
+fn gf_mul(mut a: u8, mut b: u8, poly: u16) -> u8 {
+    let mut res = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            res ^= a;
+        }
+        a = ((a as u16) << 1 ^ if a & 0x80 != 0 { poly } else { 0 }) as u8;
+        b >>= 1;
+    }
+    res
+}
+
+// h function: `l` holds the key words, from L0 to L(k-1)
+fn h(x: u32, l: &[u32]) -> u32 {
+    let mut y = x.to_le_bytes();
+    let l: Vec<[u8; 4]> = l.iter().map(|w| w.to_le_bytes()).collect();
+
+    if l.len() == 4 {
+        y = [Q1[y[0] as usize] ^ l[3][0], Q0[y[1] as usize] ^ l[3][1], Q0[y[2] as usize] ^ l[3][2], Q1[y[3] as usize] ^ l[3][3]];
+    }
+    if l.len() >= 3 {
+        y = [Q1[y[0] as usize] ^ l[2][0], Q1[y[1] as usize] ^ l[2][1], Q0[y[2] as usize] ^ l[2][2], Q0[y[3] as usize] ^ l[2][3]];
+    }
+    y = [
+        Q1[(Q0[(Q0[y[0] as usize] ^ l[1][0]) as usize] ^ l[0][0]) as usize],
+        Q0[(Q0[(Q1[y[1] as usize] ^ l[1][1]) as usize] ^ l[0][1]) as usize],
+        Q1[(Q1[(Q0[y[2] as usize] ^ l[1][2]) as usize] ^ l[0][2]) as usize],
+        Q0[(Q1[(Q1[y[3] as usize] ^ l[1][3]) as usize] ^ l[0][3]) as usize],
+    ];
+
+    // MDS matrix multiplication
+    let mut z = [0; 4];
+    for i in 0..4 {
+        for j in 0..4 {
+            z[i] ^= gf_mul(MDS[i][j], y[j], 0x169);
+        }
+    }
+    u32::from_le_bytes(z)
+}
+
+struct ExpandedKey {
+    k: [u32; 40],
+    s: Vec<u32>,  // S-box key words, from S(k-1) to S0
+}
+
+fn key_expansion(key: &[u8]) -> ExpandedKey {
+    let m: Vec<u32> = key.chunks_exact(4).map(|w| u32::from_le_bytes(w.try_into().unwrap())).collect();
+    let m_even: Vec<u32> = m.iter().step_by(2).copied().collect();
+    let m_odd: Vec<u32> = m.iter().skip(1).step_by(2).copied().collect();
+
+    // S-box key words, computed with the RS matrix
+    let mut s: Vec<u32> = key.chunks_exact(8).map(|chunk| {
+        let mut word = [0; 4];
+        for i in 0..4 {
+            for j in 0..8 {
+                word[i] ^= gf_mul(RS[i][j], chunk[j], 0x14D);
+            }
+        }
+        u32::from_le_bytes(word)
+    }).collect();
+    s.reverse();
+
+    // round subkeys
+    const RHO: u32 = 0x01010101;
+    let mut k = [0; 40];
+    for i in 0..20 {
+        let a = h((2 * i as u32).wrapping_mul(RHO), &m_even);
+        let b = h((2 * i as u32 + 1).wrapping_mul(RHO), &m_odd).rotate_left(8);
+        k[2 * i] = a.wrapping_add(b);
+        k[2 * i + 1] = a.wrapping_add(b.wrapping_mul(2)).rotate_left(9);
+    }
+
+    ExpandedKey { k, s }
+}
+
+fn block_to_words(block: &[u8; 16]) -> [u32; 4] {
+    core::array::from_fn(|i| u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap()))
+}
+
+fn words_to_block(words: &[u32; 4]) -> [u8; 16] {
+    let mut block = [0; 16];
+    for i in 0..4 {
+        block[i * 4..i * 4 + 4].copy_from_slice(&words[i].to_le_bytes());
+    }
+    block
+}
+
+// round function F, returns the values xored into R2 and R3
+fn function_f(r0: u32, r1: u32, round: usize, key: &ExpandedKey) -> (u32, u32) {
+    let t0 = h(r0, &key.s);
+    let t1 = h(r1.rotate_left(8), &key.s);
+    (
+        t0.wrapping_add(t1).wrapping_add(key.k[2 * round + 8]),
+        t0.wrapping_add(t1.wrapping_mul(2)).wrapping_add(key.k[2 * round + 9]),
+    )
+}
+
+fn encrypt_block(input: &[u8; 16], key: &ExpandedKey) -> [u8; 16] {
+    let mut r = block_to_words(input);
+    for (word, k) in r.iter_mut().zip(&key.k) {
+        *word ^= k;
+    }
+
+    for round in 0..16 {
+        let (f0, f1) = function_f(r[0], r[1], round, key);
+        r = [r[0], r[1], (r[2] ^ f0).rotate_right(1), r[3].rotate_left(1) ^ f1];
+        r = [r[2], r[3], r[0], r[1]];
+    }
+
+    // undo last swap and output whitening
+    words_to_block(&[r[2] ^ key.k[4], r[3] ^ key.k[5], r[0] ^ key.k[6], r[1] ^ key.k[7]])
+}
+
+fn decrypt_block(input: &[u8; 16], key: &ExpandedKey) -> [u8; 16] {
+    let c = block_to_words(input);
+    let mut r = [c[2] ^ key.k[6], c[3] ^ key.k[7], c[0] ^ key.k[4], c[1] ^ key.k[5]];
+
+    for round in (0..16).rev() {
+        r = [r[2], r[3], r[0], r[1]];
+        let (f0, f1) = function_f(r[0], r[1], round, key);
+        r = [r[0], r[1], r[2].rotate_left(1) ^ f0, (r[3] ^ f1).rotate_right(1)];
+    }
+
+    for (word, k) in r.iter_mut().zip(&key.k) {
+        *word ^= k;
+    }
+    words_to_block(&r)
+}
+
+macro_rules! define_twofish {
+    ( $twofish_name:ident, $key_length:expr) => {
+        pub struct $twofish_name {}
+
+        impl BlockCipher for $twofish_name {
+            const KEY_SIZE: usize = $key_length;
+            const BLOCK_SIZE: usize = 16;
+
+            type KeyType = [u8; $key_length];
+            type BlockType = [u8; 16];
+
+            fn cipher(plaintext: &Self::BlockType, ciphertext: &mut Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *ciphertext = encrypt_block(plaintext, &key_expansion(key));
+                Ok(())
+            }
+
+            fn decipher(plaintext: &mut Self::BlockType, ciphertext: &Self::BlockType, key: &Self::KeyType) -> Result<(), &'static str> {
+                *plaintext = decrypt_block(ciphertext, &key_expansion(key));
+                Ok(())
+            }
+
+            fn cipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let expanded_key = key_expansion(key);
+                for block in blocks.iter_mut() {
+                    *block = encrypt_block(block, &expanded_key);
+                }
+                Ok(())
+            }
+
+            fn decipher_blocks(blocks: &mut [Self::BlockType], key: &Self::KeyType) -> Result<(), &'static str> {
+                let expanded_key = key_expansion(key);
+                for block in blocks.iter_mut() {
+                    *block = decrypt_block(block, &expanded_key);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+define_twofish!(Twofish128, 16);
+define_twofish!(Twofish192, 24);
+define_twofish!(Twofish256, 32);
+
+#[cfg(test)]
+mod tests_twofish {
+    use super::*;
+
+    // cf. "Twofish: A 128-Bit Block Cipher", appendix A.1
+    const KEY: [u8; 32] = [
+        0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10,
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF,
+    ];
+
+    #[test]
+    fn twofish128_encrypt_decrypt() {
+        let expected = [0x9F, 0x58, 0x9F, 0x5C, 0xF6, 0x12, 0x2C, 0x32, 0xB6, 0xBF, 0xEC, 0x2F, 0x2A, 0xE8, 0xC3, 0x5A];
+        let mut ciphertext = [0; 16];
+        Twofish128::cipher(&[0; 16], &mut ciphertext, &[0; 16]).expect("Error during Twofish128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0xFF; 16];
+        Twofish128::decipher(&mut plain, &ciphertext, &[0; 16]).expect("Error during Twofish128 execution");
+        assert_eq!(plain, [0; 16]);
+    }
+
+    #[test]
+    fn twofish192_encrypt_decrypt() {
+        let key = KEY[..24].try_into().unwrap();
+        let expected = [0xCF, 0xD1, 0xD2, 0xE5, 0xA9, 0xBE, 0x9C, 0xDF, 0x50, 0x1F, 0x13, 0xB8, 0x92, 0xBD, 0x22, 0x48];
+        let mut ciphertext = [0; 16];
+        Twofish192::cipher(&[0; 16], &mut ciphertext, &key).expect("Error during Twofish192 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0xFF; 16];
+        Twofish192::decipher(&mut plain, &ciphertext, &key).expect("Error during Twofish192 execution");
+        assert_eq!(plain, [0; 16]);
+    }
+
+    #[test]
+    fn twofish256_encrypt_decrypt() {
+        let expected = [0x37, 0x52, 0x7B, 0xE0, 0x05, 0x23, 0x34, 0xB8, 0x9F, 0x0C, 0xFC, 0xCA, 0xE8, 0x7C, 0xFA, 0x20];
+        let mut ciphertext = [0; 16];
+        Twofish256::cipher(&[0; 16], &mut ciphertext, &KEY).expect("Error during Twofish256 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0xFF; 16];
+        Twofish256::decipher(&mut plain, &ciphertext, &KEY).expect("Error during Twofish256 execution");
+        assert_eq!(plain, [0; 16]);
+    }
+
+    #[test]
+    fn twofish128_encrypt_decrypt_blocks() {
+        // cf. ecb_tbl.txt from the Twofish submission package (iterations 1 and 2)
+        let plain = [[0; 16], [0x9F, 0x58, 0x9F, 0x5C, 0xF6, 0x12, 0x2C, 0x32, 0xB6, 0xBF, 0xEC, 0x2F, 0x2A, 0xE8, 0xC3, 0x5A]];
+        let expected = [
+            [0x9F, 0x58, 0x9F, 0x5C, 0xF6, 0x12, 0x2C, 0x32, 0xB6, 0xBF, 0xEC, 0x2F, 0x2A, 0xE8, 0xC3, 0x5A],
+            [0xD4, 0x91, 0xDB, 0x16, 0xE7, 0xB1, 0xC3, 0x9E, 0x86, 0xCB, 0x08, 0x6B, 0x78, 0x9F, 0x54, 0x19],
+        ];
+
+        let mut blocks = plain;
+        Twofish128::cipher_blocks(&mut blocks, &[0; 16]).expect("Error during Twofish128 execution");
+        assert_eq!(blocks, expected);
+
+        Twofish128::decipher_blocks(&mut blocks, &[0; 16]).expect("Error during Twofish128 execution");
+        assert_eq!(blocks, plain);
+    }
+}
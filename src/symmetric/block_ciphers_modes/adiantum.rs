@@ -0,0 +1,212 @@
+//! Definition of Adiantum length-preserving encryption (cf. "Adiantum: length-preserving encryption for
+//! entry-level processors", Crowley and Biggers)
+//!
+//! Encryption of `P = L || R` (`R` being the last 16 bytes) with the tweak `T`:
+//!```text
+//!   K_E || K_T || K_L || K_N = XChaCha12(K, nonce = 1)              (derived keys)
+//!
+//!   H(T, X) = Poly1305(K_T, [8|X|] || T) + Poly1305(K_L, NH(K_N, X))
+//!   P_M = R + H(T, L)                                              (modulo 2^128, little endian)
+//!   C_M = AES-256(K_E, P_M)
+//!   C_L = L xor XChaCha12(K, nonce = C_M || 1)
+//!   C_R = C_M - H(T, C_L)
+//!   C   = C_L || C_R
+//! ```
+//! Poly1305 is used without the final addition of `s`, and NH hashes 1024-byte chunks of the
+//! zero-padded input to 32 bytes each.
+//!
+//! Adiantum is a wide-block cipher like HCTR2 (see [crate::symmetric::block_ciphers_modes::hctr2]), fast
+//! on processors without AES instructions. There is no authentication, and the ciphertext has the same
+//! length as the plaintext (at least 16 bytes). It is used by Linux fscrypt (with XChaCha12 and a 32-byte
+//! tweak) to encrypt both filenames and contents.
+
+use crate::mac::poly1305::Poly1305;
+use crate::symmetric::block_ciphers::aes::AES256;
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::stream_ciphers::chacha::XChaChaInitStruct;
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+const NH_KEY_WORDS: usize = 268;
+const NH_CHUNK_SIZE: usize = 1024;
+
+struct DerivedKeys {
+    block_cipher: [u8; 32],
+    tweak_hash: [u8; 16],
+    message_hash: [u8; 16],
+    nh: [u32; NH_KEY_WORDS]
+}
+
+// NH with 4 passes over 16-byte units, the key being shifted by 16 bytes for each pass
+fn nh(key: &[u32; NH_KEY_WORDS], chunk: &[u8]) -> [u8; 32] {
+    let mut sums = [0u64; 4];
+    for (j, unit) in chunk.chunks_exact(16).enumerate() {
+        let m: [u32; 4] = core::array::from_fn(|i| u32::from_le_bytes(unit[4 * i..4 * i + 4].try_into().unwrap()));
+        for (i, sum) in sums.iter_mut().enumerate() {
+            let k = &key[4 * (i + j)..];
+            let a = m[0].wrapping_add(k[0]) as u64 * m[2].wrapping_add(k[2]) as u64;
+            let b = m[1].wrapping_add(k[1]) as u64 * m[3].wrapping_add(k[3]) as u64;
+            *sum = sum.wrapping_add(a).wrapping_add(b);
+        }
+    }
+
+    let mut res = [0; 32];
+    for (chunk, sum) in res.chunks_exact_mut(8).zip(sums) {
+        chunk.copy_from_slice(&sum.to_le_bytes());
+    }
+    res
+}
+
+// Poly1305 without the final addition of s
+fn poly1305(r: &[u8; 16]) -> Poly1305 {
+    let mut key = [0; 32];
+    key[..16].copy_from_slice(r);
+    Poly1305::new(&key)
+}
+
+/// Adiantum over the stream cipher `S` (XChaCha12, or XChaCha20) and AES-256.
+pub struct Adiantum<S>(std::marker::PhantomData<S>);
+
+impl<S> Adiantum<S>
+where
+    S: StreamCipher<KeyType = [u8; 32], InitStruct = XChaChaInitStruct>,
+{
+    fn stream(key: &[u8; 32], nonce: [u8; 24], input: &[u8], output: &mut [u8]) -> Result<(), &'static str> {
+        S::new(&XChaChaInitStruct { nonce, counter: 0 }, key).cipher(input, output)
+    }
+
+    fn derive_keys(key: &[u8; 32]) -> Result<DerivedKeys, &'static str> {
+        let mut nonce = [0; 24];
+        nonce[0] = 1;
+        let mut keys = [0; 64 + 4 * NH_KEY_WORDS];
+        Self::stream(key, nonce, &[0; 64 + 4 * NH_KEY_WORDS], &mut keys)?;
+
+        Ok(DerivedKeys {
+            block_cipher: keys[..32].try_into().unwrap(),
+            tweak_hash: keys[32..48].try_into().unwrap(),
+            message_hash: keys[48..64].try_into().unwrap(),
+            nh: core::array::from_fn(|i| u32::from_le_bytes(keys[64 + 4 * i..68 + 4 * i].try_into().unwrap())),
+        })
+    }
+
+    fn hash(keys: &DerivedKeys, tweak: &[u8], data: &[u8]) -> u128 {
+        let mut tweak_hash = poly1305(&keys.tweak_hash);
+        tweak_hash.update(&(8 * data.len() as u128).to_le_bytes());
+        tweak_hash.update(tweak);
+
+        let mut padded = data.to_vec();
+        padded.resize(data.len().next_multiple_of(16), 0);
+        let mut message_hash = poly1305(&keys.message_hash);
+        for chunk in padded.chunks(NH_CHUNK_SIZE) {
+            message_hash.update(&nh(&keys.nh, chunk));
+        }
+
+        u128::from_le_bytes(tweak_hash.finalise()).wrapping_add(u128::from_le_bytes(message_hash.finalise()))
+    }
+
+    // encryption and decryption only differ by the direction of the block cipher
+    fn process(key: &[u8; 32], tweak: &[u8], input: &[u8], output: &mut [u8], decrypt: bool) -> Result<(), &'static str> {
+        if input.len() != output.len() {
+            return Err("Input and output must have the same length");
+        }
+        if input.len() < 16 {
+            return Err("Data must be at least 16 bytes long");
+        }
+
+        let keys = Self::derive_keys(key)?;
+        let n = input.len() - 16;
+
+        let right = u128::from_le_bytes(input[n..].try_into().unwrap());
+        let first = right.wrapping_add(Self::hash(&keys, tweak, &input[..n])).to_le_bytes();
+        let mut second = [0; 16];
+        if decrypt {
+            AES256::decipher(&mut second, &first, &keys.block_cipher)?;
+        } else {
+            AES256::cipher(&first, &mut second, &keys.block_cipher)?;
+        }
+
+        let mut nonce = [0; 24];
+        nonce[..16].copy_from_slice(if decrypt { &first } else { &second });
+        nonce[16] = 1;
+        Self::stream(key, nonce, &input[..n], &mut output[..n])?;
+
+        let right = u128::from_le_bytes(second).wrapping_sub(Self::hash(&keys, tweak, &output[..n]));
+        output[n..].copy_from_slice(&right.to_le_bytes());
+        Ok(())
+    }
+
+    /// Encrypts `plaintext` (at least 16 bytes) with the `tweak` (of any length).
+    pub fn encrypt(key: &[u8; 32], tweak: &[u8], plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        Self::process(key, tweak, plaintext, ciphertext, false)
+    }
+
+    /// Decrypts `ciphertext` (at least 16 bytes) with the `tweak` (of any length).
+    pub fn decrypt(key: &[u8; 32], tweak: &[u8], ciphertext: &[u8], plaintext: &mut [u8]) -> Result<(), &'static str> {
+        Self::process(key, tweak, ciphertext, plaintext, true)
+    }
+}
+
+#[cfg(test)]
+mod tests_adiantum {
+    use crate::symmetric::stream_ciphers::chacha::XChaCha12;
+    use super::*;
+
+    // test vectors from a reference implementation of Adiantum
+    const KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+    ];
+    const TWEAK: [u8; 32] = [
+        0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F,
+        0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F,
+    ];
+
+    #[test]
+    fn adiantum_xchacha12() {
+        let plain: [u8; 64] = core::array::from_fn(|i| 0x80 + i as u8);
+        let expected = [
+            0x77, 0x67, 0xD0, 0x99, 0x7A, 0x61, 0x8B, 0x39, 0x8E, 0xCC, 0x5C, 0x73, 0xE3, 0xF6, 0x1F, 0x31,
+            0x70, 0xEA, 0x40, 0xCD, 0xF7, 0xD5, 0x4F, 0x9E, 0x1D, 0xDC, 0x84, 0x07, 0x3C, 0x96, 0xFC, 0xC3,
+            0xA6, 0xAE, 0x5D, 0x3C, 0xB2, 0x37, 0xD0, 0xA2, 0x9E, 0xF0, 0xD0, 0x17, 0x3E, 0xA0, 0x5D, 0x14,
+            0x01, 0x71, 0x8E, 0xF8, 0x8A, 0x63, 0x34, 0xEA, 0x29, 0x0E, 0xBD, 0x56, 0xC8, 0x14, 0x61, 0x68,
+        ];
+
+        let mut ciphertext = [0; 64];
+        Adiantum::<XChaCha12>::encrypt(&KEY, &TWEAK, &plain, &mut ciphertext).expect("Error during Adiantum execution");
+        assert_eq!(ciphertext, expected);
+        let mut decrypted = [0; 64];
+        Adiantum::<XChaCha12>::decrypt(&KEY, &TWEAK, &ciphertext, &mut decrypted).expect("Error during Adiantum execution");
+        assert_eq!(decrypted, plain);
+
+        // only the block cipher part, then a single byte of stream cipher
+        let expected = [0xF9, 0x8C, 0xFA, 0x9A, 0xED, 0xF1, 0x89, 0x26, 0x7A, 0x21, 0xDD, 0xC6, 0x81, 0xF5, 0xE7, 0xF3];
+        let mut ciphertext = [0; 16];
+        Adiantum::<XChaCha12>::encrypt(&KEY, &TWEAK, &plain[..16], &mut ciphertext).expect("Error during Adiantum execution");
+        assert_eq!(ciphertext, expected);
+
+        let expected = [
+            0x34, 0xE0, 0xE7, 0x38, 0x94, 0x68, 0x69, 0xA9, 0x44, 0x6B, 0xE3, 0x66, 0x02, 0x7E, 0x6A, 0x81,
+            0xC6,
+        ];
+        let mut ciphertext = [0; 17];
+        Adiantum::<XChaCha12>::encrypt(&KEY, &TWEAK, &plain[..17], &mut ciphertext).expect("Error during Adiantum execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn adiantum_several_nh_chunks() {
+        let plain: Vec<u8> = (0..1100).map(|i| i as u8).collect();
+        let mut ciphertext = vec![0; 1100];
+        Adiantum::<XChaCha12>::encrypt(&KEY, &TWEAK, &plain, &mut ciphertext).expect("Error during Adiantum execution");
+        assert_eq!(ciphertext[..16], [0xC2, 0xF1, 0xF1, 0xA0, 0xD2, 0x4B, 0x4C, 0x75, 0x87, 0xA0, 0xA2, 0xC5, 0xB5, 0x20, 0x01, 0xBA]);
+        assert_eq!(ciphertext[1084..], [0xCF, 0x20, 0x5C, 0xAC, 0x7C, 0xFB, 0xE5, 0xC2, 0xA4, 0x30, 0xF5, 0xC4, 0x87, 0x15, 0xBD, 0xBC]);
+
+        let mut decrypted = vec![0; 1100];
+        Adiantum::<XChaCha12>::decrypt(&KEY, &TWEAK, &ciphertext, &mut decrypted).expect("Error during Adiantum execution");
+        assert_eq!(decrypted, plain);
+
+        // a different tweak changes everything
+        let mut other = vec![0; 1100];
+        Adiantum::<XChaCha12>::encrypt(&KEY, &TWEAK[1..], &plain, &mut other).expect("Error during Adiantum execution");
+        assert!(ciphertext.iter().zip(&other).filter(|(a, b)| a == b).count() < 20);
+    }
+}
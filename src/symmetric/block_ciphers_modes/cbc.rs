@@ -29,10 +29,20 @@
 //!          |         |         |
 //!          C1        C2        Cn
 //! ```
+//!
+//! Ciphertext stealing variants (cf. NIST SP 800-38A addendum) treat data of any length (at least one
+//! block) without expansion. The last plaintext block `Pn` is padded with zeros, then the last 2
+//! ciphertext blocks are truncated/reordered (`C(n-1)*` is `C(n-1)` truncated to the length of `Pn`):
+//!```text
+//!   CBC-CS1: C1 ... C(n-2) C(n-1)* Cn
+//!   CBC-CS2: same as CBC-CS1 when Pn is a full block, same as CBC-CS3 otherwise
+//!   CBC-CS3: C1 ... C(n-2) Cn C(n-1)*      (used by Kerberos)
+//! ```
 
-use crate::symmetric::block_ciphers_modes::common::{BlockChainingWithIV};
+use crate::symmetric::block_ciphers_modes::common::{BlockChainingWithIV, StreamingBlockChaining};
 use crate::symmetric::block_ciphers::common::BlockCipher;
-use crate::utils::{check_cipher_params, extract_array_from_slice, xor_arrays};
+use crate::utils::{check_cipher_params, extract_array_from_slice, slice_to_arrays, xor_arrays};
+use paste::paste;
 
 pub struct CBC{}
 
@@ -40,59 +50,179 @@ impl<T, const NB: usize> BlockChainingWithIV<T> for CBC
 where
     T: BlockCipher<BlockType = [u8; NB]>,
 {
-    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str> {
-        let mut last_cipher = *iv;
+    type IVType = [u8; NB];
 
+    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
         // check parameters
         check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
 
-        // do the chain
-        let mut i = 0;
-        while i < plaintext.len() {
-            let mut chunk_p = extract_array_from_slice(plaintext, i)?;
-            let mut chunk_c = extract_array_from_slice(ciphertext, i)?;
+        ciphertext.copy_from_slice(plaintext);
+        let mut state = *iv;
+        <CBC as StreamingBlockChaining<T>>::cipher_in_place(ciphertext, key, &mut state)
+    }
 
-            chunk_p = xor_arrays(&chunk_p, &last_cipher);
+    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+        // check parameters
+        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
 
-            T::cipher(&chunk_p, &mut chunk_c, key)?;
-            for j in 0..T::BLOCK_SIZE {
-                ciphertext[i+j] = chunk_c[j].into();
-            }
-            last_cipher = chunk_c;
-            i += T::BLOCK_SIZE;
+        plaintext.copy_from_slice(ciphertext);
+        let mut state = *iv;
+        <CBC as StreamingBlockChaining<T>>::decipher_in_place(plaintext, key, &mut state)
+    }
+}
+
+impl<T, const NB: usize> StreamingBlockChaining<T> for CBC
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type State = [u8; NB];  // last ciphertext block (IV at the beginning)
+
+    fn cipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
         }
 
-        return Ok(());
+        // do the chain
+        for chunk in data.chunks_exact_mut(NB) {
+            let input = xor_arrays(&chunk.try_into().unwrap(), state);
+            T::cipher(&input, state, key)?;
+            chunk.copy_from_slice(state);
+        }
+
+        Ok(())
     }
 
-    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str> {
-        let mut last_cipher = *iv;
+    fn decipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
 
-        // check parameters
-        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
+        // block decryptions do not depend on each other, so they are all done at once
+        let ciphertext = slice_to_arrays::<NB, u8>(data);
+        let mut blocks = ciphertext.clone();
+        T::decipher_blocks(&mut blocks, key)?;
 
         // do the chain
-        let mut i = 0;
-        while i < plaintext.len() {
-            let mut chunk_p = extract_array_from_slice(plaintext, i)?;
-            let chunk_c = extract_array_from_slice(ciphertext, i)?;
-
-            T::decipher(&mut chunk_p, &chunk_c, key)?;
-            let xored = xor_arrays(&chunk_p, &last_cipher);
-            for j in 0..T::BLOCK_SIZE {
-                plaintext[i+j] = xored[j];
-            }
-            last_cipher = chunk_c;
-            i += T::BLOCK_SIZE;
+        for ((chunk, block), chunk_c) in data.chunks_exact_mut(NB).zip(blocks.iter()).zip(ciphertext.iter()) {
+            chunk.copy_from_slice(&xor_arrays(block, state));
+            *state = *chunk_c;
         }
 
-        return Ok(());
+        Ok(())
+    }
+}
+
+// returns the length of the last block (from 1 to the block size), with ciphertext stealing
+fn check_cs_params(p: &[u8], c: &[u8], block_size: usize) -> Result<usize, &'static str> {
+    if p.len() != c.len() {
+        return Err("Plaintext and ciphertext must have the same length");
+    } else if p.len() < block_size {
+        return Err("Length of plain/ciphertext must be at least the block size");
+    }
+    Ok((p.len() - 1) % block_size + 1)
+}
+
+// ciphertext is in the CBC-CS1 order
+fn cipher_cs1<T, const NB: usize>(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str>
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    let d = check_cs_params(plaintext, ciphertext, NB)?;
+    if d == NB {
+        return <CBC as BlockChainingWithIV<T>>::cipher(plaintext, ciphertext, key, iv);
     }
+
+    // CBC over the plaintext padded with zeros
+    let mut padded = plaintext.to_vec();
+    padded.resize(plaintext.len() + NB - d, 0);
+    let mut full = vec![0; padded.len()];
+    <CBC as BlockChainingWithIV<T>>::cipher(&padded, &mut full, key, iv)?;
+
+    // C(n-1) is truncated
+    let start = plaintext.len() - NB - d;
+    ciphertext[..start + d].copy_from_slice(&full[..start + d]);
+    ciphertext[start + d..].copy_from_slice(&full[full.len() - NB..]);
+
+    Ok(())
 }
 
+// ciphertext is in the CBC-CS1 order
+fn decipher_cs1<T, const NB: usize>(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str>
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    let d = check_cs_params(plaintext, ciphertext, NB)?;
+    if d == NB {
+        return <CBC as BlockChainingWithIV<T>>::decipher(plaintext, ciphertext, key, iv);
+    }
+
+    // Z = D(Cn) = (Pn || 0) xor C(n-1), so the end of Z is the end of C(n-1)
+    let start = ciphertext.len() - NB - d;
+    let mut z = [0; NB];
+    T::decipher(&mut z, &extract_array_from_slice(ciphertext, ciphertext.len() - NB)?, key)?;
+    let mut c_n1 = z;
+    c_n1[..d].copy_from_slice(&ciphertext[start..start + d]);
+    let p_n = xor_arrays(&z, &c_n1);
+
+    // the other blocks are plain CBC
+    let mut chain = ciphertext[..start].to_vec();
+    chain.extend_from_slice(&c_n1);
+    <CBC as BlockChainingWithIV<T>>::decipher(&mut plaintext[..start + NB], &chain, key, iv)?;
+    plaintext[start + NB..].copy_from_slice(&p_n[..d]);
+
+    Ok(())
+}
+
+// swaps C(n-1)* and Cn, when CBC-CS1 and the variant differ
+fn needs_swap(variant: u8, len: usize, block_size: usize) -> bool {
+    len > block_size && (variant == 3 || (variant == 2 && !len.is_multiple_of(block_size)))
+}
+
+macro_rules! define_cbc_cs {
+    ($variant:literal) => {
+        paste! {
+            #[doc = "CBC with ciphertext stealing, CS" $variant " variant."]
+            pub struct [<CBCCS $variant>] {}
+
+            impl<T, const NB: usize> BlockChainingWithIV<T> for [<CBCCS $variant>]
+            where
+                T: BlockCipher<BlockType = [u8; NB]>,
+            {
+                type IVType = [u8; NB];
+
+                fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+                    cipher_cs1::<T, NB>(plaintext, ciphertext, key, iv)?;
+                    if needs_swap($variant, ciphertext.len(), NB) {
+                        let d = (ciphertext.len() - 1) % NB + 1;
+                        let len = ciphertext.len();
+                        ciphertext[len - NB - d..].rotate_left(d);
+                    }
+                    Ok(())
+                }
+
+                fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+                    if needs_swap($variant, ciphertext.len(), NB) {
+                        let d = (ciphertext.len() - 1) % NB + 1;
+                        let mut reordered = ciphertext.to_vec();
+                        let len = reordered.len();
+                        reordered[len - NB - d..].rotate_right(d);
+                        return decipher_cs1::<T, NB>(plaintext, &reordered, key, iv);
+                    }
+                    decipher_cs1::<T, NB>(plaintext, ciphertext, key, iv)
+                }
+            }
+        }
+    };
+}
+
+define_cbc_cs!(1);
+define_cbc_cs!(2);
+define_cbc_cs!(3);
+
 #[cfg(test)]
 mod tests_cbc {
     use crate::symmetric::block_ciphers::aes::AES128;
+    use crate::symmetric::block_ciphers_modes::padding::PKCS7;
     use super::*;
 
     #[test]
@@ -202,4 +332,95 @@ mod tests_cbc {
         let res = <CBC as BlockChainingWithIV<AES128>>::decipher(&mut plain, &ciphertext, &key, &iv);
         assert!(res.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn cbc_padded() {
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let iv = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let expected = [0xF1, 0xB6, 0xDE, 0x3C, 0x8A, 0xBD, 0x2F, 0xDA, 0x80, 0x1B, 0x06, 0x0C, 0xDA, 0x3B, 0x50, 0x01, 0x4F, 0x94, 0xD2, 0xDD, 0xA5, 0x0B, 0x54, 0xBB, 0xE1, 0x06, 0x4D, 0x62, 0xF2, 0x62, 0xFB, 0x80];
+        let ciphertext = <CBC as BlockChainingWithIV<AES128>>::encrypt_padded::<PKCS7>(b"Hello, padding!!!!", &key, &iv).expect("Error during CBC_AES128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let plain = <CBC as BlockChainingWithIV<AES128>>::decrypt_padded::<PKCS7>(&ciphertext, &key, &iv).expect("Error during CBC_AES128 execution");
+        assert_eq!(plain, b"Hello, padding!!!!");
+
+        // the last byte of the IV changes the last padding byte of a single block
+        let res = <CBC as BlockChainingWithIV<AES128>>::decrypt_padded::<PKCS7>(&ciphertext[..16], &key, &iv);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn cbc_cs3_kerberos() {
+        // RFC 3962, appendix B
+        let key = [0x63, 0x68, 0x69, 0x63, 0x6B, 0x65, 0x6E, 0x20, 0x74, 0x65, 0x72, 0x69, 0x79, 0x61, 0x6B, 0x69];
+        let iv = [0; 16];
+        let plain = b"I would like the General Gau's Chicken, please, and wonton soup.";
+        let expected_17 = [
+            0xC6, 0x35, 0x35, 0x68, 0xF2, 0xBF, 0x8C, 0xB4, 0xD8, 0xA5, 0x80, 0x36, 0x2D, 0xA7, 0xFF, 0x7F,
+            0x97,
+        ];
+        let expected_31 = [
+            0xFC, 0x00, 0x78, 0x3E, 0x0E, 0xFD, 0xB2, 0xC1, 0xD4, 0x45, 0xD4, 0xC8, 0xEF, 0xF7, 0xED, 0x22,
+            0x97, 0x68, 0x72, 0x68, 0xD6, 0xEC, 0xCC, 0xC0, 0xC0, 0x7B, 0x25, 0xE2, 0x5E, 0xCF, 0xE5,
+        ];
+        let expected_32 = [
+            0x39, 0x31, 0x25, 0x23, 0xA7, 0x86, 0x62, 0xD5, 0xBE, 0x7F, 0xCB, 0xCC, 0x98, 0xEB, 0xF5, 0xA8,
+            0x97, 0x68, 0x72, 0x68, 0xD6, 0xEC, 0xCC, 0xC0, 0xC0, 0x7B, 0x25, 0xE2, 0x5E, 0xCF, 0xE5, 0x84,
+        ];
+
+        for (len, expected) in [(17, &expected_17[..]), (31, &expected_31[..]), (32, &expected_32[..])] {
+            let mut ciphertext = vec![0; len];
+            <CBCCS3 as BlockChainingWithIV<AES128>>::cipher(&plain[..len], &mut ciphertext, &key, &iv).expect("Error during CBC-CS3_AES128 execution");
+            assert_eq!(ciphertext, expected);
+
+            let mut decrypted = vec![0; len];
+            <CBCCS3 as BlockChainingWithIV<AES128>>::decipher(&mut decrypted, &ciphertext, &key, &iv).expect("Error during CBC-CS3_AES128 execution");
+            assert_eq!(decrypted, plain[..len]);
+        }
+    }
+
+    #[test]
+    fn cbc_cs_variants() {
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let iv = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let plain: [u8; 40] = core::array::from_fn(|i| i as u8);
+        let expected_cs1 = [
+            0x7D, 0xF7, 0x6B, 0x0C, 0x1A, 0xB8, 0x99, 0xB3, 0x3E, 0x42, 0xF0, 0x47, 0xB9, 0x1B, 0x54, 0x6F,
+            0x1C, 0xAA, 0x80, 0x18, 0xC8, 0x0B, 0x15, 0xB8, 0xBB, 0xD3, 0x47, 0x5C, 0x6B, 0x2A, 0xB3, 0x26,
+            0xAA, 0xD2, 0x80, 0x13, 0x64, 0x41, 0x22, 0xC8,
+        ];
+        let expected_cs2 = [
+            0x7D, 0xF7, 0x6B, 0x0C, 0x1A, 0xB8, 0x99, 0xB3, 0x3E, 0x42, 0xF0, 0x47, 0xB9, 0x1B, 0x54, 0x6F,
+            0xBB, 0xD3, 0x47, 0x5C, 0x6B, 0x2A, 0xB3, 0x26, 0xAA, 0xD2, 0x80, 0x13, 0x64, 0x41, 0x22, 0xC8,
+            0x1C, 0xAA, 0x80, 0x18, 0xC8, 0x0B, 0x15, 0xB8,
+        ];
+
+        let mut cs1 = [0; 40];
+        let mut cs2 = [0; 40];
+        let mut cs3 = [0; 40];
+        <CBCCS1 as BlockChainingWithIV<AES128>>::cipher(&plain, &mut cs1, &key, &iv).expect("Error during CBC-CS1_AES128 execution");
+        <CBCCS2 as BlockChainingWithIV<AES128>>::cipher(&plain, &mut cs2, &key, &iv).expect("Error during CBC-CS2_AES128 execution");
+        <CBCCS3 as BlockChainingWithIV<AES128>>::cipher(&plain, &mut cs3, &key, &iv).expect("Error during CBC-CS3_AES128 execution");
+        assert_eq!(cs1, expected_cs1);
+        assert_eq!(cs2, expected_cs2);
+        assert_eq!(cs3, expected_cs2);
+
+        let mut decrypted = [0; 40];
+        <CBCCS1 as BlockChainingWithIV<AES128>>::decipher(&mut decrypted, &cs1, &key, &iv).expect("Error during CBC-CS1_AES128 execution");
+        assert_eq!(decrypted, plain);
+        let mut decrypted = [0; 40];
+        <CBCCS2 as BlockChainingWithIV<AES128>>::decipher(&mut decrypted, &cs2, &key, &iv).expect("Error during CBC-CS2_AES128 execution");
+        assert_eq!(decrypted, plain);
+
+        // full blocks: CS1 and CS2 are CBC
+        let mut cs2 = [0; 32];
+        let mut cbc = [0; 32];
+        <CBCCS2 as BlockChainingWithIV<AES128>>::cipher(&plain[..32], &mut cs2, &key, &iv).expect("Error during CBC-CS2_AES128 execution");
+        <CBC as BlockChainingWithIV<AES128>>::cipher(&plain[..32], &mut cbc, &key, &iv).expect("Error during CBC_AES128 execution");
+        assert_eq!(cs2, cbc);
+
+        // at least one block
+        let res = <CBCCS1 as BlockChainingWithIV<AES128>>::cipher(&plain[..15], &mut cs1[..15], &key, &iv);
+        assert!(res.is_err());
+    }
+}
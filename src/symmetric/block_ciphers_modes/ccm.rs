@@ -0,0 +1,463 @@
+//! Definition of CCM authenticated encryption mode (cf. NIST SP 800-38C and RFC 3610)
+//!
+//! Encryption:
+//!```text
+//!   B0 = flags || N || len(P)      (len(P) is encoded on L bytes)
+//!   T  = CBC-MAC(B0 || encode(A) || P)
+//!
+//!   Ai = (L - 1) || N || i
+//!   C  = P xor (E(A1) || E(A2) || ...)
+//!   U  = MSB_M(T xor E(A0))
+//! ```
+//!
+//! The nonce length gives L: `nonce.len() = 15 - L`, with L from 2 to 8 (nonces from 7 to 13 bytes).
+//! The tag length gives M: 4, 6, 8, 10, 12, 14 or 16 bytes.
+//!
+//! 3 variants are available:
+//! - [CCM]: the mode from SP 800-38C
+//! - [CCMStar]: CCM* from IEEE 802.15.4 (used by Zigbee), which also allows M = 0 (encryption only)
+//! - [BLECCM]: the Bluetooth Low Energy profile (AES-128, 13-byte nonce, 4-byte MIC)
+//!
+//! The length of the data must be known before the processing, so only one-shot functions are available.
+//! Decryption only releases the plaintext once the tag has been checked (in constant time).
+
+use crate::symmetric::block_ciphers::aes::AES128;
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::ctr::{CounterEndianness, CounterSize, CTRInitStruct, CTR};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+
+// checks the parameters and returns L
+fn check_parameters(nonce: &[u8], tag_len: usize, text_len: usize, allow_no_tag: bool) -> Result<usize, AeadError> {
+    if !(7..=13).contains(&nonce.len()) {
+        return Err(AeadError::InvalidNonceLength);
+    }
+    let valid_tag = (4..=16).contains(&tag_len) && tag_len.is_multiple_of(2);
+    if !(valid_tag || (allow_no_tag && tag_len == 0)) {
+        return Err(AeadError::InvalidTagLength);
+    }
+
+    let l = 15 - nonce.len();
+    if l < 8 && text_len as u128 >= 1 << (8 * l) {
+        return Err(AeadError::BadLength);
+    }
+    Ok(l)
+}
+
+// CBC-MAC over B0 || encode(A) || P (cf. SP 800-38C, A.2)
+fn cbc_mac<T, const NK: usize>(key: &[u8; NK], nonce: &[u8], l: usize, tag_len: usize, aad: &[u8], plaintext: &[u8]) -> Result<[u8; 16], AeadError>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // B0
+    let m_field = if tag_len == 0 { 0 } else { (tag_len as u8 - 2) / 2 };
+    let a_field = if aad.is_empty() { 0 } else { 1 };
+    let mut b0 = [0; 16];
+    b0[0] = (a_field << 6) | (m_field << 3) | (l as u8 - 1);
+    b0[1..1 + nonce.len()].copy_from_slice(nonce);
+    b0[16 - l..].copy_from_slice(&(plaintext.len() as u64).to_be_bytes()[8 - l..]);
+
+    // encoding of the additional data length
+    let mut aad_len = Vec::new();
+    if !aad.is_empty() {
+        if aad.len() < 0xFF00 {
+            aad_len.extend_from_slice(&(aad.len() as u16).to_be_bytes());
+        } else if aad.len() as u64 <= u32::MAX as u64 {
+            aad_len.extend_from_slice(&[0xFF, 0xFE]);
+            aad_len.extend_from_slice(&(aad.len() as u32).to_be_bytes());
+        } else {
+            aad_len.extend_from_slice(&[0xFF, 0xFF]);
+            aad_len.extend_from_slice(&(aad.len() as u64).to_be_bytes());
+        }
+    }
+
+    let mut mac = [0; 16];
+    T::cipher(&b0, &mut mac, key)?;
+
+    // additional data and plaintext are each padded with zeros to a multiple of the block size
+    let aad_part = aad_len.iter().chain(aad.iter());
+    let aad_padding = (16 - (aad_len.len() + aad.len()) % 16) % 16;
+    let text_padding = (16 - plaintext.len() % 16) % 16;
+    let zeros = [0u8; 16];
+    let data = aad_part
+        .chain(zeros[..aad_padding].iter())
+        .chain(plaintext.iter())
+        .chain(zeros[..text_padding].iter());
+
+    let mut block = [0; 16];
+    for (i, byte) in data.enumerate() {
+        block[i % 16] = mac[i % 16] ^ byte;
+        if i % 16 == 15 {
+            T::cipher(&block, &mut mac, key)?;
+        }
+    }
+
+    Ok(mac)
+}
+
+// CTR mode starting at the counter block A0
+fn counter_mode<T, const NK: usize>(key: &[u8; NK], nonce: &[u8], l: usize) -> CTR<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // the counter never overflows its L bytes, so a 64-bit counter gives the same blocks
+    let mut a0 = [0; 16];
+    a0[0] = l as u8 - 1;
+    a0[1..1 + nonce.len()].copy_from_slice(nonce);
+    let init = CTRInitStruct { counter_block: a0, counter_size: CounterSize::Bits64, endianness: CounterEndianness::BigEndian };
+    CTR::new(&init, key)
+}
+
+fn ccm_encrypt<T, const NK: usize>(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8], allow_no_tag: bool) -> Result<(), AeadError>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    let l = check_parameters(nonce, tag.len(), buffer.len(), allow_no_tag)?;
+
+    let mac = cbc_mac::<T, NK>(key, nonce, l, tag.len(), aad, buffer)?;
+
+    // E(A0) encrypts the tag, the data starts at A1
+    let mut ctr = counter_mode::<T, NK>(key, nonce, l);
+    let mut s0 = [0; 16];
+    ctr.cipher(&mac, &mut s0)?;
+    ctr.cipher_in_place(buffer)?;
+
+    tag.copy_from_slice(&s0[..tag.len()]);
+    Ok(())
+}
+
+// the plaintext is needed to check the tag: if the tag is not valid, `buffer` is encrypted again
+fn ccm_decrypt<T, const NK: usize>(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8], allow_no_tag: bool) -> Result<(), AeadError>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    let l = check_parameters(nonce, tag.len(), buffer.len(), allow_no_tag)?;
+
+    let mut ctr = counter_mode::<T, NK>(key, nonce, l);
+    let mut s0 = [0; 16];
+    ctr.cipher_in_place(&mut s0)?;
+    ctr.cipher_in_place(buffer)?;
+
+    let mac = cbc_mac::<T, NK>(key, nonce, l, tag.len(), aad, buffer)?;
+    let expected: Vec<u8> = mac.iter().zip(s0.iter()).take(tag.len()).map(|(a, b)| a ^ b).collect();
+    if let Err(error) = verify_tag(&expected, tag) {
+        let mut ctr = counter_mode::<T, NK>(key, nonce, l);
+        ctr.seek(16)?;
+        ctr.cipher_in_place(buffer)?;
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// CCM over the 128-bit block cipher `T`.
+///
+/// The tag length is given by the length of the `tag` buffer.
+pub struct CCM<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> CCM<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    pub fn encrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        ccm_encrypt::<T, NK>(key, nonce, aad, ciphertext, tag, false)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut decrypted = ciphertext.to_vec();
+        ccm_decrypt::<T, NK>(key, nonce, aad, &mut decrypted, tag, false)?;
+        plaintext.copy_from_slice(&decrypted);
+        Ok(())
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        ccm_encrypt::<T, NK>(key, nonce, aad, buffer, tag, false)
+    }
+
+    /// If the tag is not valid, `buffer` still holds the ciphertext on return.
+    pub fn decrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        ccm_decrypt::<T, NK>(key, nonce, aad, buffer, tag, false)
+    }
+}
+
+/// CCM* over the 128-bit block cipher `T` (cf. IEEE 802.15.4, annex B).
+///
+/// Same as [CCM], but an empty `tag` is allowed: data is then only encrypted, without authentication.
+pub struct CCMStar<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> CCMStar<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    pub fn encrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        ccm_encrypt::<T, NK>(key, nonce, aad, ciphertext, tag, true)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut decrypted = ciphertext.to_vec();
+        ccm_decrypt::<T, NK>(key, nonce, aad, &mut decrypted, tag, true)?;
+        plaintext.copy_from_slice(&decrypted);
+        Ok(())
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        ccm_encrypt::<T, NK>(key, nonce, aad, buffer, tag, true)
+    }
+
+    /// If the tag is not valid, `buffer` still holds the ciphertext on return.
+    pub fn decrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        ccm_decrypt::<T, NK>(key, nonce, aad, buffer, tag, true)
+    }
+}
+
+/// AES-CCM as used by Bluetooth Low Energy link layer encryption (cf. Bluetooth Core, Vol 6, Part E).
+///
+/// The nonce is built from the packet counter, the direction bit and the IV with [BLECCM::nonce].
+/// The additional data is the first byte of the PDU header, with the NESN, SN and MD bits set to 0 (they are
+/// cleared from `header`, which can be given as received).
+pub struct BLECCM;
+
+// clears the NESN, SN and MD bits of the PDU header
+const HEADER_MASK: u8 = 0xE3;
+
+impl BLECCM {
+    pub const NONCE_SIZE: usize = 13;
+    pub const MIC_SIZE: usize = 4;
+
+    /// Builds the nonce: 39-bit packet counter (little-endian), direction bit, then IV (IVm || IVs).
+    pub fn nonce(packet_counter: u64, direction: bool, iv: &[u8; 8]) -> Result<[u8; 13], &'static str> {
+        if packet_counter >= 1 << 39 {
+            return Err("Packet counter must fit in 39 bits");
+        }
+
+        let mut nonce = [0; 13];
+        nonce[..5].copy_from_slice(&packet_counter.to_le_bytes()[..5]);
+        if direction {
+            nonce[4] |= 0x80;
+        }
+        nonce[5..].copy_from_slice(iv);
+        Ok(nonce)
+    }
+
+    pub fn encrypt(key: &[u8; 16], nonce: &[u8; 13], header: u8, plaintext: &[u8], ciphertext: &mut [u8], mic: &mut [u8; 4]) -> Result<(), AeadError> {
+        CCM::<AES128>::encrypt(key, nonce, &[header & HEADER_MASK], plaintext, ciphertext, mic)
+    }
+
+    /// `plaintext` is only written if the MIC is valid.
+    pub fn decrypt(key: &[u8; 16], nonce: &[u8; 13], header: u8, ciphertext: &[u8], mic: &[u8; 4], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        CCM::<AES128>::decrypt(key, nonce, &[header & HEADER_MASK], ciphertext, mic, plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests_ccm {
+    use crate::symmetric::block_ciphers::aes::AES256;
+    use super::*;
+
+    const KEY: [u8; 16] = [0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F];
+
+    // RFC 3610, packet vector #1
+    const RFC_KEY: [u8; 16] = [0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF];
+    const RFC_NONCE: [u8; 13] = [0x00, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+    const RFC_AAD: [u8; 8] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+    const RFC_PLAIN: [u8; 23] = [
+        0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E,
+    ];
+    const RFC_CIPHER: [u8; 23] = [
+        0x58, 0x8C, 0x97, 0x9A, 0x61, 0xC6, 0x63, 0xD2, 0xF0, 0x66, 0xD0, 0xC2, 0xC0, 0xF9, 0x89, 0x80,
+        0x6D, 0x5F, 0x6B, 0x61, 0xDA, 0xC3, 0x84,
+    ];
+    const RFC_TAG: [u8; 8] = [0x17, 0xE8, 0xD1, 0x2C, 0xFD, 0xF9, 0x26, 0xE0];
+
+    #[test]
+    fn ccm_aes128_example_1() {
+        // SP 800-38C, C.1
+        let nonce = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16];
+        let mut ciphertext = [0; 4];
+        let mut tag = [0; 4];
+        CCM::<AES128>::encrypt(&KEY, &nonce, &RFC_AAD, &[0x20, 0x21, 0x22, 0x23], &mut ciphertext, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(ciphertext, [0x71, 0x62, 0x01, 0x5B]);
+        assert_eq!(tag, [0x4D, 0xAC, 0x25, 0x5D]);
+    }
+
+    #[test]
+    fn ccm_aes128_example_2() {
+        // SP 800-38C, C.2
+        let nonce = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17];
+        let aad: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 16] = core::array::from_fn(|i| 0x20 + i as u8);
+        let expected = [0xD2, 0xA1, 0xF0, 0xE0, 0x51, 0xEA, 0x5F, 0x62, 0x08, 0x1A, 0x77, 0x92, 0x07, 0x3D, 0x59, 0x3D];
+        let mut ciphertext = [0; 16];
+        let mut tag = [0; 6];
+        CCM::<AES128>::encrypt(&KEY, &nonce, &aad, &plain, &mut ciphertext, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, [0x1F, 0xC6, 0x4F, 0xBF, 0xAC, 0xCD]);
+    }
+
+    #[test]
+    fn ccm_aes128_example_3() {
+        // SP 800-38C, C.3
+        let nonce = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B];
+        let aad: [u8; 20] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 24] = core::array::from_fn(|i| 0x20 + i as u8);
+        let expected = [
+            0xE3, 0xB2, 0x01, 0xA9, 0xF5, 0xB7, 0x1A, 0x7A, 0x9B, 0x1C, 0xEA, 0xEC, 0xCD, 0x97, 0xE7, 0x0B,
+            0x61, 0x76, 0xAA, 0xD9, 0xA4, 0x42, 0x8A, 0xA5,
+        ];
+        let mut ciphertext = [0; 24];
+        let mut tag = [0; 8];
+        CCM::<AES128>::encrypt(&KEY, &nonce, &aad, &plain, &mut ciphertext, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, [0x48, 0x43, 0x92, 0xFB, 0xC1, 0xB0, 0x99, 0x51]);
+    }
+
+    #[test]
+    fn ccm_aes128_long_aad() {
+        // additional data length encoded as 0xFFFE || 4 bytes
+        let nonce: [u8; 13] = core::array::from_fn(|i| 0x10 + i as u8);
+        let aad = vec![0x5A; 0xFF00];
+        let plain: [u8; 32] = core::array::from_fn(|i| 0x20 + i as u8);
+        let expected = [
+            0x69, 0x91, 0x5D, 0xAD, 0x1E, 0x84, 0xC6, 0x37, 0x6A, 0x68, 0xC2, 0x96, 0x7E, 0x4D, 0xAB, 0x61,
+            0x5A, 0xE0, 0xFD, 0x1F, 0xAE, 0xC4, 0x4C, 0xC4, 0x84, 0x82, 0x85, 0x29, 0x46, 0x3C, 0xCF, 0x72,
+        ];
+        let expected_tag = [0x37, 0xE1, 0x49, 0xA5, 0x22, 0xA6, 0x5F, 0x50, 0x2D, 0x7F, 0xF5, 0x43, 0x67, 0x78, 0x45, 0xFE];
+        let mut ciphertext = [0; 32];
+        let mut tag = [0; 16];
+        CCM::<AES128>::encrypt(&KEY, &nonce, &aad, &plain, &mut ciphertext, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn ccm_aes256_no_aad() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 13] = core::array::from_fn(|i| 0x10 + i as u8);
+        let ciphertext = [
+            0x3D, 0x93, 0x6E, 0xCB, 0xF3, 0x8A, 0x50, 0x5F, 0x4F, 0x09, 0xBD, 0xB7, 0x82, 0x1B, 0x5E, 0x67,
+            0x72, 0x2D, 0x86, 0x1F, 0x18, 0xE4, 0xC0, 0xDF, 0xE7, 0xFE, 0x61, 0x03, 0xEB, 0x32, 0x78, 0x3E,
+        ];
+        let tag = [0x67, 0x9A, 0x85, 0x53, 0x3A, 0xE4, 0x24, 0x19, 0x4E, 0xA8, 0x78, 0x98, 0xBE, 0x4F, 0x28, 0x47];
+        let mut plain = [0; 32];
+        CCM::<AES256>::decrypt(&key, &nonce, &[], &ciphertext, &tag, &mut plain).expect("Error during CCM_AES256 execution");
+        assert_eq!(plain, core::array::from_fn::<u8, 32, _>(|i| 0x20 + i as u8));
+    }
+
+    #[test]
+    fn ccm_aes128_rfc3610() {
+        let mut ciphertext = [0; 23];
+        let mut tag = [0; 8];
+        CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(ciphertext, RFC_CIPHER);
+        assert_eq!(tag, RFC_TAG);
+
+        let mut plain = [0; 23];
+        CCM::<AES128>::decrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_CIPHER, &RFC_TAG, &mut plain).expect("Error during CCM_AES128 execution");
+        assert_eq!(plain, RFC_PLAIN);
+    }
+
+    #[test]
+    fn ccm_aes128_bad_tag() {
+        let mut plain = [0; 23];
+        let mut tag = RFC_TAG;
+        tag[0] ^= 0x80;
+        assert_eq!(CCM::<AES128>::decrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_CIPHER, &tag, &mut plain), Err(AeadError::AuthenticationFailed));
+        assert_eq!(plain, [0; 23]);
+        assert_eq!(CCM::<AES128>::decrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD[1..], &RFC_CIPHER, &RFC_TAG, &mut plain), Err(AeadError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn ccm_aes128_in_place() {
+        let mut buffer = RFC_PLAIN;
+        let mut tag = [0; 8];
+        CCM::<AES128>::encrypt_in_place(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &mut buffer, &mut tag).expect("Error during CCM_AES128 execution");
+        assert_eq!(buffer, RFC_CIPHER);
+        assert_eq!(tag, RFC_TAG);
+
+        // the ciphertext is restored when the tag is not valid
+        tag[0] ^= 0x80;
+        assert_eq!(CCM::<AES128>::decrypt_in_place(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &mut buffer, &tag), Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, RFC_CIPHER);
+
+        CCM::<AES128>::decrypt_in_place(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &mut buffer, &RFC_TAG).expect("Error during CCM_AES128 execution");
+        assert_eq!(buffer, RFC_PLAIN);
+    }
+
+    #[test]
+    fn ccm_bad_parameters() {
+        let mut ciphertext = [0; 23];
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut []), Err(AeadError::InvalidTagLength));
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut [0; 5]), Err(AeadError::InvalidTagLength));
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut [0; 18]), Err(AeadError::InvalidTagLength));
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE[0..6], &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut [0; 8]), Err(AeadError::InvalidNonceLength));
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &[0; 14], &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut [0; 8]), Err(AeadError::InvalidNonceLength));
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext[0..22], &mut [0; 8]), Err(AeadError::BadLength));
+
+        // L = 2: at most 2^16 - 1 bytes
+        let plain = vec![0; 1 << 16];
+        let mut ciphertext = vec![0; 1 << 16];
+        assert_eq!(CCM::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &[], &plain, &mut ciphertext, &mut [0; 8]), Err(AeadError::BadLength));
+    }
+
+    #[test]
+    fn ccm_star() {
+        // with a tag, CCM* is the same as CCM
+        let mut ciphertext = [0; 23];
+        let mut tag = [0; 8];
+        CCMStar::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut tag).expect("Error during CCM*_AES128 execution");
+        assert_eq!(ciphertext, RFC_CIPHER);
+        assert_eq!(tag, RFC_TAG);
+
+        // without a tag, data is only encrypted
+        let mut ciphertext = [0; 23];
+        CCMStar::<AES128>::encrypt(&RFC_KEY, &RFC_NONCE, &RFC_AAD, &RFC_PLAIN, &mut ciphertext, &mut []).expect("Error during CCM*_AES128 execution");
+        assert_eq!(ciphertext, RFC_CIPHER);
+
+        let mut plain = [0; 23];
+        CCMStar::<AES128>::decrypt(&RFC_KEY, &RFC_NONCE, &[], &RFC_CIPHER, &[], &mut plain).expect("Error during CCM*_AES128 execution");
+        assert_eq!(plain, RFC_PLAIN);
+    }
+
+    #[test]
+    fn ble_ccm() {
+        let key = [0x99, 0xAD, 0x1B, 0x52, 0x26, 0xA3, 0x7E, 0x3E, 0x05, 0x8E, 0x3B, 0x8E, 0x27, 0xC2, 0xC6, 0x66];
+        let iv = [0x24, 0xAB, 0xDC, 0xBA, 0xBE, 0xBA, 0xAF, 0xDE];
+        let nonce = BLECCM::nonce(1, true, &iv).expect("Error during BLE nonce computation");
+        assert_eq!(nonce, [0x01, 0x00, 0x00, 0x00, 0x80, 0x24, 0xAB, 0xDC, 0xBA, 0xBE, 0xBA, 0xAF, 0xDE]);
+
+        let mut ciphertext = [0; 1];
+        let mut mic = [0; 4];
+        BLECCM::encrypt(&key, &nonce, 0x03, &[0x06], &mut ciphertext, &mut mic).expect("Error during BLE CCM execution");
+        assert_eq!(ciphertext, [0x6B]);
+        assert_eq!(mic, [0xD0, 0xB4, 0x9B, 0x95]);
+
+        let mut plain = [0; 1];
+        BLECCM::decrypt(&key, &nonce, 0x03, &ciphertext, &mic, &mut plain).expect("Error during BLE CCM execution");
+        assert_eq!(plain, [0x06]);
+
+        // NESN, SN and MD are not authenticated
+        BLECCM::encrypt(&key, &nonce, 0x1F, &[0x06], &mut ciphertext, &mut mic).expect("Error during BLE CCM execution");
+        assert_eq!(ciphertext, [0x6B]);
+        assert_eq!(mic, [0xD0, 0xB4, 0x9B, 0x95]);
+        BLECCM::decrypt(&key, &nonce, 0x0B, &ciphertext, &mic, &mut plain).expect("Error during BLE CCM execution");
+        assert_eq!(plain, [0x06]);
+
+        assert!(BLECCM::nonce(1 << 39, false, &iv).is_err());
+    }
+}
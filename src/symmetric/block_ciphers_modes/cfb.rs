@@ -0,0 +1,376 @@
+//! Definition of CFB chaining mode (cf. NIST SP 800-38A)
+//!
+//! Encryption (s-bit segments):
+//!```text
+//!        IV         I2                 In
+//!        |          |                  |
+//!       ---        ---                ---
+//!      | K |      | K |      ...     | K |
+//!       ---        ---                ---
+//!        |          |                  |
+//!  s MSB |    s MSB |            s MSB |
+//!  P1 ---+    P2 ---+            Pn ---+
+//!        |          |                  |
+//!        C1 ------> C2 -- ... ------>  Cn
+//! ```
+//!
+//! The input block `Ij` is made of the last bits of `Ij-1` followed by `Cj-1`.
+//!
+//! The segment size `S` (in bits) is a parameter of the mode. It must be 1 or a multiple of 8 that
+//! is not greater than the block size. Aliases are defined for the usual sizes (CFB-1, CFB-8,
+//! CFB-64 and CFB-128).
+//!
+//! The encryptor and decryptor are streaming objects implementing [StreamCipher]: data can be
+//! given in chunks of any length (CFB-1 treats each byte as 8 segments of 1 bit, most significant
+//! bit first).
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::common::IVInitStruct;
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+// state shared by the encryptor and the decryptor
+struct CFBState<T: BlockCipher, const S: usize> {
+    key: T::KeyType,
+    register: T::BlockType,  // input block of the block cipher
+    keystream: T::BlockType,  // output block of the block cipher
+    segment: T::BlockType,  // ciphertext of the current segment (first `position` bytes)
+    position: usize  // number of bytes of the current segment already treated
+}
+
+impl<T, const S: usize, const NK: usize, const NB: usize> CFBState<T, S>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    fn new(init_struct: &IVInitStruct<NB>, key: &[u8; NK]) -> Self {
+        CFBState { key: *key, register: init_struct.iv, keystream: [0; NB], segment: [0; NB], position: 0 }
+    }
+
+    fn process(&mut self, input: &[u8], output: &mut [u8], decrypt: bool) -> Result<(), &'static str> {
+        // check parameters
+        if input.len() != output.len() {
+            return Err("Plaintext and ciphertext buffers must have the same length");
+        }
+        self.check_segment_size()?;
+
+        output.copy_from_slice(input);
+        self.process_in_place(output, decrypt)
+    }
+
+    fn process_in_place(&mut self, data: &mut [u8], decrypt: bool) -> Result<(), &'static str> {
+        self.check_segment_size()?;
+
+        for byte in data.iter_mut() {
+            *byte = if S == 1 {
+                self.process_bits(*byte, decrypt)?
+            } else {
+                self.process_byte(*byte, decrypt)?
+            };
+        }
+
+        Ok(())
+    }
+
+    fn check_segment_size(&self) -> Result<(), &'static str> {
+        if S == 0 || (S != 1 && !S.is_multiple_of(8)) || S > 8 * NB {
+            return Err("Invalid segment size");
+        }
+        Ok(())
+    }
+
+    // treats a byte of a segment of S >= 8 bits
+    fn process_byte(&mut self, input: u8, decrypt: bool) -> Result<u8, &'static str> {
+        let segment_len = S / 8;
+
+        if self.position == 0 {
+            T::cipher(&self.register, &mut self.keystream, &self.key)?;
+        }
+
+        let output = input ^ self.keystream[self.position];
+        self.segment[self.position] = if decrypt { input } else { output };
+        self.position += 1;
+
+        // end of the segment: shift it into the register
+        if self.position == segment_len {
+            self.register.copy_within(segment_len.., 0);
+            self.register[NB - segment_len..].copy_from_slice(&self.segment[..segment_len]);
+            self.position = 0;
+        }
+
+        Ok(output)
+    }
+
+    // treats a byte as 8 segments of 1 bit
+    fn process_bits(&mut self, input: u8, decrypt: bool) -> Result<u8, &'static str> {
+        let mut output = 0;
+
+        for i in (0..8).rev() {
+            T::cipher(&self.register, &mut self.keystream, &self.key)?;
+
+            let in_bit = (input >> i) & 1;
+            let out_bit = in_bit ^ (self.keystream[0] >> 7);
+            output |= out_bit << i;
+
+            // shift the ciphertext bit into the register
+            let mut carry = if decrypt { in_bit } else { out_bit };
+            for byte in self.register.iter_mut().rev() {
+                let next_carry = *byte >> 7;
+                *byte = *byte << 1 | carry;
+                carry = next_carry;
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// CFB encryptor over the block cipher `T`, with segments of `S` bits.
+pub struct CFBEncryptor<T: BlockCipher, const S: usize> {
+    state: CFBState<T, S>
+}
+
+/// CFB decryptor over the block cipher `T`, with segments of `S` bits.
+///
+/// `cipher` deciphers its first argument and puts the result in the second one.
+pub struct CFBDecryptor<T: BlockCipher, const S: usize> {
+    state: CFBState<T, S>
+}
+
+pub type CFB1Encryptor<T> = CFBEncryptor<T, 1>;
+pub type CFB1Decryptor<T> = CFBDecryptor<T, 1>;
+pub type CFB8Encryptor<T> = CFBEncryptor<T, 8>;
+pub type CFB8Decryptor<T> = CFBDecryptor<T, 8>;
+pub type CFB64Encryptor<T> = CFBEncryptor<T, 64>;
+pub type CFB64Decryptor<T> = CFBDecryptor<T, 64>;
+pub type CFB128Encryptor<T> = CFBEncryptor<T, 128>;
+pub type CFB128Decryptor<T> = CFBDecryptor<T, 128>;
+
+impl<T, const S: usize, const NK: usize, const NB: usize> StreamCipher for CFBEncryptor<T, S>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    const KEY_SIZE: usize = NK;
+    type KeyType = [u8; NK];
+    type InitStruct = IVInitStruct<NB>;
+    type State = [u8; NB];
+
+    fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+        CFBEncryptor { state: CFBState::new(init_struct, key) }
+    }
+
+    fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        self.state.process(plaintext, ciphertext, false)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        self.state.process_in_place(data, false)
+    }
+}
+
+impl<T, const S: usize, const NK: usize, const NB: usize> StreamCipher for CFBDecryptor<T, S>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    const KEY_SIZE: usize = NK;
+    type KeyType = [u8; NK];
+    type InitStruct = IVInitStruct<NB>;
+    type State = [u8; NB];
+
+    fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+        CFBDecryptor { state: CFBState::new(init_struct, key) }
+    }
+
+    fn cipher(&mut self, ciphertext: &[u8], plaintext: &mut [u8]) -> Result<(), &'static str> {
+        self.state.process(ciphertext, plaintext, true)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        self.state.process_in_place(data, true)
+    }
+}
+
+#[cfg(test)]
+mod tests_cfb {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use crate::symmetric::block_ciphers::des::DES;
+    use crate::symmetric::block_ciphers::triple_des::TripleDES3K;
+    use super::*;
+
+    const PLAIN: [u8; 64] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF,
+        0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10,
+    ];
+
+    const KEY: [u8; 16] = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+
+    const INIT: IVInitStruct<16> = IVInitStruct { iv: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F] };
+
+    // SP 800-38A, F.3.1 and F.3.2 (16 segments of 1 bit)
+    const CIPHER_CFB1: [u8; 2] = [0x68, 0xB3];
+
+    // SP 800-38A, F.3.7
+    const CIPHER_CFB8: [u8; 18] = [
+        0x3B, 0x79, 0x42, 0x4C, 0x9C, 0x0D, 0xD4, 0x36, 0xBA, 0xCE, 0x9E, 0x0E, 0xD4, 0x58, 0x6A, 0x4F,
+        0x32, 0xB9,
+    ];
+
+    // SP 800-38A, F.3.13
+    const CIPHER_CFB128: [u8; 64] = [
+        0x3B, 0x3F, 0xD9, 0x2E, 0xB7, 0x2D, 0xAD, 0x20, 0x33, 0x34, 0x49, 0xF8, 0xE8, 0x3C, 0xFB, 0x4A,
+        0xC8, 0xA6, 0x45, 0x37, 0xA0, 0xB3, 0xA9, 0x3F, 0xCD, 0xE3, 0xCD, 0xAD, 0x9F, 0x1C, 0xE5, 0x8B,
+        0x26, 0x75, 0x1F, 0x67, 0xA3, 0xCB, 0xB1, 0x40, 0xB1, 0x80, 0x8C, 0xF1, 0x87, 0xA4, 0xF4, 0xDF,
+        0xC0, 0x4B, 0x05, 0x35, 0x7C, 0x5D, 0x1C, 0x0E, 0xEA, 0xC4, 0xC6, 0x6F, 0x9F, 0xF7, 0xF2, 0xE6,
+    ];
+
+    #[test]
+    fn cfb1_aes128_encrypt() {
+        let mut ciphertext = [0; 2];
+        let mut cfb = CFB1Encryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&PLAIN[0..2], &mut ciphertext).expect("Error during CFB1_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_CFB1);
+    }
+
+    #[test]
+    fn cfb1_aes128_decrypt() {
+        let mut plain = [0; 2];
+        let mut cfb = CFB1Decryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&CIPHER_CFB1[0..1], &mut plain[0..1]).expect("Error during CFB1_AES128 execution");
+        cfb.cipher(&CIPHER_CFB1[1..2], &mut plain[1..2]).expect("Error during CFB1_AES128 execution");
+        assert_eq!(plain, PLAIN[0..2]);
+    }
+
+    #[test]
+    fn cfb1_aes128_gfsbox() {
+        // CAVP CFB1GFSbox128.rsp, COUNT = 0 and 1 (1-bit messages, compared with the first bit of a byte)
+        let vectors = [
+            ([0xF3, 0x44, 0x81, 0xEC, 0x3C, 0xC6, 0x27, 0xBA, 0xCD, 0x5D, 0xC3, 0xFB, 0x08, 0xF2, 0x73, 0xE6], 0),
+            ([0x97, 0x98, 0xC4, 0x64, 0x0B, 0xAD, 0x75, 0xC7, 0xC3, 0x22, 0x7D, 0xB9, 0x10, 0x17, 0x4E, 0x72], 1),
+        ];
+
+        for (iv, expected_bit) in vectors {
+            let init = IVInitStruct { iv };
+            let mut ciphertext = [0; 1];
+            let mut cfb = CFB1Encryptor::<AES128>::new(&init, &[0; 16]);
+            cfb.cipher(&[0x00], &mut ciphertext).expect("Error during CFB1_AES128 execution");
+            assert_eq!(ciphertext[0] >> 7, expected_bit);
+
+            let mut plain = [0; 1];
+            let mut cfb = CFB1Decryptor::<AES128>::new(&init, &[0; 16]);
+            cfb.cipher(&[expected_bit << 7], &mut plain).expect("Error during CFB1_AES128 execution");
+            assert_eq!(plain[0] >> 7, 0);
+        }
+    }
+
+    #[test]
+    fn cfb8_aes128_encrypt() {
+        let mut ciphertext = [0; 18];
+        let mut cfb = CFB8Encryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&PLAIN[0..18], &mut ciphertext).expect("Error during CFB8_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_CFB8);
+    }
+
+    #[test]
+    fn cfb8_aes128_decrypt() {
+        let mut plain = [0; 18];
+        let mut cfb = CFB8Decryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&CIPHER_CFB8[0..7], &mut plain[0..7]).expect("Error during CFB8_AES128 execution");
+        cfb.cipher(&CIPHER_CFB8[7..18], &mut plain[7..18]).expect("Error during CFB8_AES128 execution");
+        assert_eq!(plain, PLAIN[0..18]);
+    }
+
+    #[test]
+    fn cfb8_aes128_gfsbox() {
+        // CAVP CFB8GFSbox128.rsp, COUNT = 0
+        let init = IVInitStruct { iv: [0xF3, 0x44, 0x81, 0xEC, 0x3C, 0xC6, 0x27, 0xBA, 0xCD, 0x5D, 0xC3, 0xFB, 0x08, 0xF2, 0x73, 0xE6] };
+        let mut ciphertext = [0; 1];
+        let mut cfb = CFB8Encryptor::<AES128>::new(&init, &[0; 16]);
+        cfb.cipher(&[0x00], &mut ciphertext).expect("Error during CFB8_AES128 execution");
+        assert_eq!(ciphertext, [0x03]);
+    }
+
+    #[test]
+    fn cfb64_triple_des_3k() {
+        // CAVP TCFB64varkey.rsp (COUNT = 0 and 1) and TCFB64vartext.rsp (COUNT = 0 and 1), the 3 keys being equal
+        let vectors = [
+            ([0x80, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01], [0x00; 8], [0x95, 0xA8, 0xD7, 0x28, 0x13, 0xDA, 0xA9, 0x4D]),
+            ([0x40, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01], [0x00; 8], [0x0E, 0xEC, 0x14, 0x87, 0xDD, 0x8C, 0x26, 0xD5]),
+            ([0x01; 8], [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], [0x95, 0xF8, 0xA5, 0xE5, 0xDD, 0x31, 0xD9, 0x00]),
+            ([0x01; 8], [0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], [0xDD, 0x7F, 0x12, 0x1C, 0xA5, 0x01, 0x56, 0x19]),
+        ];
+
+        for (single_key, iv, expected) in vectors {
+            let key: [u8; 24] = core::array::from_fn(|i| single_key[i % 8]);
+            let init = IVInitStruct { iv };
+
+            let mut ciphertext = [0; 8];
+            let mut cfb = CFB64Encryptor::<TripleDES3K>::new(&init, &key);
+            cfb.cipher(&[0; 8], &mut ciphertext).expect("Error during CFB64_TDES execution");
+            assert_eq!(ciphertext, expected);
+
+            let mut plain = [0xFF; 8];
+            let mut cfb = CFB64Decryptor::<TripleDES3K>::new(&init, &key);
+            cfb.cipher(&expected, &mut plain).expect("Error during CFB64_TDES execution");
+            assert_eq!(plain, [0; 8]);
+        }
+    }
+
+    #[test]
+    fn cfb128_aes128_encrypt() {
+        let mut ciphertext = [0; 64];
+        let mut cfb = CFB128Encryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&PLAIN, &mut ciphertext).expect("Error during CFB128_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_CFB128);
+    }
+
+    #[test]
+    fn cfb128_aes128_encrypt_partial_segments() {
+        let mut ciphertext = [0; 60];
+        let mut cfb = CFB128Encryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&PLAIN[0..10], &mut ciphertext[0..10]).expect("Error during CFB128_AES128 execution");
+        cfb.cipher(&PLAIN[10..33], &mut ciphertext[10..33]).expect("Error during CFB128_AES128 execution");
+        cfb.cipher(&PLAIN[33..60], &mut ciphertext[33..60]).expect("Error during CFB128_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_CFB128[0..60]);
+    }
+
+    #[test]
+    fn cfb128_aes128_decrypt() {
+        let mut plain = [0; 64];
+        let mut cfb = CFB128Decryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher(&CIPHER_CFB128[0..17], &mut plain[0..17]).expect("Error during CFB128_AES128 execution");
+        cfb.cipher(&CIPHER_CFB128[17..64], &mut plain[17..64]).expect("Error during CFB128_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn cfb_aes128_in_place() {
+        let mut buffer = PLAIN;
+        let mut cfb = CFB128Encryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher_in_place(&mut buffer[0..17]).expect("Error during CFB128_AES128 execution");
+        cfb.cipher_in_place(&mut buffer[17..64]).expect("Error during CFB128_AES128 execution");
+        assert_eq!(buffer, CIPHER_CFB128);
+
+        let mut buffer = CIPHER_CFB1;
+        let mut cfb = CFB1Decryptor::<AES128>::new(&INIT, &KEY);
+        cfb.cipher_in_place(&mut buffer).expect("Error during CFB1_AES128 execution");
+        assert_eq!(buffer, PLAIN[0..2]);
+    }
+
+    #[test]
+    fn cfb_invalid_segment_size() {
+        let mut ciphertext = [0; 8];
+        let mut cfb = CFB128Encryptor::<DES>::new(&IVInitStruct { iv: [0; 8] }, &[0; 8]);
+        assert!(cfb.cipher(&[0; 8], &mut ciphertext).is_err());
+
+        let mut ciphertext = [0; 16];
+        let mut cfb = CFBEncryptor::<AES128, 12>::new(&INIT, &KEY);
+        assert!(cfb.cipher(&PLAIN[0..16], &mut ciphertext).is_err());
+    }
+
+    #[test]
+    fn cfb_bad_length() {
+        let mut ciphertext = [0; 15];
+        let mut cfb = CFB8Encryptor::<AES128>::new(&INIT, &KEY);
+        assert!(cfb.cipher(&PLAIN[0..16], &mut ciphertext).is_err());
+    }
+}
@@ -1,21 +1,79 @@
 //! Shared code between chaining modes
 
 use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::padding::Padding;
 
 /// Trait implemented by chaining modes that do not need an IV or anything else.
+///
+/// `encrypt_padded` and `decrypt_padded` treat data of any length, padded with `P`
+/// (see [crate::symmetric::block_ciphers_modes::padding]).
 pub trait BlockChaining<T>
 where
     T: BlockCipher,  // chaining only applies to block ciphers
 {
     fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType) -> Result<(), &'static str>;
     fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType) -> Result<(), &'static str>;
+
+    fn encrypt_padded<P: Padding>(plaintext: &[u8], key: &T::KeyType) -> Result<Vec<u8>, &'static str> {
+        let padded = P::pad(plaintext, T::BLOCK_SIZE)?;
+        let mut ciphertext = vec![0; padded.len()];
+        Self::cipher(&padded, &mut ciphertext, key)?;
+        Ok(ciphertext)
+    }
+
+    fn decrypt_padded<P: Padding>(ciphertext: &[u8], key: &T::KeyType) -> Result<Vec<u8>, &'static str> {
+        let mut padded = vec![0; ciphertext.len()];
+        Self::decipher(&mut padded, ciphertext, key)?;
+        Ok(P::unpad(&padded, T::BLOCK_SIZE)?.to_vec())
+    }
 }
 
 /// Trait implemented by chaining modes that need an IV.
+///
+/// `IVType` depends on the mode: a block for most of them (e.g. CBC), two blocks for IGE.
+/// `encrypt_padded` and `decrypt_padded` treat data of any length, padded with `P`
+/// (see [crate::symmetric::block_ciphers_modes::padding]).
 pub trait BlockChainingWithIV<T>
 where
     T: BlockCipher,
 {
-    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str>;
-    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &T::BlockType) -> Result<(), &'static str>;
+    type IVType;
+
+    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str>;
+    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str>;
+
+    fn encrypt_padded<P: Padding>(plaintext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<Vec<u8>, &'static str> {
+        let padded = P::pad(plaintext, T::BLOCK_SIZE)?;
+        let mut ciphertext = vec![0; padded.len()];
+        Self::cipher(&padded, &mut ciphertext, key, iv)?;
+        Ok(ciphertext)
+    }
+
+    fn decrypt_padded<P: Padding>(ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<Vec<u8>, &'static str> {
+        let mut padded = vec![0; ciphertext.len()];
+        Self::decipher(&mut padded, ciphertext, key, iv)?;
+        Ok(P::unpad(&padded, T::BLOCK_SIZE)?.to_vec())
+    }
+}
+
+/// Trait implemented by chaining modes that can treat data in several calls, with the streaming
+/// [crate::symmetric::block_ciphers_modes::streaming::BlockEncryptor] and
+/// [crate::symmetric::block_ciphers_modes::streaming::BlockDecryptor].
+///
+/// `State` is the chaining state kept between calls (e.g. the IV at the beginning, then the last
+/// ciphertext block for CBC). `cipher_in_place` and `decipher_in_place` treat full blocks in place,
+/// and update the state.
+pub trait StreamingBlockChaining<T>
+where
+    T: BlockCipher,
+{
+    type State;
+
+    fn cipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str>;
+    fn decipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str>;
+}
+
+/// Structure used to initialise the streaming chaining modes that only need an IV.
+pub struct IVInitStruct<const NB: usize> {
+    pub iv: [u8; NB]
 }
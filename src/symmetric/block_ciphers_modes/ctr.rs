@@ -0,0 +1,467 @@
+//! Definition of CTR chaining mode (cf. NIST SP 800-38A)
+//!
+//! Encryption and decryption are the same operation:
+//!```text
+//!       CTR1      CTR2      CTRn
+//!        |         |         |
+//!       ---       ---       ---
+//!      | K |     | K | ... | K |
+//!       ---       ---       ---
+//!        |         |         |
+//!  P1 ---+   P2 ---+   Pn ---+
+//!        |         |         |
+//!        C1        C2        Cn
+//! ```
+//!
+//! The counter block is split in 2 parts: a nonce (first bytes of the block) and a counter (last bytes
+//! of the block). Only the counter part is incremented from a block to the next one. The size of the counter
+//! part and its byte order are configurable (see [CTRInitStruct]).
+//!
+//! The counter never wraps: as SP 800-38A (appendix B.2) requires the counter blocks to be unique, an error
+//! is returned when more blocks are requested than the counter part can count from its initial value.
+//!
+//! As CTR turns a block cipher into a stream cipher, [CTR] implements the
+//! [StreamCipher] trait. Inputs can have any length.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+/// Size of the counter part of the counter block.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CounterSize {
+    Bits32,
+    Bits64,
+    Bits128,
+}
+
+impl CounterSize {
+    /// Size of the counter, in bytes
+    pub fn bytes(self) -> usize {
+        match self {
+            CounterSize::Bits32 => 4,
+            CounterSize::Bits64 => 8,
+            CounterSize::Bits128 => 16,
+        }
+    }
+
+    fn mask(self) -> u128 {
+        match self {
+            CounterSize::Bits128 => u128::MAX,
+            _ => (1u128 << (8 * self.bytes())) - 1,
+        }
+    }
+}
+
+/// Byte order of the counter part of the counter block.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CounterEndianness {
+    BigEndian,
+    LittleEndian,
+}
+
+/// Structure used to initialise a [CTR] instance.
+///
+/// - `counter_block`: initial counter block (nonce followed by the initial counter value)
+/// - `counter_size`: size of the counter part, at the end of `counter_block`
+/// - `endianness`: byte order of the counter part
+pub struct CTRInitStruct<const NB: usize> {
+    pub counter_block: [u8; NB],
+    pub counter_size: CounterSize,
+    pub endianness: CounterEndianness,
+}
+
+impl<const NB: usize> CTRInitStruct<NB> {
+    /// Builds the initial counter block from a `nonce` and an initial `counter` value.
+    ///
+    /// `nonce` must fill exactly the part of the block that is not used by the counter.
+    pub fn from_nonce(nonce: &[u8], counter: u128, counter_size: CounterSize, endianness: CounterEndianness) -> Result<Self, &'static str> {
+        if counter_size.bytes() > NB {
+            return Err("Counter is larger than the block");
+        }
+        if nonce.len() + counter_size.bytes() != NB {
+            return Err("Nonce and counter sizes do not match the block size");
+        }
+        if counter & !counter_size.mask() != 0 {
+            return Err("Counter value does not fit in the counter size");
+        }
+
+        let mut counter_block = [0; NB];
+        counter_block[..nonce.len()].copy_from_slice(nonce);
+        write_counter(&mut counter_block[nonce.len()..], counter, endianness);
+
+        Ok(CTRInitStruct { counter_block, counter_size, endianness })
+    }
+}
+
+fn read_counter(bytes: &[u8], endianness: CounterEndianness) -> u128 {
+    match endianness {
+        CounterEndianness::BigEndian => bytes.iter().fold(0, |acc, b| acc << 8 | *b as u128),
+        CounterEndianness::LittleEndian => bytes.iter().rev().fold(0, |acc, b| acc << 8 | *b as u128),
+    }
+}
+
+fn write_counter(bytes: &mut [u8], value: u128, endianness: CounterEndianness) {
+    let len = bytes.len();
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let shift = match endianness {
+            CounterEndianness::BigEndian => 8 * (len - 1 - i),
+            CounterEndianness::LittleEndian => 8 * i,
+        };
+        *byte = (value >> shift) as u8;
+    }
+}
+
+/// CTR mode over the block cipher `T`.
+pub struct CTR<T: BlockCipher> {
+    key: T::KeyType,
+    initial_block: T::BlockType,  // counter block used for the first keystream block
+    counter_size: CounterSize,
+    endianness: CounterEndianness,
+    block_index: u128,  // index of the next keystream block to compute
+    wrap_counter: bool,  // whether the counter wraps modulo 2^counter_size instead of failing
+    keystream: T::BlockType,  // last keystream block computed
+    remaining_bytes: usize  // number of bytes that can be ciphered using `keystream`
+}
+
+impl<T, const NK: usize, const NB: usize> CTR<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    /// Lets the counter wrap modulo 2^counter_size instead of returning an error when it passes its maximum.
+    ///
+    /// Only for the modes whose specification defines the increment this way and bounds the length of the
+    /// messages itself (GCM, EAX, SIV).
+    pub(crate) fn wrapping_counter(mut self) -> Self {
+        self.wrap_counter = true;
+        self
+    }
+
+    // counter block used to compute the keystream block number `index`
+    fn counter_block(&self, index: u128) -> Result<[u8; NB], &'static str> {
+        let mut block = self.initial_block;
+        let counter_bytes = &mut block[NB - self.counter_size.bytes()..];
+        let initial_counter = read_counter(counter_bytes, self.endianness);
+        let counter = if self.wrap_counter {
+            initial_counter.wrapping_add(index) & self.counter_size.mask()
+        } else {
+            match initial_counter.checked_add(index) {
+                Some(counter) if counter <= self.counter_size.mask() => counter,
+                _ => return Err("Counter exhausted"),
+            }
+        };
+        write_counter(counter_bytes, counter, self.endianness);
+        Ok(block)
+    }
+
+    fn check_counter_size(&self) -> Result<(), &'static str> {
+        if self.counter_size.bytes() > NB {
+            return Err("Counter is larger than the block");
+        }
+        Ok(())
+    }
+
+    // checks that the counter can reach the last keystream block needed for `len` more bytes,
+    // so that nothing is modified when the keystream is too short
+    fn check_available(&self, len: usize) -> Result<(), &'static str> {
+        self.check_counter_size()?;
+        let nb_new_blocks = (len - self.remaining_bytes.min(len)).div_ceil(NB);
+        if nb_new_blocks > 0 {
+            self.counter_block(self.block_index + nb_new_blocks as u128 - 1)?;
+        }
+        Ok(())
+    }
+
+    /// Moves to the byte `byte_offset` of the keystream, so that the next call to `cipher` treats the data
+    /// as if it started at this offset.
+    pub fn seek(&mut self, byte_offset: u128) -> Result<(), &'static str> {
+        self.check_counter_size()?;
+
+        // nothing is modified if the counter cannot reach the new position
+        let block_index = byte_offset / NB as u128;
+        let counter_block = self.counter_block(block_index)?;
+
+        let offset_in_block = (byte_offset % NB as u128) as usize;
+        if offset_in_block != 0 {
+            T::cipher(&counter_block, &mut self.keystream, &self.key)?;
+            self.block_index = block_index + 1;
+            self.remaining_bytes = NB - offset_in_block;
+        } else {
+            self.block_index = block_index;
+            self.remaining_bytes = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current offset in the keystream, in bytes.
+    pub fn current_position(&self) -> u128 {
+        self.block_index * NB as u128 - self.remaining_bytes as u128
+    }
+}
+
+impl<T, const NK: usize, const NB: usize> StreamCipher for CTR<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    const KEY_SIZE: usize = NK;
+    type KeyType = [u8; NK];
+    type InitStruct = CTRInitStruct<NB>;
+    type State = [u8; NB];
+
+    fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+        CTR {
+            key: *key,
+            initial_block: init_struct.counter_block,
+            counter_size: init_struct.counter_size,
+            endianness: init_struct.endianness,
+            block_index: 0,
+            wrap_counter: false,
+            keystream: [0; NB],
+            remaining_bytes: 0,
+        }
+    }
+
+    fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        // check parameters
+        if plaintext.len() != ciphertext.len() {
+            return Err("Plaintext and ciphertext buffers must have the same length");
+        }
+        self.check_available(plaintext.len())?;
+
+        ciphertext.copy_from_slice(plaintext);
+        self.cipher_in_place(ciphertext)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        self.check_available(data.len())?;
+
+        let max = data.len();
+
+        // process remaining bytes of the last keystream block
+        let mut offset = self.remaining_bytes.min(max);
+        for (byte, k) in data[..offset].iter_mut().zip(&self.keystream[NB - self.remaining_bytes..]) {
+            *byte ^= k;
+        }
+        self.remaining_bytes -= offset;
+
+        // keystream blocks are independent, so all the entire blocks are computed at once
+        let nb_blocks = (max - offset) / NB;
+        if nb_blocks > 0 {
+            let mut blocks: Vec<[u8; NB]> = (0..nb_blocks as u128)
+                .map(|i| self.counter_block(self.block_index + i))
+                .collect::<Result<_, _>>()?;
+            T::cipher_blocks(&mut blocks, &self.key)?;
+            for (chunk, block) in data[offset..].chunks_exact_mut(NB).zip(blocks.iter()) {
+                for (byte, k) in chunk.iter_mut().zip(block) {
+                    *byte ^= k;
+                }
+            }
+            offset += nb_blocks * NB;
+            self.block_index += nb_blocks as u128;
+        }
+
+        // process the last partial block
+        if offset < max {
+            let counter_block = self.counter_block(self.block_index)?;
+            T::cipher(&counter_block, &mut self.keystream, &self.key)?;
+            self.block_index += 1;
+            for (byte, k) in data[offset..].iter_mut().zip(&self.keystream) {
+                *byte ^= k;
+            }
+            self.remaining_bytes = NB - (max - offset);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_ctr {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES192, AES256};
+    use crate::symmetric::block_ciphers::des::DES;
+    use super::*;
+
+    const PLAIN: [u8; 64] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF,
+        0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10,
+    ];
+
+    // SP 800-38A, F.5.1
+    const CIPHER_AES128: [u8; 64] = [
+        0x87, 0x4D, 0x61, 0x91, 0xB6, 0x20, 0xE3, 0x26, 0x1B, 0xEF, 0x68, 0x64, 0x99, 0x0D, 0xB6, 0xCE,
+        0x98, 0x06, 0xF6, 0x6B, 0x79, 0x70, 0xFD, 0xFF, 0x86, 0x17, 0x18, 0x7B, 0xB9, 0xFF, 0xFD, 0xFF,
+        0x5A, 0xE4, 0xDF, 0x3E, 0xDB, 0xD5, 0xD3, 0x5E, 0x5B, 0x4F, 0x09, 0x02, 0x0D, 0xB0, 0x3E, 0xAB,
+        0x1E, 0x03, 0x1D, 0xDA, 0x2F, 0xBE, 0x03, 0xD1, 0x79, 0x21, 0x70, 0xA0, 0xF3, 0x00, 0x9C, 0xEE,
+    ];
+
+    const KEY_AES128: [u8; 16] = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+
+    fn sp800_38a_init() -> CTRInitStruct<16> {
+        CTRInitStruct {
+            counter_block: [0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF],
+            counter_size: CounterSize::Bits128,
+            endianness: CounterEndianness::BigEndian,
+        }
+    }
+
+    #[test]
+    fn ctr_aes128_encrypt() {
+        let mut ciphertext = [0; 64];
+        let mut ctr = CTR::<AES128>::new(&sp800_38a_init(), &KEY_AES128);
+        ctr.cipher(&PLAIN, &mut ciphertext).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_AES128);
+    }
+
+    #[test]
+    fn ctr_aes128_decrypt() {
+        let mut plain = [0; 64];
+        let mut ctr = CTR::<AES128>::new(&sp800_38a_init(), &KEY_AES128);
+        ctr.cipher(&CIPHER_AES128, &mut plain).expect("Error during CTR_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn ctr_aes192_encrypt() {
+        // SP 800-38A, F.5.3
+        let key = [0x8E, 0x73, 0xB0, 0xF7, 0xDA, 0x0E, 0x64, 0x52, 0xC8, 0x10, 0xF3, 0x2B, 0x80, 0x90, 0x79, 0xE5, 0x62, 0xF8, 0xEA, 0xD2, 0x52, 0x2C, 0x6B, 0x7B];
+        let expected = [
+            0x1A, 0xBC, 0x93, 0x24, 0x17, 0x52, 0x1C, 0xA2, 0x4F, 0x2B, 0x04, 0x59, 0xFE, 0x7E, 0x6E, 0x0B,
+            0x09, 0x03, 0x39, 0xEC, 0x0A, 0xA6, 0xFA, 0xEF, 0xD5, 0xCC, 0xC2, 0xC6, 0xF4, 0xCE, 0x8E, 0x94,
+            0x1E, 0x36, 0xB2, 0x6B, 0xD1, 0xEB, 0xC6, 0x70, 0xD1, 0xBD, 0x1D, 0x66, 0x56, 0x20, 0xAB, 0xF7,
+            0x4F, 0x78, 0xA7, 0xF6, 0xD2, 0x98, 0x09, 0x58, 0x5A, 0x97, 0xDA, 0xEC, 0x58, 0xC6, 0xB0, 0x50,
+        ];
+        let mut ciphertext = [0; 64];
+        let mut ctr = CTR::<AES192>::new(&sp800_38a_init(), &key);
+        ctr.cipher(&PLAIN, &mut ciphertext).expect("Error during CTR_AES192 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn ctr_aes256_encrypt() {
+        // SP 800-38A, F.5.5
+        let key = [0x60, 0x3D, 0xEB, 0x10, 0x15, 0xCA, 0x71, 0xBE, 0x2B, 0x73, 0xAE, 0xF0, 0x85, 0x7D, 0x77, 0x81, 0x1F, 0x35, 0x2C, 0x07, 0x3B, 0x61, 0x08, 0xD7, 0x2D, 0x98, 0x10, 0xA3, 0x09, 0x14, 0xDF, 0xF4];
+        let expected = [
+            0x60, 0x1E, 0xC3, 0x13, 0x77, 0x57, 0x89, 0xA5, 0xB7, 0xA7, 0xF5, 0x04, 0xBB, 0xF3, 0xD2, 0x28,
+            0xF4, 0x43, 0xE3, 0xCA, 0x4D, 0x62, 0xB5, 0x9A, 0xCA, 0x84, 0xE9, 0x90, 0xCA, 0xCA, 0xF5, 0xC5,
+            0x2B, 0x09, 0x30, 0xDA, 0xA2, 0x3D, 0xE9, 0x4C, 0xE8, 0x70, 0x17, 0xBA, 0x2D, 0x84, 0x98, 0x8D,
+            0xDF, 0xC9, 0xC5, 0x8D, 0xB6, 0x7A, 0xAD, 0xA6, 0x13, 0xC2, 0xDD, 0x08, 0x45, 0x79, 0x41, 0xA6,
+        ];
+        let mut ciphertext = [0; 64];
+        let mut ctr = CTR::<AES256>::new(&sp800_38a_init(), &key);
+        ctr.cipher(&PLAIN, &mut ciphertext).expect("Error during CTR_AES256 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn ctr_aes128_encrypt_fragmented() {
+        let mut ciphertext = [0; 64];
+        let mut ctr = CTR::<AES128>::new(&sp800_38a_init(), &KEY_AES128);
+        ctr.cipher(&PLAIN[0..5], &mut ciphertext[0..5]).expect("Error during CTR_AES128 execution");
+        ctr.cipher(&PLAIN[5..7], &mut ciphertext[5..7]).expect("Error during CTR_AES128 execution");
+        ctr.cipher(&PLAIN[7..40], &mut ciphertext[7..40]).expect("Error during CTR_AES128 execution");
+        ctr.cipher(&PLAIN[40..48], &mut ciphertext[40..48]).expect("Error during CTR_AES128 execution");
+        ctr.cipher(&PLAIN[48..64], &mut ciphertext[48..64]).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_AES128);
+        assert_eq!(ctr.current_position(), 64);
+    }
+
+    #[test]
+    fn ctr_aes128_seek() {
+        let mut ctr = CTR::<AES128>::new(&sp800_38a_init(), &KEY_AES128);
+
+        let mut ciphertext = [0; 27];
+        ctr.seek(37).expect("Error during CTR_AES128 seek");
+        assert_eq!(ctr.current_position(), 37);
+        ctr.cipher(&PLAIN[37..64], &mut ciphertext).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_AES128[37..64]);
+
+        let mut ciphertext = [0; 32];
+        ctr.seek(16).expect("Error during CTR_AES128 seek");
+        ctr.cipher(&PLAIN[16..48], &mut ciphertext).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_AES128[16..48]);
+    }
+
+    #[test]
+    fn ctr_aes128_counter_32_bits_exhausted() {
+        // the counter must not wrap without modifying the nonce
+        let nonce = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B];
+        let init = CTRInitStruct::from_nonce(&nonce, 0xFFFFFFFE, CounterSize::Bits32, CounterEndianness::BigEndian)
+            .expect("Error during CTR initialisation");
+        let expected = [
+            0x63, 0x3E, 0x3F, 0xA1, 0x30, 0xCA, 0x67, 0x17, 0xF4, 0xAE, 0x60, 0x6A, 0x9C, 0xB4, 0x08, 0xEE,
+            0x13, 0x9A, 0x4A, 0xB8, 0x57, 0x72, 0xD5, 0xDE, 0x62, 0xDF, 0x81, 0x1D, 0x33, 0x3D, 0x72, 0xA5,
+        ];
+
+        // counter values 0xFFFFFFFE and 0xFFFFFFFF can be used, but not a third one
+        let mut ciphertext = [0; 48];
+        let mut ctr = CTR::<AES128>::new(&init, &KEY_AES128);
+        assert_eq!(ctr.cipher(&PLAIN[0..48], &mut ciphertext), Err("Counter exhausted"));
+        assert_eq!(ciphertext, [0; 48]);
+
+        ctr.cipher(&PLAIN[0..32], &mut ciphertext[0..32]).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext[0..32], expected);
+        assert_eq!(ctr.cipher(&PLAIN[32..33], &mut ciphertext[32..33]), Err("Counter exhausted"));
+        assert_eq!(ctr.seek(33), Err("Counter exhausted"));
+
+        // a failed seek keeps the current position
+        let mut ctr = CTR::<AES128>::new(&init, &KEY_AES128);
+        ctr.seek(5).expect("Error during CTR_AES128 seek");
+        ctr.cipher(&PLAIN[5..8], &mut ciphertext[5..8]).expect("Error during CTR_AES128 execution");
+        assert_eq!(ctr.seek(40), Err("Counter exhausted"));
+        assert_eq!(ctr.current_position(), 8);
+        ctr.cipher(&PLAIN[8..20], &mut ciphertext[8..20]).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext[5..20], expected[5..20]);
+    }
+
+    #[test]
+    fn ctr_aes128_counter_128_bits_exhausted() {
+        let init = CTRInitStruct { counter_block: [0xFF; 16], counter_size: CounterSize::Bits128, endianness: CounterEndianness::BigEndian };
+        let mut ciphertext = [0; 32];
+        let mut ctr = CTR::<AES128>::new(&init, &KEY_AES128);
+        ctr.cipher(&PLAIN[0..16], &mut ciphertext[0..16]).expect("Error during CTR_AES128 execution");
+        assert_eq!(ctr.cipher(&PLAIN[16..32], &mut ciphertext[16..32]), Err("Counter exhausted"));
+    }
+
+    #[test]
+    fn ctr_aes128_counter_64_bits_little_endian() {
+        let nonce = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        let init = CTRInitStruct::from_nonce(&nonce, 0xFF, CounterSize::Bits64, CounterEndianness::LittleEndian)
+            .expect("Error during CTR initialisation");
+        let expected = [
+            0x91, 0x2C, 0xDC, 0x00, 0xD1, 0x81, 0xAC, 0xCE, 0x28, 0x8F, 0x05, 0xB8, 0xC3, 0x25, 0x41, 0x99,
+            0xA2, 0xD3, 0x29, 0xD6, 0x9D, 0x08, 0xA7, 0xEB, 0x70, 0xFF, 0x00, 0xD3, 0xA4, 0x4D, 0xBD, 0x29,
+            0x1D, 0xFD, 0xB8, 0x19, 0xB2, 0xE4, 0x37, 0x1F,
+        ];
+        let mut ciphertext = [0; 40];
+        let mut ctr = CTR::<AES128>::new(&init, &KEY_AES128);
+        ctr.cipher(&PLAIN[0..40], &mut ciphertext).expect("Error during CTR_AES128 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn ctr_bad_init() {
+        let res = CTRInitStruct::<16>::from_nonce(&[0; 8], 0, CounterSize::Bits32, CounterEndianness::BigEndian);
+        assert!(res.is_err());
+        let res = CTRInitStruct::<16>::from_nonce(&[0; 12], 0x1_0000_0000, CounterSize::Bits32, CounterEndianness::BigEndian);
+        assert!(res.is_err());
+        let res = CTRInitStruct::<8>::from_nonce(&[], 0, CounterSize::Bits128, CounterEndianness::BigEndian);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn ctr_counter_larger_than_block() {
+        let init = CTRInitStruct { counter_block: [0; 8], counter_size: CounterSize::Bits128, endianness: CounterEndianness::BigEndian };
+        let mut ctr = CTR::<DES>::new(&init, &[0; 8]);
+        let mut ciphertext = [0; 8];
+        assert!(ctr.cipher(&[0; 8], &mut ciphertext).is_err());
+    }
+
+    #[test]
+    fn ctr_bad_length() {
+        let mut ciphertext = [0; 15];
+        let mut ctr = CTR::<AES128>::new(&sp800_38a_init(), &KEY_AES128);
+        assert!(ctr.cipher(&PLAIN[0..16], &mut ciphertext).is_err());
+    }
+}
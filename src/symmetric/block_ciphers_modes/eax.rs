@@ -0,0 +1,160 @@
+//! Definition of EAX authenticated encryption mode (cf. "The EAX Mode of Operation", Bellare, Rogaway
+//! and Wagner)
+//!
+//! Encryption:
+//!```text
+//!   OMAC_t(M) = CMAC([t]_128 || M)
+//!
+//!   N' = OMAC_0(N)
+//!   H' = OMAC_1(H)
+//!   C  = CTR(N', P)
+//!   T  = MSB_t(N' xor OMAC_2(C) xor H')
+//! ```
+//!
+//! Nonces can be of any length, and tags from 1 to 16 bytes long.
+//! Decryption only releases the plaintext once the tag has been checked (in constant time).
+
+use crate::mac::cmac::CMAC;
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::ctr::{CounterEndianness, CounterSize, CTRInitStruct, CTR};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+/// EAX mode over the 128-bit block cipher `T`.
+pub struct EAX<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> EAX<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn omac(key: &[u8; NK], t: u8, data: &[u8]) -> [u8; 16] {
+        let mut prefix = [0; 16];
+        prefix[15] = t;
+        CMAC::<T>::compute_parts(&[&prefix, data], key)
+    }
+
+    fn ctr(key: &[u8; NK], n: &[u8; 16], data: &mut [u8]) -> Result<(), AeadError> {
+        let init = CTRInitStruct { counter_block: *n, counter_size: CounterSize::Bits128, endianness: CounterEndianness::BigEndian };
+        // the counter is incremented modulo 2^128
+        CTR::<T>::new(&init, key).wrapping_counter().cipher_in_place(data)?;
+        Ok(())
+    }
+
+    fn compute_tag(key: &[u8; NK], n: &[u8; 16], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+        let h = Self::omac(key, 1, aad);
+        let c = Self::omac(key, 2, ciphertext);
+        core::array::from_fn(|i| n[i] ^ h[i] ^ c[i])
+    }
+
+    fn check_tag_length(tag: &[u8]) -> Result<(), AeadError> {
+        if !(1..=16).contains(&tag.len()) {
+            return Err(AeadError::InvalidTagLength);
+        }
+        Ok(())
+    }
+
+    // checks the tag of `ciphertext`, and returns N'
+    fn check_tag(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<[u8; 16], AeadError> {
+        Self::check_tag_length(tag)?;
+
+        let n = Self::omac(key, 0, nonce);
+        let full_tag = Self::compute_tag(key, &n, aad, ciphertext);
+        verify_tag(&full_tag[..tag.len()], tag)?;
+        Ok(n)
+    }
+
+    pub fn encrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::encrypt_in_place(key, nonce, aad, ciphertext, tag)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let n = Self::check_tag(key, nonce, aad, ciphertext, tag)?;
+        plaintext.copy_from_slice(ciphertext);
+        Self::ctr(key, &n, plaintext)
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        Self::check_tag_length(tag)?;
+
+        let n = Self::omac(key, 0, nonce);
+        Self::ctr(key, &n, buffer)?;
+        let full_tag = Self::compute_tag(key, &n, aad, buffer);
+        let tag_len = tag.len();
+        tag.copy_from_slice(&full_tag[..tag_len]);
+        Ok(())
+    }
+
+    /// `buffer` is only decrypted if the tag is valid.
+    pub fn decrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        let n = Self::check_tag(key, nonce, aad, buffer, tag)?;
+        Self::ctr(key, &n, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests_eax {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use super::*;
+
+    #[test]
+    fn eax_aes128_empty_message() {
+        // EAX paper, test vector 1
+        let key = [0x23, 0x39, 0x52, 0xDE, 0xE4, 0xD5, 0xED, 0x5F, 0x9B, 0x9C, 0x6D, 0x6F, 0xF8, 0x0F, 0xF4, 0x78];
+        let nonce = [0x62, 0xEC, 0x67, 0xF9, 0xC3, 0xA4, 0xA4, 0x07, 0xFC, 0xB2, 0xA8, 0xC4, 0x90, 0x31, 0xA8, 0xB3];
+        let header = [0x6B, 0xFB, 0x91, 0x4F, 0xD0, 0x7E, 0xAE, 0x6B];
+        let expected_tag = [0xE0, 0x37, 0x83, 0x0E, 0x83, 0x89, 0xF2, 0x7B, 0x02, 0x5A, 0x2D, 0x65, 0x27, 0xE7, 0x9D, 0x01];
+
+        let mut tag = [0; 16];
+        EAX::<AES128>::encrypt(&key, &nonce, &header, &[], &mut [], &mut tag).expect("Error during EAX_AES128 execution");
+        assert_eq!(tag, expected_tag);
+        EAX::<AES128>::decrypt(&key, &nonce, &header, &[], &tag, &mut []).expect("Error during EAX_AES128 execution");
+    }
+
+    #[test]
+    fn eax_aes128_encrypt_decrypt() {
+        // EAX paper, test vector 10
+        let key = [0x83, 0x95, 0xFC, 0xF1, 0xE9, 0x5B, 0xEB, 0xD6, 0x97, 0xBD, 0x01, 0x0B, 0xC7, 0x66, 0xAA, 0xC3];
+        let nonce = [0x22, 0xE7, 0xAD, 0xD9, 0x3C, 0xFC, 0x63, 0x93, 0xC5, 0x7E, 0xC0, 0xB3, 0xC1, 0x7D, 0x6B, 0x44];
+        let header = [0x12, 0x67, 0x35, 0xFC, 0xC3, 0x20, 0xD2, 0x5A];
+        let plain = [
+            0xCA, 0x40, 0xD7, 0x44, 0x6E, 0x54, 0x5F, 0xFA, 0xED, 0x3B, 0xD1, 0x2A, 0x74, 0x0A, 0x65, 0x9F,
+            0xFB, 0xBB, 0x3C, 0xEA, 0xB7,
+        ];
+        let expected_c = [
+            0xCB, 0x89, 0x20, 0xF8, 0x7A, 0x6C, 0x75, 0xCF, 0xF3, 0x96, 0x27, 0xB5, 0x6E, 0x3E, 0xD1, 0x97,
+            0xC5, 0x52, 0xD2, 0x95, 0xA7,
+        ];
+        let expected_tag = [0xCF, 0xC4, 0x6A, 0xFC, 0x25, 0x3B, 0x46, 0x52, 0xB1, 0xAF, 0x37, 0x95, 0xB1, 0x24, 0xAB, 0x6E];
+
+        let mut ciphertext = [0; 21];
+        let mut tag = [0; 16];
+        EAX::<AES128>::encrypt(&key, &nonce, &header, &plain, &mut ciphertext, &mut tag).expect("Error during EAX_AES128 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+
+        let mut decrypted = [0; 21];
+        EAX::<AES128>::decrypt(&key, &nonce, &header, &ciphertext, &tag[..8], &mut decrypted).expect("Error during EAX_AES128 execution");
+        assert_eq!(decrypted, plain);
+
+        let res = EAX::<AES128>::decrypt(&key, &nonce, &header[1..], &ciphertext, &tag, &mut decrypted);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+
+        let mut buffer = plain;
+        EAX::<AES128>::encrypt_in_place(&key, &nonce, &header, &mut buffer, &mut tag).expect("Error during EAX_AES128 execution");
+        assert_eq!(buffer, expected_c);
+        assert_eq!(tag, expected_tag);
+        let res = EAX::<AES128>::decrypt_in_place(&key, &nonce, &header[1..], &mut buffer, &tag);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, expected_c);
+        EAX::<AES128>::decrypt_in_place(&key, &nonce, &header, &mut buffer, &tag).expect("Error during EAX_AES128 execution");
+        assert_eq!(buffer, plain);
+    }
+}
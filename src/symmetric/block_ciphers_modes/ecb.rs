@@ -22,9 +22,9 @@
 //!    P1    P2        Pn
 //! ```
 
-use crate::symmetric::block_ciphers_modes::common::BlockChaining;
+use crate::symmetric::block_ciphers_modes::common::{BlockChaining, StreamingBlockChaining};
 use crate::symmetric::block_ciphers::common::BlockCipher;
-use crate::utils::{check_cipher_params, extract_array_from_slice};
+use crate::utils::{check_cipher_params, slice_to_arrays};
 
 pub struct ECB {}
 
@@ -36,17 +36,10 @@ where
         // check parameters
         check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
 
-        // do the chain
-        let mut i = 0;
-        while i < plaintext.len() {
-            let chunk_p = extract_array_from_slice(plaintext, i)?;
-            let mut chunk_c = extract_array_from_slice(ciphertext, i)?;
-            T::cipher(&chunk_p, &mut chunk_c, key)?;
-            for j in 0..T::BLOCK_SIZE {
-                ciphertext[i+j] = chunk_c[j];
-            }
-            i += T::BLOCK_SIZE;
-        }
+        // blocks are independent, so they are all given to the block cipher at once
+        let mut blocks = slice_to_arrays::<NB, u8>(plaintext);
+        T::cipher_blocks(&mut blocks, key)?;
+        ciphertext.copy_from_slice(blocks.as_flattened());
 
         Ok(())
     }
@@ -55,18 +48,38 @@ where
         // check parameters
         check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
 
-        // do the chain
-        let mut i = 0;
-        while i < plaintext.len() {
-            let mut chunk_p = extract_array_from_slice(plaintext, i)?;
-            let chunk_c = extract_array_from_slice(ciphertext, i)?;
-            T::decipher(&mut chunk_p, &chunk_c, key)?;
-            for j in 0..T::BLOCK_SIZE {
-                plaintext[i + j] = chunk_p[j];
-            }
-            i += T::BLOCK_SIZE;
+        // blocks are independent, so they are all given to the block cipher at once
+        let mut blocks = slice_to_arrays::<NB, u8>(ciphertext);
+        T::decipher_blocks(&mut blocks, key)?;
+        plaintext.copy_from_slice(blocks.as_flattened());
+
+        Ok(())
+    }
+}
+
+impl<T, const NB: usize> StreamingBlockChaining<T> for ECB
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type State = ();  // blocks are independent
+
+    fn cipher_in_place(data: &mut [u8], key: &T::KeyType, _state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
         }
+        let mut blocks = slice_to_arrays::<NB, u8>(data);
+        T::cipher_blocks(&mut blocks, key)?;
+        data.copy_from_slice(blocks.as_flattened());
+        Ok(())
+    }
 
+    fn decipher_in_place(data: &mut [u8], key: &T::KeyType, _state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
+        let mut blocks = slice_to_arrays::<NB, u8>(data);
+        T::decipher_blocks(&mut blocks, key)?;
+        data.copy_from_slice(blocks.as_flattened());
         Ok(())
     }
 }
@@ -74,6 +87,7 @@ where
 #[cfg(test)]
 mod tests_ecb {
     use crate::symmetric::block_ciphers::aes::AES128;
+    use crate::symmetric::block_ciphers_modes::padding::{ISO7816, PKCS7};
     use super::*;
 
     #[test]
@@ -169,4 +183,18 @@ mod tests_ecb {
         let res = <ECB as BlockChaining<AES128>>::decipher(&mut plain, &ciphertext, &key);
         assert!(res.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn ecb_padded() {
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let expected = [0xA2, 0x72, 0xC6, 0x30, 0xCA, 0xFF, 0x77, 0x86, 0xA2, 0x61, 0x96, 0x1A, 0x55, 0xCC, 0xA7, 0x20, 0x05, 0x1D, 0xE3, 0xEC, 0x9E, 0xC9, 0x5C, 0x66, 0x0E, 0xA1, 0x24, 0xDF, 0xA0, 0x85, 0xEC, 0xAB];
+        let ciphertext = <ECB as BlockChaining<AES128>>::encrypt_padded::<PKCS7>(b"Hello, padding!!!!", &key).expect("Error during ECB_AES128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let plain = <ECB as BlockChaining<AES128>>::decrypt_padded::<PKCS7>(&ciphertext, &key).expect("Error during ECB_AES128 execution");
+        assert_eq!(plain, b"Hello, padding!!!!");
+
+        let res = <ECB as BlockChaining<AES128>>::decrypt_padded::<ISO7816>(&ciphertext, &key);
+        assert!(res.is_err());
+    }
+}
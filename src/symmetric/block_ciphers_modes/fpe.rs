@@ -0,0 +1,377 @@
+//! Definition of FF1 and FF3-1 format-preserving encryption modes (cf. NIST SP 800-38G, revision 1)
+//!
+//! The input is a string of numerals in a given radix (from 2 to 2^16), and the output is a string of
+//! the same length in the same radix, e.g. a 16-digit card number is encrypted to another 16-digit number.
+//! Both are Feistel networks over the two halves `A` and `B` of the numeral string:
+//!```text
+//!   A                 B
+//!   |                 |
+//!   |        ---------|
+//!   |       |         |
+//!   |      ---        |
+//!   |     | F |-- tweak, round i
+//!   |      ---        |
+//!   |       |         |
+//!   +<------          |          (addition modulo radix^m)
+//!    \               /
+//!      ------ X -----
+//!    /               \
+//!   A                 B          (10 rounds for FF1, 8 rounds for FF3-1)
+//! ```
+//!
+//! With FF1, `F` is a CBC-MAC of the round data (the tweak can be of any length).
+//! With FF3-1, `F` is a single block encryption with the reversed key (the tweak is 7 bytes long).
+//!
+//! The domain must have at least one million elements (`radix^len >= 1 000 000`). Numeral strings
+//! can also be given with an alphabet (e.g. `"0123456789"`), see
+//! [FormatPreservingEncryption::encrypt_str].
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+const MIN_DOMAIN_SIZE: u32 = 1_000_000;
+const MAX_RADIX: u32 = 1 << 16;
+
+/// Common interface of the format-preserving encryption modes.
+pub trait FormatPreservingEncryption<const NK: usize> {
+    /// Encrypts `numerals` (each lower than `radix`).
+    fn encrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str>;
+
+    /// Decrypts `numerals` (each lower than `radix`).
+    fn decrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str>;
+
+    /// Encrypts the characters of `input`, the radix being the number of characters of `alphabet`.
+    fn encrypt_str(key: &[u8; NK], tweak: &[u8], alphabet: &str, input: &str) -> Result<String, &'static str> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let numerals = to_numerals(&alphabet, input)?;
+        Ok(from_numerals(&alphabet, &Self::encrypt(key, tweak, alphabet.len() as u32, &numerals)?))
+    }
+
+    /// Decrypts the characters of `input`, the radix being the number of characters of `alphabet`.
+    fn decrypt_str(key: &[u8; NK], tweak: &[u8], alphabet: &str, input: &str) -> Result<String, &'static str> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let numerals = to_numerals(&alphabet, input)?;
+        Ok(from_numerals(&alphabet, &Self::decrypt(key, tweak, alphabet.len() as u32, &numerals)?))
+    }
+}
+
+fn to_numerals(alphabet: &[char], input: &str) -> Result<Vec<u16>, &'static str> {
+    if alphabet.len() > MAX_RADIX as usize {
+        return Err("Alphabet must have at most 2^16 characters");
+    }
+    if alphabet.iter().enumerate().any(|(i, c)| alphabet[..i].contains(c)) {
+        return Err("Alphabet characters must be unique");
+    }
+    input.chars()
+        .map(|c| alphabet.iter().position(|&a| a == c).map(|i| i as u16).ok_or("Input character not in alphabet"))
+        .collect()
+}
+
+fn from_numerals(alphabet: &[char], numerals: &[u16]) -> String {
+    numerals.iter().map(|&x| alphabet[x as usize]).collect()
+}
+
+fn check_numerals(radix: u32, numerals: &[u16]) -> Result<(), &'static str> {
+    if !(2..=MAX_RADIX).contains(&radix) {
+        return Err("Radix must be between 2 and 2^16");
+    }
+    if numerals.len() < 2 || BigUint::from(radix).pow(numerals.len() as u32) < BigUint::from(MIN_DOMAIN_SIZE) {
+        return Err("Input is too short for this radix");
+    }
+    if numerals.iter().any(|&x| x as u32 >= radix) {
+        return Err("Numerals must be lower than the radix");
+    }
+    Ok(())
+}
+
+// NUM_radix(X), most significant numeral first
+fn num(radix: u32, numerals: &[u16]) -> BigUint {
+    numerals.iter().fold(BigUint::ZERO, |acc, &x| acc * radix + x)
+}
+
+// STR^m_radix(x)
+fn str_m(radix: u32, m: usize, mut x: BigUint) -> Vec<u16> {
+    let mut res = vec![0; m];
+    for numeral in res.iter_mut().rev() {
+        *numeral = (&x % radix).to_u16().unwrap();
+        x /= radix;
+    }
+    res
+}
+
+// big endian representation of `x` on `len` bytes
+fn to_bytes(x: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = x.to_bytes_be();
+    let mut res = vec![0; len - bytes.len()];
+    res.extend(bytes);
+    res
+}
+
+/// FF1 mode over the 128-bit block cipher `T`.
+pub struct FF1<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> FF1<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // CBC-MAC with a zero IV
+    fn prf(key: &[u8; NK], data: &[u8]) -> Result<[u8; 16], &'static str> {
+        let mut y = [0; 16];
+        for block in data.chunks_exact(16) {
+            let input: [u8; 16] = core::array::from_fn(|i| y[i] ^ block[i]);
+            T::cipher(&input, &mut y, key)?;
+        }
+        Ok(y)
+    }
+
+    // round function: the first `d` bytes of R || E(R xor [1]) || E(R xor [2]) ...
+    fn round(key: &[u8; NK], p: &[u8], tweak: &[u8], i: u8, x: &BigUint, b_len: usize, d: usize) -> Result<BigUint, &'static str> {
+        let mut data = p.to_vec();
+        data.extend_from_slice(tweak);
+        data.resize(data.len() + (16 - (tweak.len() + b_len + 1) % 16) % 16, 0);
+        data.push(i);
+        data.extend(to_bytes(x, b_len));
+
+        let r = Self::prf(key, &data)?;
+        let mut s = r.to_vec();
+        for j in 1..d.div_ceil(16) as u128 {
+            let input: [u8; 16] = core::array::from_fn(|k| r[k] ^ j.to_be_bytes()[k]);
+            let mut block = [0; 16];
+            T::cipher(&input, &mut block, key)?;
+            s.extend_from_slice(&block);
+        }
+        Ok(BigUint::from_bytes_be(&s[..d]))
+    }
+
+    fn feistel(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16], decrypt: bool) -> Result<Vec<u16>, &'static str> {
+        check_numerals(radix, numerals)?;
+        let n = numerals.len();
+        if n > u32::MAX as usize || tweak.len() > u32::MAX as usize {
+            return Err("Input or tweak is too long");
+        }
+
+        let u = n / 2;
+        let v = n - u;
+        let modulus = [BigUint::from(radix).pow(u as u32), BigUint::from(radix).pow(v as u32)];
+        let b_len = (modulus[1].clone() - 1u32).bits().div_ceil(8) as usize;
+        let d = 4 * b_len.div_ceil(4) + 4;
+
+        let mut p = vec![1, 2, 1];
+        p.extend_from_slice(&radix.to_be_bytes()[1..]);
+        p.extend_from_slice(&[10, u as u8]);
+        p.extend_from_slice(&(n as u32).to_be_bytes());
+        p.extend_from_slice(&(tweak.len() as u32).to_be_bytes());
+
+        let mut a = num(radix, &numerals[..u]);
+        let mut b = num(radix, &numerals[u..]);
+        for i in 0..10 {
+            let i = if decrypt { 9 - i } else { i };
+            let modulus = &modulus[i as usize % 2];
+            if decrypt {
+                let y = Self::round(key, &p, tweak, i, &a, b_len, d)? % modulus;
+                let c = (b + modulus - y) % modulus;
+                b = a;
+                a = c;
+            } else {
+                let y = Self::round(key, &p, tweak, i, &b, b_len, d)?;
+                let c = (a + y) % modulus;
+                a = b;
+                b = c;
+            }
+        }
+
+        let mut res = str_m(radix, u, a);
+        res.extend(str_m(radix, v, b));
+        Ok(res)
+    }
+}
+
+impl<T, const NK: usize> FormatPreservingEncryption<NK> for FF1<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn encrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str> {
+        Self::feistel(key, tweak, radix, numerals, false)
+    }
+
+    fn decrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str> {
+        Self::feistel(key, tweak, radix, numerals, true)
+    }
+}
+
+/// FF3-1 mode over the 128-bit block cipher `T`, with 7-byte tweaks.
+pub struct FF31<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> FF31<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // NUM_radix(REV(X))
+    fn rev_num(radix: u32, numerals: &[u16]) -> BigUint {
+        numerals.iter().rev().fold(BigUint::ZERO, |acc, &x| acc * radix + x)
+    }
+
+    // REV(STR^m_radix(x))
+    fn rev_str_m(radix: u32, m: usize, x: BigUint) -> Vec<u16> {
+        let mut res = str_m(radix, m, x);
+        res.reverse();
+        res
+    }
+
+    // REVB(E(REVB(K), REVB(W xor [i] || NUM_radix(REV(X)))))
+    fn round(key: &[u8; NK], w: &[u8; 4], i: u8, x: &BigUint) -> Result<BigUint, &'static str> {
+        let mut p = [0; 16];
+        p[..4].copy_from_slice(w);
+        p[3] ^= i;
+        p[4..].copy_from_slice(&to_bytes(x, 12));
+        p.reverse();
+
+        let mut s = [0; 16];
+        T::cipher(&p, &mut s, key)?;
+        s.reverse();
+        Ok(BigUint::from_bytes_be(&s))
+    }
+
+    fn feistel(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16], decrypt: bool) -> Result<Vec<u16>, &'static str> {
+        check_numerals(radix, numerals)?;
+        let tweak: [u8; 7] = tweak.try_into().map_err(|_| "Tweak length must be 7 bytes")?;
+
+        // maxlen = 2 * floor(log_radix(2^96))
+        let mut max_half = 0;
+        while (radix as u128).pow(max_half + 1) <= 1 << 96 {
+            max_half += 1;
+        }
+        let n = numerals.len();
+        if n > 2 * max_half as usize {
+            return Err("Input is too long for this radix");
+        }
+
+        let mut reversed_key = *key;
+        reversed_key.reverse();
+        let tweaks = [
+            [tweak[4], tweak[5], tweak[6], tweak[3] << 4],
+            [tweak[0], tweak[1], tweak[2], tweak[3] & 0xF0],
+        ];
+
+        let u = n.div_ceil(2);
+        let v = n - u;
+        let modulus = [BigUint::from(radix).pow(u as u32), BigUint::from(radix).pow(v as u32)];
+
+        let mut a = Self::rev_num(radix, &numerals[..u]);
+        let mut b = Self::rev_num(radix, &numerals[u..]);
+        for i in 0..8 {
+            let i = if decrypt { 7 - i } else { i };
+            let (modulus, w) = (&modulus[i as usize % 2], &tweaks[i as usize % 2]);
+            if decrypt {
+                let y = Self::round(&reversed_key, w, i, &a)? % modulus;
+                let c = (b + modulus - y) % modulus;
+                b = a;
+                a = c;
+            } else {
+                let y = Self::round(&reversed_key, w, i, &b)?;
+                let c = (a + y) % modulus;
+                a = b;
+                b = c;
+            }
+        }
+
+        let mut res = Self::rev_str_m(radix, u, a);
+        res.extend(Self::rev_str_m(radix, v, b));
+        Ok(res)
+    }
+}
+
+impl<T, const NK: usize> FormatPreservingEncryption<NK> for FF31<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn encrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str> {
+        Self::feistel(key, tweak, radix, numerals, false)
+    }
+
+    fn decrypt(key: &[u8; NK], tweak: &[u8], radix: u32, numerals: &[u16]) -> Result<Vec<u16>, &'static str> {
+        Self::feistel(key, tweak, radix, numerals, true)
+    }
+}
+
+#[cfg(test)]
+mod tests_fpe {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES256};
+    use super::*;
+
+    const ALPHABET_36: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+    #[test]
+    fn ff1_aes128() {
+        // SP 800-38G, FF1 samples 1 to 3
+        let key = [0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+        let plain = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        let res = FF1::<AES128>::encrypt(&key, &[], 10, &plain).expect("Error during FF1_AES128 execution");
+        assert_eq!(res, [2, 4, 3, 3, 4, 7, 7, 4, 8, 4]);
+        let res = FF1::<AES128>::decrypt(&key, &[], 10, &res).expect("Error during FF1_AES128 execution");
+        assert_eq!(res, plain);
+
+        let tweak = [0x39, 0x38, 0x37, 0x36, 0x35, 0x34, 0x33, 0x32, 0x31, 0x30];
+        let res = FF1::<AES128>::encrypt(&key, &tweak, 10, &plain).expect("Error during FF1_AES128 execution");
+        assert_eq!(res, [6, 1, 2, 4, 2, 0, 0, 7, 7, 3]);
+
+        let tweak = [0x37, 0x37, 0x37, 0x37, 0x70, 0x71, 0x72, 0x73, 0x37, 0x37, 0x37];
+        let res = FF1::<AES128>::encrypt_str(&key, &tweak, ALPHABET_36, "0123456789abcdefghi").expect("Error during FF1_AES128 execution");
+        assert_eq!(res, "a9tv40mll9kdu509eum");
+        let res = FF1::<AES128>::decrypt_str(&key, &tweak, ALPHABET_36, &res).expect("Error during FF1_AES128 execution");
+        assert_eq!(res, "0123456789abcdefghi");
+    }
+
+    #[test]
+    fn ff1_aes256() {
+        // SP 800-38G, FF1 samples 7 and 9
+        let key = [
+            0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C,
+            0xEF, 0x43, 0x59, 0xD8, 0xD5, 0x80, 0xAA, 0x4F, 0x7F, 0x03, 0x6D, 0x6F, 0x04, 0xFC, 0x6A, 0x94,
+        ];
+        let res = FF1::<AES256>::encrypt_str(&key, &[], "0123456789", "0123456789").expect("Error during FF1_AES256 execution");
+        assert_eq!(res, "6657667009");
+
+        let tweak = [0x37, 0x37, 0x37, 0x37, 0x70, 0x71, 0x72, 0x73, 0x37, 0x37, 0x37];
+        let res = FF1::<AES256>::encrypt_str(&key, &tweak, ALPHABET_36, "0123456789abcdefghi").expect("Error during FF1_AES256 execution");
+        assert_eq!(res, "xs8a0azh2avyalyzuwd");
+    }
+
+    #[test]
+    fn ff31_aes128() {
+        // SP 800-38G, FF3 sample 4 (a zero tweak is the same for FF3 and FF3-1)
+        let key = [0xEF, 0x43, 0x59, 0xD8, 0xD5, 0x80, 0xAA, 0x4F, 0x7F, 0x03, 0x6D, 0x6F, 0x04, 0xFC, 0x6A, 0x94];
+        let res = FF31::<AES128>::encrypt_str(&key, &[0; 7], "0123456789", "89012123456789000000789000000").expect("Error during FF31_AES128 execution");
+        assert_eq!(res, "34695224821734535122613701434");
+        let res = FF31::<AES128>::decrypt_str(&key, &[0; 7], "0123456789", &res).expect("Error during FF31_AES128 execution");
+        assert_eq!(res, "89012123456789000000789000000");
+
+        // FF3-1 with a 56-bit tweak, split into T_L and T_R
+        let tweak = [0xD8, 0xE7, 0x92, 0x0A, 0xFA, 0x33, 0x0A];
+        let res = FF31::<AES128>::encrypt_str(&key, &tweak, "0123456789", "890121234567890000").expect("Error during FF31_AES128 execution");
+        assert_eq!(res, "477064185124354662");
+        let res = FF31::<AES128>::decrypt_str(&key, &tweak, "0123456789", &res).expect("Error during FF31_AES128 execution");
+        assert_eq!(res, "890121234567890000");
+
+        let plain = [0, 1, 2, 65535, 40000, 7];
+        let res = FF31::<AES128>::encrypt(&key, &tweak, 1 << 16, &plain).expect("Error during FF31_AES128 execution");
+        assert_eq!(res, [45448, 60762, 41061, 36634, 47825, 47533]);
+        let res = FF31::<AES128>::decrypt(&key, &tweak, 1 << 16, &res).expect("Error during FF31_AES128 execution");
+        assert_eq!(res, plain);
+    }
+
+    #[test]
+    fn fpe_bad_parameters() {
+        let key = [0; 16];
+        assert!(FF1::<AES128>::encrypt(&key, &[], 10, &[1, 2, 3, 4, 5]).is_err());
+        assert!(FF1::<AES128>::encrypt(&key, &[], 1, &[0; 20]).is_err());
+        assert!(FF1::<AES128>::encrypt(&key, &[], 10, &[10; 6]).is_err());
+        assert!(FF1::<AES128>::encrypt_str(&key, &[], "0123456789", "12345a").is_err());
+        assert!(FF1::<AES128>::encrypt_str(&key, &[], "0023456789", "123456").is_err());
+        assert!(FF31::<AES128>::encrypt(&key, &[0; 8], 10, &[0; 10]).is_err());
+        assert!(FF31::<AES128>::encrypt(&key, &[0; 7], 10, &[0; 57]).is_err());
+        assert!(FF31::<AES128>::encrypt(&key, &[0; 7], 10, &[0; 56]).is_ok());
+    }
+}
@@ -0,0 +1,562 @@
+//! Definition of GCM authenticated encryption mode (cf. NIST SP 800-38D)
+//!
+//! Encryption:
+//!```text
+//!   J0 = IV || 0^31 || 1          (96-bit IV)
+//!   J0 = GHASH(IV || 0^s || len(IV))  (other IV lengths)
+//!
+//!   C = GCTR(inc32(J0), P)
+//!   S = GHASH(A || 0^v || C || 0^u || len(A) || len(C))
+//!   T = MSB_t(E(J0) xor S)
+//! ```
+//!
+//! GCM only works with 128-bit block ciphers. Tags can be from 4 to 16 bytes long.
+//!
+//! 2 APIs are available:
+//! - one-shot: [GCM::encrypt] and [GCM::decrypt] (or [GCM::encrypt_in_place] and [GCM::decrypt_in_place])
+//! - streaming: [GCMEncryptor] and [GCMDecryptor]
+//!
+//! Decryption only releases the plaintext once the tag has been checked (in constant time).
+//! Hence, [GCMDecryptor] keeps the ciphertext until [GCMDecryptor::finalize] is called.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::ctr::{CounterEndianness, CounterSize, CTRInitStruct, CTR};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+
+// maximum length of the plaintext: 2^39 - 256 bits
+const MAX_TEXT_LENGTH: u64 = (1 << 36) - 32;
+
+// multiplication in GF(2^128), with the bit order of GCM (cf. SP 800-38D, 6.3)
+// No branch nor memory access depends on the operands.
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xE1 << 120;
+    let mut z = 0;
+    let mut v = y;
+
+    for i in (0..128).rev() {
+        let x_i = (x >> i) & 1;
+        z ^= v & 0u128.wrapping_sub(x_i);
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & 0u128.wrapping_sub(lsb));
+    }
+
+    z
+}
+
+/// GHASH function (cf. SP 800-38D, 6.4).
+///
+/// Data can be given in several calls to `update`. `pad` completes the current block with zeros.
+pub(crate) struct GHash {
+    h: u128,
+    y: u128,
+    buffer: [u8; 16],
+    buffer_len: usize
+}
+
+impl GHash {
+    pub(crate) fn new(h: &[u8; 16]) -> Self {
+        GHash { h: u128::from_be_bytes(*h), y: 0, buffer: [0; 16], buffer_len: 0 }
+    }
+
+    fn process_block(&mut self, block: &[u8; 16]) {
+        self.y = gf128_mul(self.y ^ u128::from_be_bytes(*block), self.h);
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        for byte in data {
+            self.buffer[self.buffer_len] = *byte;
+            self.buffer_len += 1;
+            if self.buffer_len == 16 {
+                let block = self.buffer;
+                self.process_block(&block);
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    pub(crate) fn pad(&mut self) {
+        if self.buffer_len > 0 {
+            let mut block = [0; 16];
+            block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+            self.process_block(&block);
+            self.buffer_len = 0;
+        }
+    }
+
+    pub(crate) fn finalise(mut self) -> [u8; 16] {
+        self.pad();
+        self.y.to_be_bytes()
+    }
+}
+
+// state shared by the encryptor and the decryptor
+struct GCMState<T: BlockCipher> {
+    key: T::KeyType,
+    j0: [u8; 16],
+    ctr: CTR<T>,
+    ghash: GHash,
+    tag_len: usize,
+    aad_len: u64,
+    text_len: u64,
+    aad_done: bool  // true once the first byte of data was given
+}
+
+impl<T, const NK: usize> GCMState<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn new(key: &[u8; NK], iv: &[u8], tag_len: usize) -> Result<Self, AeadError> {
+        if !(4..=16).contains(&tag_len) {
+            return Err(AeadError::InvalidTagLength);
+        }
+        if iv.is_empty() {
+            return Err(AeadError::InvalidNonceLength);
+        }
+
+        // hash subkey
+        let mut h = [0; 16];
+        T::cipher(&[0; 16], &mut h, key)?;
+
+        // pre-counter block
+        let j0 = if iv.len() == 12 {
+            let mut j0 = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+            j0[..12].copy_from_slice(iv);
+            j0
+        } else {
+            let mut ghash = GHash::new(&h);
+            ghash.update(iv);
+            ghash.pad();
+            let mut len_block = [0; 16];
+            len_block[8..].copy_from_slice(&(iv.len() as u64 * 8).to_be_bytes());
+            ghash.update(&len_block);
+            ghash.finalise()
+        };
+
+        // keystream starts at inc32(J0), inc32 being defined modulo 2^32
+        let init = CTRInitStruct { counter_block: j0, counter_size: CounterSize::Bits32, endianness: CounterEndianness::BigEndian };
+        let mut ctr = CTR::new(&init, key).wrapping_counter();
+        ctr.seek(16)?;
+
+        Ok(GCMState { key: *key, j0, ctr, ghash: GHash::new(&h), tag_len, aad_len: 0, text_len: 0, aad_done: false })
+    }
+
+    fn update_aad(&mut self, aad: &[u8]) -> Result<(), AeadError> {
+        if self.aad_done {
+            return Err(AeadError::Cipher("Additional data must be given before the data"));
+        }
+        self.ghash.update(aad);
+        self.aad_len += aad.len() as u64;
+        Ok(())
+    }
+
+    fn start_text(&mut self, len: usize) -> Result<(), AeadError> {
+        if !self.aad_done {
+            self.ghash.pad();
+            self.aad_done = true;
+        }
+        self.text_len += len as u64;
+        if self.text_len > MAX_TEXT_LENGTH {
+            return Err(AeadError::BadLength);
+        }
+        Ok(())
+    }
+
+    fn compute_tag(&mut self) -> Result<Vec<u8>, AeadError> {
+        self.start_text(0)?;
+        self.ghash.pad();
+
+        let mut len_block = [0; 16];
+        len_block[..8].copy_from_slice(&(self.aad_len * 8).to_be_bytes());
+        len_block[8..].copy_from_slice(&(self.text_len * 8).to_be_bytes());
+        self.ghash.update(&len_block);
+
+        let s = std::mem::replace(&mut self.ghash, GHash::new(&[0; 16])).finalise();
+        let mut ek_j0 = [0; 16];
+        T::cipher(&self.j0, &mut ek_j0, &self.key)?;
+
+        Ok(ek_j0.iter().zip(s.iter()).take(self.tag_len).map(|(a, b)| a ^ b).collect())
+    }
+}
+
+/// Streaming GCM encryptor over the 128-bit block cipher `T`.
+///
+/// Additional data must be given (`update_aad`) before the plaintext (`update`).
+pub struct GCMEncryptor<T: BlockCipher> {
+    state: GCMState<T>
+}
+
+impl<T, const NK: usize> GCMEncryptor<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    pub fn new(key: &[u8; NK], iv: &[u8], tag_len: usize) -> Result<Self, AeadError> {
+        Ok(GCMEncryptor { state: GCMState::new(key, iv, tag_len)? })
+    }
+
+    pub fn update_aad(&mut self, aad: &[u8]) -> Result<(), AeadError> {
+        self.state.update_aad(aad)
+    }
+
+    pub fn update(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), AeadError> {
+        self.state.start_text(plaintext.len())?;
+        self.state.ctr.cipher(plaintext, ciphertext)?;
+        self.state.ghash.update(ciphertext);
+        Ok(())
+    }
+
+    /// Returns the tag.
+    pub fn finalize(mut self) -> Result<Vec<u8>, AeadError> {
+        self.state.compute_tag()
+    }
+}
+
+/// Streaming GCM decryptor over the 128-bit block cipher `T`.
+///
+/// Additional data must be given (`update_aad`) before the ciphertext (`update`).
+/// The plaintext is only returned by `finalize`, once the tag has been checked.
+pub struct GCMDecryptor<T: BlockCipher> {
+    state: GCMState<T>,
+    ciphertext: Vec<u8>
+}
+
+impl<T, const NK: usize> GCMDecryptor<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    pub fn new(key: &[u8; NK], iv: &[u8], tag_len: usize) -> Result<Self, AeadError> {
+        Ok(GCMDecryptor { state: GCMState::new(key, iv, tag_len)?, ciphertext: Vec::new() })
+    }
+
+    pub fn update_aad(&mut self, aad: &[u8]) -> Result<(), AeadError> {
+        self.state.update_aad(aad)
+    }
+
+    pub fn update(&mut self, ciphertext: &[u8]) -> Result<(), AeadError> {
+        self.state.start_text(ciphertext.len())?;
+        self.state.ghash.update(ciphertext);
+        self.ciphertext.extend_from_slice(ciphertext);
+        Ok(())
+    }
+
+    /// Checks the tag and returns the plaintext if it is valid.
+    pub fn finalize(mut self, tag: &[u8]) -> Result<Vec<u8>, AeadError> {
+        if tag.len() != self.state.tag_len {
+            return Err(AeadError::InvalidTagLength);
+        }
+        verify_tag(&self.state.compute_tag()?, tag)?;
+
+        let mut plaintext = vec![0; self.ciphertext.len()];
+        self.state.ctr.cipher(&self.ciphertext, &mut plaintext)?;
+        Ok(plaintext)
+    }
+}
+
+/// One-shot GCM over the 128-bit block cipher `T`.
+///
+/// The tag length is given by the length of the `tag` buffer. The `_in_place` functions overwrite `buffer`
+/// with the result.
+pub struct GCM<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> GCM<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // checks the tag of `ciphertext`, and returns the state ready to decrypt it
+    fn check_tag(key: &[u8; NK], iv: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<GCMState<T>, AeadError> {
+        let mut state = GCMState::<T>::new(key, iv, tag.len())?;
+        state.update_aad(aad)?;
+        state.start_text(ciphertext.len())?;
+        state.ghash.update(ciphertext);
+        verify_tag(&state.compute_tag()?, tag)?;
+        Ok(state)
+    }
+
+    pub fn encrypt(key: &[u8; NK], iv: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::encrypt_in_place(key, iv, aad, ciphertext, tag)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], iv: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut state = Self::check_tag(key, iv, aad, ciphertext, tag)?;
+        state.ctr.cipher(ciphertext, plaintext)?;
+        Ok(())
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], iv: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        let mut state = GCMState::<T>::new(key, iv, tag.len())?;
+        state.update_aad(aad)?;
+        state.start_text(buffer.len())?;
+        state.ctr.cipher_in_place(buffer)?;
+        state.ghash.update(buffer);
+        tag.copy_from_slice(&state.compute_tag()?);
+        Ok(())
+    }
+
+    /// `buffer` is only decrypted if the tag is valid.
+    pub fn decrypt_in_place(key: &[u8; NK], iv: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        let mut state = Self::check_tag(key, iv, aad, buffer, tag)?;
+        state.ctr.cipher_in_place(buffer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_gcm {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES192, AES256};
+    use super::*;
+
+    // test vectors from "The Galois/Counter Mode of Operation (GCM)", McGrew & Viega
+    const KEY: [u8; 16] = [0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08];
+    const IV: [u8; 12] = [0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88];
+    const AAD: [u8; 20] = [
+        0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF,
+        0xAB, 0xAD, 0xDA, 0xD2,
+    ];
+    const PLAIN: [u8; 60] = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26, 0x9A,
+        0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31, 0x8A, 0x72,
+        0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49, 0xA6, 0xB5, 0x25,
+        0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+    ];
+
+    // test case 4
+    const CIPHER_4: [u8; 60] = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4, 0x9C,
+        0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC, 0xA1, 0x2E,
+        0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC, 0x84, 0xAA, 0x05,
+        0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91,
+    ];
+    const TAG_4: [u8; 16] = [0x5B, 0xC9, 0x4F, 0xBC, 0x32, 0x21, 0xA5, 0xDB, 0x94, 0xFA, 0xE9, 0x5A, 0xE7, 0x12, 0x1A, 0x47];
+
+    const IV_6: [u8; 60] = [
+        0x93, 0x13, 0x22, 0x5D, 0xF8, 0x84, 0x06, 0xE5, 0x55, 0x90, 0x9C, 0x5A, 0xFF, 0x52, 0x69, 0xAA,
+        0x6A, 0x7A, 0x95, 0x38, 0x53, 0x4F, 0x7D, 0xA1, 0xE4, 0xC3, 0x03, 0xD2, 0xA3, 0x18, 0xA7, 0x28,
+        0xC3, 0xC0, 0xC9, 0x51, 0x56, 0x80, 0x95, 0x39, 0xFC, 0xF0, 0xE2, 0x42, 0x9A, 0x6B, 0x52, 0x54,
+        0x16, 0xAE, 0xDB, 0xF5, 0xA0, 0xDE, 0x6A, 0x57, 0xA6, 0x37, 0xB3, 0x9B,
+    ];
+
+    #[test]
+    fn gcm_aes128_empty() {
+        // test case 1
+        let mut tag = [0; 16];
+        let expected_tag = [0x58, 0xE2, 0xFC, 0xCE, 0xFA, 0x7E, 0x30, 0x61, 0x36, 0x7F, 0x1D, 0x57, 0xA4, 0xE7, 0x45, 0x5A];
+        GCM::<AES128>::encrypt(&[0; 16], &[0; 12], &[], &[], &mut [], &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes128_1_block() {
+        // test case 2
+        let mut ciphertext = [0; 16];
+        let mut tag = [0; 16];
+        let expected = [0x03, 0x88, 0xDA, 0xCE, 0x60, 0xB6, 0xA3, 0x92, 0xF3, 0x28, 0xC2, 0xB9, 0x71, 0xB2, 0xFE, 0x78];
+        let expected_tag = [0xAB, 0x6E, 0x47, 0xD4, 0x2C, 0xEC, 0x13, 0xBD, 0xF5, 0x3A, 0x67, 0xB2, 0x12, 0x57, 0xBD, 0xDF];
+        GCM::<AES128>::encrypt(&[0; 16], &[0; 12], &[], &[0; 16], &mut ciphertext, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes128_encrypt() {
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 16];
+        GCM::<AES128>::encrypt(&KEY, &IV, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_4);
+        assert_eq!(tag, TAG_4);
+    }
+
+    #[test]
+    fn gcm_aes128_decrypt() {
+        let mut plain = [0; 60];
+        GCM::<AES128>::decrypt(&KEY, &IV, &AAD, &CIPHER_4, &TAG_4, &mut plain).expect("Error during GCM_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn gcm_aes128_iv_64_bits() {
+        // test case 5
+        let iv = [0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD];
+        let expected = [
+            0x61, 0x35, 0x3B, 0x4C, 0x28, 0x06, 0x93, 0x4A, 0x77, 0x7F, 0xF5, 0x1F, 0xA2, 0x2A, 0x47, 0x55,
+            0x69, 0x9B, 0x2A, 0x71, 0x4F, 0xCD, 0xC6, 0xF8, 0x37, 0x66, 0xE5, 0xF9, 0x7B, 0x6C, 0x74, 0x23,
+            0x73, 0x80, 0x69, 0x00, 0xE4, 0x9F, 0x24, 0xB2, 0x2B, 0x09, 0x75, 0x44, 0xD4, 0x89, 0x6B, 0x42,
+            0x49, 0x89, 0xB5, 0xE1, 0xEB, 0xAC, 0x0F, 0x07, 0xC2, 0x3F, 0x45, 0x98,
+        ];
+        let expected_tag = [0x36, 0x12, 0xD2, 0xE7, 0x9E, 0x3B, 0x07, 0x85, 0x56, 0x1B, 0xE1, 0x4A, 0xAC, 0xA2, 0xFC, 0xCB];
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 16];
+        GCM::<AES128>::encrypt(&KEY, &iv, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes128_iv_480_bits() {
+        // test case 6
+        let expected = [
+            0x8C, 0xE2, 0x49, 0x98, 0x62, 0x56, 0x15, 0xB6, 0x03, 0xA0, 0x33, 0xAC, 0xA1, 0x3F, 0xB8, 0x94,
+            0xBE, 0x91, 0x12, 0xA5, 0xC3, 0xA2, 0x11, 0xA8, 0xBA, 0x26, 0x2A, 0x3C, 0xCA, 0x7E, 0x2C, 0xA7,
+            0x01, 0xE4, 0xA9, 0xA4, 0xFB, 0xA4, 0x3C, 0x90, 0xCC, 0xDC, 0xB2, 0x81, 0xD4, 0x8C, 0x7C, 0x6F,
+            0xD6, 0x28, 0x75, 0xD2, 0xAC, 0xA4, 0x17, 0x03, 0x4C, 0x34, 0xAE, 0xE5,
+        ];
+        let expected_tag = [0x61, 0x9C, 0xC5, 0xAE, 0xFF, 0xFE, 0x0B, 0xFA, 0x46, 0x2A, 0xF4, 0x3C, 0x16, 0x99, 0xD0, 0x50];
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 16];
+        GCM::<AES128>::encrypt(&KEY, &IV_6, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes192_encrypt() {
+        // test case 10
+        let key = [
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08,
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C,
+        ];
+        let expected = [
+            0x39, 0x80, 0xCA, 0x0B, 0x3C, 0x00, 0xE8, 0x41, 0xEB, 0x06, 0xFA, 0xC4, 0x87, 0x2A, 0x27, 0x57,
+            0x85, 0x9E, 0x1C, 0xEA, 0xA6, 0xEF, 0xD9, 0x84, 0x62, 0x85, 0x93, 0xB4, 0x0C, 0xA1, 0xE1, 0x9C,
+            0x7D, 0x77, 0x3D, 0x00, 0xC1, 0x44, 0xC5, 0x25, 0xAC, 0x61, 0x9D, 0x18, 0xC8, 0x4A, 0x3F, 0x47,
+            0x18, 0xE2, 0x44, 0x8B, 0x2F, 0xE3, 0x24, 0xD9, 0xCC, 0xDA, 0x27, 0x10,
+        ];
+        let expected_tag = [0x25, 0x19, 0x49, 0x8E, 0x80, 0xF1, 0x47, 0x8F, 0x37, 0xBA, 0x55, 0xBD, 0x6D, 0x27, 0x61, 0x8C];
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 16];
+        GCM::<AES192>::encrypt(&key, &IV, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES192 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes256_encrypt() {
+        // test case 16
+        let key = [
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08,
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08,
+        ];
+        let expected = [
+            0x52, 0x2D, 0xC1, 0xF0, 0x99, 0x56, 0x7D, 0x07, 0xF4, 0x7F, 0x37, 0xA3, 0x2A, 0x84, 0x42, 0x7D,
+            0x64, 0x3A, 0x8C, 0xDC, 0xBF, 0xE5, 0xC0, 0xC9, 0x75, 0x98, 0xA2, 0xBD, 0x25, 0x55, 0xD1, 0xAA,
+            0x8C, 0xB0, 0x8E, 0x48, 0x59, 0x0D, 0xBB, 0x3D, 0xA7, 0xB0, 0x8B, 0x10, 0x56, 0x82, 0x88, 0x38,
+            0xC5, 0xF6, 0x1E, 0x63, 0x93, 0xBA, 0x7A, 0x0A, 0xBC, 0xC9, 0xF6, 0x62,
+        ];
+        let expected_tag = [0x76, 0xFC, 0x6E, 0xCE, 0x0F, 0x4E, 0x17, 0x68, 0xCD, 0xDF, 0x88, 0x53, 0xBB, 0x2D, 0x55, 0x1B];
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 16];
+        GCM::<AES256>::encrypt(&key, &IV, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES256 execution");
+        assert_eq!(ciphertext, expected);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_aes256_decrypt_iv_480_bits() {
+        // test case 18
+        let key = [
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08,
+            0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83, 0x08,
+        ];
+        let ciphertext = [
+            0x5A, 0x8D, 0xEF, 0x2F, 0x0C, 0x9E, 0x53, 0xF1, 0xF7, 0x5D, 0x78, 0x53, 0x65, 0x9E, 0x2A, 0x20,
+            0xEE, 0xB2, 0xB2, 0x2A, 0xAF, 0xDE, 0x64, 0x19, 0xA0, 0x58, 0xAB, 0x4F, 0x6F, 0x74, 0x6B, 0xF4,
+            0x0F, 0xC0, 0xC3, 0xB7, 0x80, 0xF2, 0x44, 0x45, 0x2D, 0xA3, 0xEB, 0xF1, 0xC5, 0xD8, 0x2C, 0xDE,
+            0xA2, 0x41, 0x89, 0x97, 0x20, 0x0E, 0xF8, 0x2E, 0x44, 0xAE, 0x7E, 0x3F,
+        ];
+        let tag = [0xA4, 0x4A, 0x82, 0x66, 0xEE, 0x1C, 0x8E, 0xB0, 0xC8, 0xB5, 0xD4, 0xCF, 0x5A, 0xE9, 0xF1, 0x9A];
+        let mut plain = [0; 60];
+        GCM::<AES256>::decrypt(&key, &IV_6, &AAD, &ciphertext, &tag, &mut plain).expect("Error during GCM_AES256 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn gcm_aes128_streaming_encrypt() {
+        let mut ciphertext = [0; 60];
+        let mut gcm = GCMEncryptor::<AES128>::new(&KEY, &IV, 16).expect("Error during GCM_AES128 initialisation");
+        gcm.update_aad(&AAD[0..7]).expect("Error during GCM_AES128 execution");
+        gcm.update_aad(&AAD[7..20]).expect("Error during GCM_AES128 execution");
+        gcm.update(&PLAIN[0..5], &mut ciphertext[0..5]).expect("Error during GCM_AES128 execution");
+        gcm.update(&PLAIN[5..37], &mut ciphertext[5..37]).expect("Error during GCM_AES128 execution");
+        gcm.update(&PLAIN[37..60], &mut ciphertext[37..60]).expect("Error during GCM_AES128 execution");
+        let tag = gcm.finalize().expect("Error during GCM_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_4);
+        assert_eq!(tag, TAG_4);
+    }
+
+    #[test]
+    fn gcm_aes128_streaming_decrypt() {
+        let mut gcm = GCMDecryptor::<AES128>::new(&KEY, &IV, 16).expect("Error during GCM_AES128 initialisation");
+        gcm.update_aad(&AAD).expect("Error during GCM_AES128 execution");
+        gcm.update(&CIPHER_4[0..33]).expect("Error during GCM_AES128 execution");
+        gcm.update(&CIPHER_4[33..60]).expect("Error during GCM_AES128 execution");
+        let plain = gcm.finalize(&TAG_4).expect("Error during GCM_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn gcm_aes128_truncated_tag() {
+        let mut ciphertext = [0; 60];
+        let mut tag = [0; 8];
+        GCM::<AES128>::encrypt(&KEY, &IV, &AAD, &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(tag, TAG_4[0..8]);
+
+        let mut plain = [0; 60];
+        GCM::<AES128>::decrypt(&KEY, &IV, &AAD, &CIPHER_4, &TAG_4[0..4], &mut plain).expect("Error during GCM_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn gcm_aes128_bad_tag() {
+        let mut plain = [0; 60];
+        let mut tag = TAG_4;
+        tag[15] ^= 1;
+        let res = GCM::<AES128>::decrypt(&KEY, &IV, &AAD, &CIPHER_4, &tag, &mut plain);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+        assert_eq!(plain, [0; 60]);
+    }
+
+    #[test]
+    fn gcm_aes128_bad_aad() {
+        let mut plain = [0; 60];
+        let res = GCM::<AES128>::decrypt(&KEY, &IV, &AAD[0..19], &CIPHER_4, &TAG_4, &mut plain);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn gcm_aes128_in_place() {
+        let mut buffer = PLAIN;
+        let mut tag = [0; 16];
+        GCM::<AES128>::encrypt_in_place(&KEY, &IV, &AAD, &mut buffer, &mut tag).expect("Error during GCM_AES128 execution");
+        assert_eq!(buffer, CIPHER_4);
+        assert_eq!(tag, TAG_4);
+
+        tag[15] ^= 1;
+        assert_eq!(GCM::<AES128>::decrypt_in_place(&KEY, &IV, &AAD, &mut buffer, &tag), Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, CIPHER_4);
+
+        GCM::<AES128>::decrypt_in_place(&KEY, &IV, &AAD, &mut buffer, &TAG_4).expect("Error during GCM_AES128 execution");
+        assert_eq!(buffer, PLAIN);
+    }
+
+    #[test]
+    fn gcm_bad_parameters() {
+        assert_eq!(GCMEncryptor::<AES128>::new(&KEY, &IV, 3).err(), Some(AeadError::InvalidTagLength));
+        assert_eq!(GCMEncryptor::<AES128>::new(&KEY, &IV, 17).err(), Some(AeadError::InvalidTagLength));
+        assert_eq!(GCMEncryptor::<AES128>::new(&KEY, &[], 16).err(), Some(AeadError::InvalidNonceLength));
+
+        let mut ciphertext = [0; 16];
+        let mut gcm = GCMEncryptor::<AES128>::new(&KEY, &IV, 16).expect("Error during GCM_AES128 initialisation");
+        gcm.update(&PLAIN[0..16], &mut ciphertext).expect("Error during GCM_AES128 execution");
+        assert!(gcm.update_aad(&AAD).is_err());
+
+        let gcm = GCMDecryptor::<AES128>::new(&KEY, &IV, 16).expect("Error during GCM_AES128 initialisation");
+        assert_eq!(gcm.finalize(&TAG_4[0..12]), Err(AeadError::InvalidTagLength));
+    }
+}
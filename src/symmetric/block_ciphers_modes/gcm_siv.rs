@@ -0,0 +1,251 @@
+//! Definition of GCM-SIV authenticated encryption mode (cf. RFC 8452), resistant to nonce misuse.
+//!
+//! Encryption:
+//!```text
+//!   Ki = E_K(LE32(i) || N), truncated to 8 bytes
+//!   auth key = K0 || K1,  enc key = K2 || K3 (|| K4 || K5 for 256-bit keys)
+//!
+//!   S = POLYVAL(auth key, A || 0^v || P || 0^u || LE64(len(A)) || LE64(len(P)))
+//!   T = E_enc key((S xor N) with the top bit of the last byte cleared)
+//!   C = CTR(enc key, T with the top bit of the last byte set, P)    (32-bit little endian counter
+//!                                                                  on the first 4 bytes, wrapping)
+//! ```
+//!
+//! GCM-SIV works with AES (`GCMSIV<AES128>` and `GCMSIV<AES256>`). Nonces are 12 bytes long and tags
+//! 16 bytes long. Repeating a nonce only reveals whether the same data was encrypted.
+//!
+//! Decryption only releases the plaintext once the tag has been checked (in constant time).
+
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::gcm::GHash;
+
+// maximum length of the plaintext and of the additional data: 2^36 bytes
+const MAX_LENGTH: u64 = 1 << 36;
+
+/// POLYVAL function (cf. RFC 8452, 3), computed with GHASH (cf. RFC 8452, appendix A).
+/// Each of the `parts` is padded with zeros to a multiple of 16 bytes.
+pub(crate) fn polyval(h: &[u8; 16], parts: &[&[u8]]) -> [u8; 16] {
+    // mulX_GHASH(ByteReverse(H))
+    const R: u128 = 0xE1 << 120;
+    let h = u128::from_le_bytes(*h);
+    let h = (h >> 1) ^ (R & 0u128.wrapping_sub(h & 1));
+
+    let mut ghash = GHash::new(&h.to_be_bytes());
+    for chunk in parts.iter().flat_map(|part| part.chunks(16)) {
+        let mut block = [0; 16];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block.reverse();
+        ghash.update(&block);
+    }
+    let mut res = ghash.finalise();
+    res.reverse();
+    res
+}
+
+/// GCM-SIV mode over the 128-bit block cipher `T`.
+pub struct GCMSIV<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> GCMSIV<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // returns the message-authentication key and the message-encryption key
+    fn derive_keys(key: &[u8; NK], nonce: &[u8; 12]) -> Result<([u8; 16], [u8; NK]), AeadError> {
+        let mut blocks = vec![[0; 16]; 2 + NK / 8];
+        for (i, block) in blocks.iter_mut().enumerate() {
+            block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+        }
+        T::cipher_blocks(&mut blocks, key)?;
+
+        let halves: Vec<u8> = blocks.iter().flat_map(|b| b[..8].to_vec()).collect();
+        Ok((halves[..16].try_into().unwrap(), halves[16..].try_into().unwrap()))
+    }
+
+    fn compute_tag(auth_key: &[u8; 16], enc_key: &[u8; NK], nonce: &[u8; 12], aad: &[u8], plaintext: &[u8]) -> Result<[u8; 16], AeadError> {
+        let mut length_block = [0; 16];
+        length_block[..8].copy_from_slice(&(aad.len() as u64 * 8).to_le_bytes());
+        length_block[8..].copy_from_slice(&(plaintext.len() as u64 * 8).to_le_bytes());
+
+        let mut s = polyval(auth_key, &[aad, plaintext, &length_block]);
+        for (s, n) in s.iter_mut().zip(nonce) {
+            *s ^= n;
+        }
+        s[15] &= 0x7F;
+
+        let mut tag = [0; 16];
+        T::cipher(&s, &mut tag, enc_key)?;
+        Ok(tag)
+    }
+
+    fn ctr(enc_key: &[u8; NK], tag: &[u8; 16], data: &mut [u8]) -> Result<(), AeadError> {
+        let mut counter_block = *tag;
+        counter_block[15] |= 0x80;
+        let counter = u32::from_le_bytes(counter_block[..4].try_into().unwrap());
+
+        // keystream blocks are independent, so they are all computed at once
+        let mut keystream = vec![counter_block; data.len().div_ceil(16)];
+        for (i, block) in keystream.iter_mut().enumerate() {
+            block[..4].copy_from_slice(&counter.wrapping_add(i as u32).to_le_bytes());
+        }
+        T::cipher_blocks(&mut keystream, enc_key)?;
+
+        for (d, k) in data.iter_mut().zip(keystream.as_flattened()) {
+            *d ^= k;
+        }
+        Ok(())
+    }
+
+    fn check_parameters(nonce: &[u8], aad: &[u8], data: &[u8], tag: &[u8]) -> Result<[u8; 12], AeadError> {
+        if tag.len() != 16 {
+            return Err(AeadError::InvalidTagLength);
+        }
+        if data.len() as u64 > MAX_LENGTH || aad.len() as u64 > MAX_LENGTH {
+            return Err(AeadError::BadLength);
+        }
+        nonce.try_into().map_err(|_| AeadError::InvalidNonceLength)
+    }
+
+    pub fn encrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::encrypt_in_place(key, nonce, aad, ciphertext, tag)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut decrypted = ciphertext.to_vec();
+        Self::decrypt_in_place(key, nonce, aad, &mut decrypted, tag)?;
+        plaintext.copy_from_slice(&decrypted);
+        Ok(())
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        let nonce = Self::check_parameters(nonce, aad, buffer, tag)?;
+        let (auth_key, enc_key) = Self::derive_keys(key, &nonce)?;
+
+        let computed_tag = Self::compute_tag(&auth_key, &enc_key, &nonce, aad, buffer)?;
+        Self::ctr(&enc_key, &computed_tag, buffer)?;
+        tag.copy_from_slice(&computed_tag);
+        Ok(())
+    }
+
+    /// The plaintext is needed to check the tag: if the tag is not valid, `buffer` is encrypted again and
+    /// still holds the ciphertext on return.
+    pub fn decrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        let nonce = Self::check_parameters(nonce, aad, buffer, tag)?;
+        let (auth_key, enc_key) = Self::derive_keys(key, &nonce)?;
+
+        let tag: [u8; 16] = tag.try_into().unwrap();
+        Self::ctr(&enc_key, &tag, buffer)?;
+        let computed_tag = Self::compute_tag(&auth_key, &enc_key, &nonce, aad, buffer)?;
+        if let Err(error) = verify_tag(&computed_tag, &tag) {
+            Self::ctr(&enc_key, &tag, buffer)?;
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_gcm_siv {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES256};
+    use super::*;
+
+    const NONCE: [u8; 12] = [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    const PLAIN: [u8; 32] = [
+        0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_polyval() {
+        // RFC 8452, appendix A
+        let h = [0x25, 0x62, 0x93, 0x47, 0x58, 0x92, 0x42, 0x76, 0x1D, 0x31, 0xF8, 0x26, 0xBA, 0x4B, 0x75, 0x7B];
+        let x = [
+            0x4F, 0x4F, 0x95, 0x66, 0x8C, 0x83, 0xDF, 0xB6, 0x40, 0x17, 0x62, 0xBB, 0x2D, 0x01, 0xA2, 0x62,
+            0xD1, 0xA2, 0x4D, 0xDD, 0x27, 0x21, 0xD0, 0x06, 0xBB, 0xE4, 0x5F, 0x20, 0xD3, 0xC9, 0xF3, 0x62,
+        ];
+        let expected = [0xF7, 0xA3, 0xB4, 0x7B, 0x84, 0x61, 0x19, 0xFA, 0xE5, 0xB7, 0x86, 0x6C, 0xF5, 0xE5, 0xB7, 0x7E];
+        assert_eq!(polyval(&h, &[&x]), expected);
+    }
+
+    #[test]
+    fn gcm_siv_aes128() {
+        // RFC 8452, C.1
+        let key = [0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut tag = [0; 16];
+        GCMSIV::<AES128>::encrypt(&key, &NONCE, &[], &[], &mut [], &mut tag).expect("Error during GCM-SIV_AES128 execution");
+        assert_eq!(tag, [0xDC, 0x20, 0xE2, 0xD8, 0x3F, 0x25, 0x70, 0x5B, 0xB4, 0x9E, 0x43, 0x9E, 0xCA, 0x56, 0xDE, 0x25]);
+
+        let expected_c = [
+            0x62, 0x00, 0x48, 0xEF, 0x3C, 0x1E, 0x73, 0xE5, 0x7E, 0x02, 0xBB, 0x85, 0x62, 0xC4, 0x16, 0xA3,
+            0x19, 0xE7, 0x3E, 0x4C, 0xAA, 0xC8, 0xE9, 0x6A, 0x1E, 0xCB, 0x29, 0x33, 0x14, 0x5A, 0x1D, 0x71,
+        ];
+        let expected_tag = [0xE6, 0xAF, 0x6A, 0x7F, 0x87, 0x28, 0x7D, 0xA0, 0x59, 0xA7, 0x16, 0x84, 0xED, 0x34, 0x98, 0xE1];
+        let mut ciphertext = [0; 32];
+        GCMSIV::<AES128>::encrypt(&key, &NONCE, &[0x01], &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM-SIV_AES128 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+
+        let mut plain = [0; 32];
+        GCMSIV::<AES128>::decrypt(&key, &NONCE, &[0x01], &ciphertext, &tag, &mut plain).expect("Error during GCM-SIV_AES128 execution");
+        assert_eq!(plain, PLAIN);
+
+        let res = GCMSIV::<AES128>::decrypt(&key, &NONCE, &[0x02], &ciphertext, &tag, &mut plain);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+
+        // in place, the ciphertext is restored when the tag is not valid
+        let mut buffer = ciphertext;
+        let res = GCMSIV::<AES128>::decrypt_in_place(&key, &NONCE, &[0x02], &mut buffer, &tag);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, expected_c);
+        GCMSIV::<AES128>::decrypt_in_place(&key, &NONCE, &[0x01], &mut buffer, &tag).expect("Error during GCM-SIV_AES128 execution");
+        assert_eq!(buffer, PLAIN);
+    }
+
+    #[test]
+    fn gcm_siv_aes256() {
+        // RFC 8452, C.2
+        let mut key = [0; 32];
+        key[0] = 0x01;
+        let expected_c = [
+            0x07, 0xDA, 0xD3, 0x64, 0xBF, 0xC2, 0xB9, 0xDA, 0x89, 0x11, 0x6D, 0x7B, 0xEF, 0x6D, 0xAA, 0xAF,
+            0x6F, 0x25, 0x55, 0x10, 0xAA, 0x65, 0x4F, 0x92, 0x0A, 0xC8, 0x1B, 0x94, 0xE8, 0xBA, 0xD3, 0x65,
+        ];
+        let expected_tag = [0xAE, 0xA1, 0xBA, 0xD1, 0x27, 0x02, 0xE1, 0x96, 0x56, 0x04, 0x37, 0x4A, 0xAB, 0x96, 0xDB, 0xBC];
+
+        let mut ciphertext = [0; 32];
+        let mut tag = [0; 16];
+        GCMSIV::<AES256>::encrypt(&key, &NONCE, &[0x01], &PLAIN, &mut ciphertext, &mut tag).expect("Error during GCM-SIV_AES256 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+    }
+
+    #[test]
+    fn gcm_siv_counter_wrap() {
+        // RFC 8452, C.3
+        let key = [0; 32];
+        let plain = [
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0x4D, 0xB9, 0x23, 0xDC, 0x79, 0x3E, 0xE6, 0x49, 0x7C, 0x76, 0xDC, 0xC0, 0x3A, 0x98, 0xE1, 0x08,
+        ];
+        let expected_c = [
+            0xF3, 0xF8, 0x0F, 0x2C, 0xF0, 0xCB, 0x2D, 0xD9, 0xC5, 0x98, 0x4F, 0xCD, 0xA9, 0x08, 0x45, 0x6C,
+            0xC5, 0x37, 0x70, 0x3B, 0x5B, 0xA7, 0x03, 0x24, 0xA6, 0x79, 0x3A, 0x7B, 0xF2, 0x18, 0xD3, 0xEA,
+        ];
+        let expected_tag = [0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        let mut ciphertext = [0; 32];
+        let mut tag = [0; 16];
+        GCMSIV::<AES256>::encrypt(&key, &[0; 12], &[], &plain, &mut ciphertext, &mut tag).expect("Error during GCM-SIV_AES256 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+    }
+}
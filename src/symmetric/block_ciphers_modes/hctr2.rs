@@ -0,0 +1,171 @@
+//! Definition of HCTR2 length-preserving encryption mode (cf. "Length-preserving encryption with HCTR2",
+//! Crowley, Huckleberry and Biggers)
+//!
+//! Encryption of `P = M || N` (`M` being the first block) with the tweak `T`:
+//!```text
+//!   h  = E(0),  L = E(1)
+//!
+//!   MM = M xor H(T, N)
+//!   UU = E(MM)
+//!   S  = MM xor UU xor L
+//!   V  = N xor XCTR(S)                 (keystream E(S xor 1) || E(S xor 2) || ...)
+//!   U  = UU xor H(T, V)
+//!   C  = U || V
+//!
+//!   H(T, X) = POLYVAL(h, [2|T| + 2] || T || X)              if |X| is a multiple of the block length
+//!   H(T, X) = POLYVAL(h, [2|T| + 3] || T || X || 1 || 0*)   otherwise
+//! ```
+//! Integers are little endian 128-bit blocks, lengths are in bits, and `T` is padded with zeros.
+//!
+//! HCTR2 is a wide-block cipher: changing any bit of the plaintext or of the tweak changes the whole
+//! ciphertext. There is no authentication, and the ciphertext has the same length as the plaintext
+//! (at least one block). It is used by Linux fscrypt (with AES-256) to encrypt filenames.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::gcm_siv::polyval;
+use crate::utils::xor_arrays;
+
+/// HCTR2 mode over the 128-bit block cipher `T`.
+pub struct HCTR2<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> HCTR2<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn hash(h: &[u8; 16], tweak: &[u8], data: &[u8]) -> [u8; 16] {
+        let has_remainder = !data.len().is_multiple_of(16);
+        let first_block = 16 * tweak.len() as u128 + 2 + has_remainder as u128;
+
+        let mut input = first_block.to_le_bytes().to_vec();
+        input.extend_from_slice(tweak);
+        input.resize(input.len().next_multiple_of(16), 0);
+        input.extend_from_slice(data);
+        if has_remainder {
+            input.push(1);
+        }
+        polyval(h, &[&input])
+    }
+
+    fn xctr(key: &[u8; NK], s: &[u8; 16], input: &[u8], output: &mut [u8]) -> Result<(), &'static str> {
+        let s = u128::from_le_bytes(*s);
+        let mut keystream: Vec<[u8; 16]> = (1..=input.len().div_ceil(16) as u128).map(|i| (s ^ i).to_le_bytes()).collect();
+        T::cipher_blocks(&mut keystream, key)?;
+        for ((o, i), k) in output.iter_mut().zip(input).zip(keystream.iter().flatten()) {
+            *o = i ^ k;
+        }
+        Ok(())
+    }
+
+    // encryption and decryption only differ by the direction of the block cipher
+    fn process(key: &[u8; NK], tweak: &[u8], input: &[u8], output: &mut [u8], decrypt: bool) -> Result<(), &'static str> {
+        if input.len() != output.len() {
+            return Err("Input and output must have the same length");
+        }
+        if input.len() < 16 {
+            return Err("Data must be at least one block long");
+        }
+
+        let mut h = [0; 16];
+        T::cipher(&[0; 16], &mut h, key)?;
+        let mut l = [0; 16];
+        T::cipher(&1u128.to_le_bytes(), &mut l, key)?;
+
+        let first = xor_arrays(input[..16].try_into().unwrap(), &Self::hash(&h, tweak, &input[16..]));
+        let mut second = [0; 16];
+        if decrypt {
+            T::decipher(&mut second, &first, key)?;
+        } else {
+            T::cipher(&first, &mut second, key)?;
+        }
+
+        let s = xor_arrays(&xor_arrays(&first, &second), &l);
+        Self::xctr(key, &s, &input[16..], &mut output[16..])?;
+        let hash = Self::hash(&h, tweak, &output[16..]);
+        output[..16].copy_from_slice(&xor_arrays(&second, &hash));
+        Ok(())
+    }
+
+    /// Encrypts `plaintext` (at least 16 bytes) with the `tweak` (of any length).
+    pub fn encrypt(key: &[u8; NK], tweak: &[u8], plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        Self::process(key, tweak, plaintext, ciphertext, false)
+    }
+
+    /// Decrypts `ciphertext` (at least 16 bytes) with the `tweak` (of any length).
+    pub fn decrypt(key: &[u8; NK], tweak: &[u8], ciphertext: &[u8], plaintext: &mut [u8]) -> Result<(), &'static str> {
+        Self::process(key, tweak, ciphertext, plaintext, true)
+    }
+}
+
+#[cfg(test)]
+mod tests_hctr2 {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES256};
+    use super::*;
+
+    // test vectors from the Linux kernel (crypto/testmgr.h, aes_hctr2_tv_template)
+    const TWEAK: [u8; 32] = [
+        0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F,
+        0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F,
+    ];
+
+    #[test]
+    fn hctr2_aes256() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 48] = core::array::from_fn(|i| 0x80 + i as u8);
+        let expected = [
+            0x0B, 0x48, 0x20, 0xBB, 0x27, 0x47, 0x9A, 0x9B, 0x26, 0x9B, 0x49, 0xD6, 0x87, 0xE4, 0x9F, 0xD8,
+            0xD2, 0x5E, 0x07, 0x83, 0xDF, 0x03, 0x2C, 0x2B, 0xF7, 0xDC, 0x9B, 0xCE, 0x25, 0x0C, 0xCA, 0xFD,
+            0x6E, 0x17, 0xB3, 0xDD, 0x5E, 0xBF, 0xFD, 0x63, 0xD5, 0xDE, 0xF4, 0xB8, 0x32, 0x39, 0xE9, 0x1D,
+        ];
+
+        let mut ciphertext = [0; 48];
+        HCTR2::<AES256>::encrypt(&key, &TWEAK, &plain, &mut ciphertext).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(ciphertext, expected);
+        let mut decrypted = [0; 48];
+        HCTR2::<AES256>::decrypt(&key, &TWEAK, &ciphertext, &mut decrypted).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(decrypted, plain);
+
+        // single block
+        let expected = [0x4B, 0x3C, 0xF6, 0xD7, 0x7A, 0x02, 0x62, 0x61, 0xA9, 0x54, 0x55, 0xA4, 0x75, 0x13, 0x3F, 0x93];
+        let mut ciphertext = [0; 16];
+        HCTR2::<AES256>::encrypt(&key, &TWEAK, &plain[..16], &mut ciphertext).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(ciphertext, expected);
+
+        // empty tweak
+        let expected = [0x81, 0x6D, 0xDF, 0x20, 0x16, 0x74, 0x6B, 0xA4, 0x47, 0xB6, 0xD7, 0xAC, 0x65, 0x94, 0x03, 0x7A];
+        HCTR2::<AES256>::encrypt(&key, &[], &[0; 16], &mut ciphertext).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn hctr2_partial_block() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let plain: [u8; 31] = core::array::from_fn(|i| 0x80 + i as u8);
+        let expected = [
+            0x33, 0x41, 0x5C, 0x04, 0x53, 0xF4, 0xF1, 0x28, 0x9E, 0x7B, 0xAB, 0x29, 0xDA, 0x3F, 0x63, 0x11,
+            0xA5, 0xD5, 0x37, 0x41, 0x85, 0x9A, 0xD1, 0x3D, 0x9D, 0x19, 0x01, 0x24, 0xEE, 0x48, 0x7F,
+        ];
+
+        let mut ciphertext = [0; 31];
+        HCTR2::<AES256>::encrypt(&key, &TWEAK, &plain, &mut ciphertext).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(ciphertext, expected);
+        let mut decrypted = [0; 31];
+        HCTR2::<AES256>::decrypt(&key, &TWEAK, &ciphertext, &mut decrypted).expect("Error during HCTR2_AES256 execution");
+        assert_eq!(decrypted, plain);
+
+        let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let expected = [
+            0x33, 0xE8, 0x34, 0x6E, 0x00, 0xB3, 0x19, 0x56, 0x4A, 0x8B, 0xB0, 0x32, 0x3A, 0x4D, 0x28, 0x9E,
+            0x8B, 0x51, 0x4B, 0x2A,
+        ];
+        let mut ciphertext = [0; 20];
+        HCTR2::<AES128>::encrypt(&key, &TWEAK, &plain[..20], &mut ciphertext).expect("Error during HCTR2_AES128 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn hctr2_bad_length() {
+        let key = [0; 32];
+        assert!(HCTR2::<AES256>::encrypt(&key, &TWEAK, &[0; 15], &mut [0; 15]).is_err());
+        assert!(HCTR2::<AES256>::encrypt(&key, &TWEAK, &[0; 16], &mut [0; 17]).is_err());
+    }
+}
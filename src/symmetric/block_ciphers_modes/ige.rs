@@ -0,0 +1,130 @@
+//! Definition of IGE (infinite garble extension) chaining mode, used by Telegram MTProto
+//!
+//! Encryption and decryption:
+//!```text
+//!   Ci = E(Pi xor C(i-1)) xor P(i-1)
+//!   Pi = D(Ci xor P(i-1)) xor C(i-1)
+//! ```
+//!
+//! Unlike CBC, an error in a ciphertext block propagates to all the following plaintext blocks.
+//! The IV is made of two blocks, `[C0, P0]`, in the order used by OpenSSL and Telegram (where the
+//! 32-byte IV is `C0 || P0`).
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::common::{BlockChainingWithIV, StreamingBlockChaining};
+use crate::utils::{check_cipher_params, xor_arrays};
+
+pub struct IGE{}
+
+impl<T, const NB: usize> BlockChainingWithIV<T> for IGE
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type IVType = [[u8; NB]; 2];
+
+    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+        // check parameters
+        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
+
+        ciphertext.copy_from_slice(plaintext);
+        let mut state = *iv;
+        <IGE as StreamingBlockChaining<T>>::cipher_in_place(ciphertext, key, &mut state)
+    }
+
+    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+        // check parameters
+        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
+
+        plaintext.copy_from_slice(ciphertext);
+        let mut state = *iv;
+        <IGE as StreamingBlockChaining<T>>::decipher_in_place(plaintext, key, &mut state)
+    }
+}
+
+impl<T, const NB: usize> StreamingBlockChaining<T> for IGE
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type State = [[u8; NB]; 2];  // last ciphertext block and last plaintext block (IV at the beginning)
+
+    fn cipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
+
+        // do the chain
+        for chunk in data.chunks_exact_mut(NB) {
+            let plain: [u8; NB] = chunk.try_into().unwrap();
+            let mut block = [0; NB];
+            T::cipher(&xor_arrays(&plain, &state[0]), &mut block, key)?;
+            *state = [xor_arrays(&block, &state[1]), plain];
+            chunk.copy_from_slice(&state[0]);
+        }
+
+        Ok(())
+    }
+
+    fn decipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
+
+        // each block decryption depends on the previous plaintext block
+        for chunk in data.chunks_exact_mut(NB) {
+            let cipher: [u8; NB] = chunk.try_into().unwrap();
+            let mut block = [0; NB];
+            T::decipher(&mut block, &xor_arrays(&cipher, &state[1]), key)?;
+            *state = [cipher, xor_arrays(&block, &state[0])];
+            chunk.copy_from_slice(&state[1]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_ige {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use super::*;
+
+    #[test]
+    fn ige_encrypt_decrypt() {
+        // "AES IGE mode", Ben Laurie (OpenSSL test vectors)
+        let key = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let iv = [
+            [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F],
+            [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F],
+        ];
+        let expected = [
+            0x1A, 0x85, 0x19, 0xA6, 0x55, 0x7B, 0xE6, 0x52, 0xE9, 0xDA, 0x8E, 0x43, 0xDA, 0x4E, 0xF4, 0x45,
+            0x3C, 0xF4, 0x56, 0xB4, 0xCA, 0x48, 0x8A, 0xA3, 0x83, 0xC7, 0x9C, 0x98, 0xB3, 0x47, 0x97, 0xCB,
+        ];
+
+        let mut ciphertext = [0; 32];
+        <IGE as BlockChainingWithIV<AES128>>::cipher(&[0; 32], &mut ciphertext, &key, &iv).expect("Error during IGE_AES128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut plain = [0xFF; 32];
+        <IGE as BlockChainingWithIV<AES128>>::decipher(&mut plain, &ciphertext, &key, &iv).expect("Error during IGE_AES128 execution");
+        assert_eq!(plain, [0; 32]);
+    }
+
+    #[test]
+    fn ige_encrypt_decrypt_text_key() {
+        // "AES IGE mode", Ben Laurie (OpenSSL test vectors)
+        let key = *b"This is an imple";
+        let iv = [*b"mentation of IGE", *b" mode for OpenSS"];
+        let plain = [
+            0x99, 0x70, 0x64, 0x87, 0xA1, 0xCD, 0xE6, 0x13, 0xBC, 0x6D, 0xE0, 0xB6, 0xF2, 0x4B, 0x1C, 0x7A,
+            0xA4, 0x48, 0xC8, 0xB9, 0xC3, 0x40, 0x3E, 0x34, 0x67, 0xA8, 0xCA, 0xD8, 0x93, 0x40, 0xF5, 0x3B,
+        ];
+
+        let mut ciphertext = [0; 32];
+        <IGE as BlockChainingWithIV<AES128>>::cipher(&plain, &mut ciphertext, &key, &iv).expect("Error during IGE_AES128 execution");
+        assert_eq!(&ciphertext, b"L. Let's hope Ben got it right!\n");
+
+        let mut decrypted = [0; 32];
+        <IGE as BlockChainingWithIV<AES128>>::decipher(&mut decrypted, &ciphertext, &key, &iv).expect("Error during IGE_AES128 execution");
+        assert_eq!(decrypted, plain);
+    }
+}
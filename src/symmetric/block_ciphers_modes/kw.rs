@@ -0,0 +1,294 @@
+//! Definition of key wrapping modes (cf. NIST SP 800-38F, RFC 3394 and RFC 5649)
+//!
+//! The data is cut into semiblocks (half blocks) `R1 ... Rn`, and the wrapping function W mixes them
+//! with an integrity check value `A` (ICV at the beginning), in 6 passes:
+//!```text
+//!   for t = 1 to 6n:
+//!        A || R1 || R2 ... Rn
+//!        |    |
+//!       ---------
+//!      |    K    |
+//!       ---------
+//!        |    |
+//!        B1   B2
+//!
+//!     A = B1 xor t,  and  R1 ... Rn = R2 ... Rn || B2
+//! ```
+//!
+//! Unwrapping inverts W, and checks the ICV (in constant time).
+//!
+//! 3 variants are available:
+//! - [KW]: data is a multiple of the semiblock size, at least 2 semiblocks
+//! - [TKW]: KW over 3-key TDEA (32-bit semiblocks)
+//! - [KWP]: data of any length (at least 1 byte), padded with zeros. Only for 128-bit block ciphers (e.g. AES)
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers::triple_des::TripleDES3K;
+use crate::utils::constant_time_eq;
+
+/// Error returned when the integrity check fails during unwrapping.
+pub const INTEGRITY_CHECK_FAILED: &str = "Integrity check failed";
+
+// wrapping function W: `data` is ICV || plaintext
+fn wrap_semiblocks<T, const NB: usize>(key: &T::KeyType, data: &mut [u8]) -> Result<(), &'static str>
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    let sb = NB / 2;
+    let n = data.len() / sb - 1;
+    let mut block = [0; NB];
+    for j in 0..6 {
+        for i in 1..=n {
+            block[..sb].copy_from_slice(&data[..sb]);
+            block[sb..].copy_from_slice(&data[i * sb..(i + 1) * sb]);
+            let input = block;
+            T::cipher(&input, &mut block, key)?;
+
+            let t = (n * j + i) as u64;
+            data[..sb].copy_from_slice(&block[..sb]);
+            for (a, t) in data[..sb].iter_mut().rev().zip(t.to_le_bytes()) {
+                *a ^= t;
+            }
+            data[i * sb..(i + 1) * sb].copy_from_slice(&block[sb..]);
+        }
+    }
+    Ok(())
+}
+
+// unwrapping function W^-1: `data` becomes ICV || plaintext
+fn unwrap_semiblocks<T, const NB: usize>(key: &T::KeyType, data: &mut [u8]) -> Result<(), &'static str>
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    let sb = NB / 2;
+    let n = data.len() / sb - 1;
+    let mut block = [0; NB];
+    for j in (0..6).rev() {
+        for i in (1..=n).rev() {
+            let t = (n * j + i) as u64;
+            block[..sb].copy_from_slice(&data[..sb]);
+            for (a, t) in block[..sb].iter_mut().rev().zip(t.to_le_bytes()) {
+                *a ^= t;
+            }
+            block[sb..].copy_from_slice(&data[i * sb..(i + 1) * sb]);
+            let input = block;
+            T::decipher(&mut block, &input, key)?;
+
+            data[..sb].copy_from_slice(&block[..sb]);
+            data[i * sb..(i + 1) * sb].copy_from_slice(&block[sb..]);
+        }
+    }
+    Ok(())
+}
+
+/// Key wrap (KW) over the block cipher `T`. The ICV is 0xA6 repeated over a semiblock.
+pub struct KW<T>(std::marker::PhantomData<T>);
+
+/// Key wrap over 3-key TDEA (TKW from SP 800-38F): 32-bit semiblocks.
+pub type TKW = KW<TripleDES3K>;
+
+impl<T, const NB: usize> KW<T>
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    pub fn wrap(key: &T::KeyType, plaintext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let sb = NB / 2;
+        if plaintext.len() < 2 * sb || !plaintext.len().is_multiple_of(sb) {
+            return Err("Plaintext length must be a multiple of half the block size, and at least 2 half blocks");
+        }
+
+        let mut data = vec![0xA6; sb];
+        data.extend_from_slice(plaintext);
+        wrap_semiblocks::<T, NB>(key, &mut data)?;
+        Ok(data)
+    }
+
+    pub fn unwrap(key: &T::KeyType, ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let sb = NB / 2;
+        if ciphertext.len() < 3 * sb || !ciphertext.len().is_multiple_of(sb) {
+            return Err("Ciphertext length must be a multiple of half the block size, and at least 3 half blocks");
+        }
+
+        let mut data = ciphertext.to_vec();
+        unwrap_semiblocks::<T, NB>(key, &mut data)?;
+        if !constant_time_eq(&data[..sb], &vec![0xA6; sb]) {
+            return Err(INTEGRITY_CHECK_FAILED);
+        }
+        Ok(data.split_off(sb))
+    }
+}
+
+/// Key wrap with padding (KWP) over the 128-bit block cipher `T`.
+/// The ICV is 0xA65959A6 followed by the plaintext length (32 bits, big endian).
+pub struct KWP<T>(std::marker::PhantomData<T>);
+
+impl<T> KWP<T>
+where
+    T: BlockCipher<BlockType = [u8; 16]>,
+{
+    pub fn wrap(key: &T::KeyType, plaintext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        if plaintext.is_empty() || plaintext.len() as u64 > u32::MAX as u64 {
+            return Err("Plaintext length must be between 1 and 2^32 - 1 bytes");
+        }
+
+        let mut data = vec![0xA6, 0x59, 0x59, 0xA6];
+        data.extend_from_slice(&(plaintext.len() as u32).to_be_bytes());
+        data.extend_from_slice(plaintext);
+        data.resize(8 + plaintext.len().div_ceil(8) * 8, 0);
+
+        // a single semiblock of data is encrypted as one block
+        if data.len() == 16 {
+            let input: [u8; 16] = data[..].try_into().unwrap();
+            let mut block = [0; 16];
+            T::cipher(&input, &mut block, key)?;
+            return Ok(block.to_vec());
+        }
+
+        wrap_semiblocks::<T, 16>(key, &mut data)?;
+        Ok(data)
+    }
+
+    pub fn unwrap(key: &T::KeyType, ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        if ciphertext.len() < 16 || !ciphertext.len().is_multiple_of(8) {
+            return Err("Ciphertext length must be a multiple of 8 bytes, and at least 16 bytes");
+        }
+
+        let mut data = ciphertext.to_vec();
+        if data.len() == 16 {
+            let input: [u8; 16] = data[..].try_into().unwrap();
+            let mut block = [0; 16];
+            T::decipher(&mut block, &input, key)?;
+            data.copy_from_slice(&block);
+        } else {
+            unwrap_semiblocks::<T, 16>(key, &mut data)?;
+        }
+
+        // the ICV, the length and the padding are all checked before returning an error
+        let len = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+        let padded_len = data.len() - 8;
+        let valid_len = len + 8 > padded_len && len <= padded_len;
+        let padding_start = 8 + len.min(padded_len);
+        let zeros = vec![0; data.len() - padding_start];
+        let valid_padding = constant_time_eq(&data[padding_start..], &zeros);
+        let valid_icv = constant_time_eq(&data[..4], &[0xA6, 0x59, 0x59, 0xA6]);
+        if !(valid_icv & valid_len & valid_padding) {
+            return Err(INTEGRITY_CHECK_FAILED);
+        }
+
+        data.truncate(8 + len);
+        Ok(data.split_off(8))
+    }
+}
+
+#[cfg(test)]
+mod tests_kw {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES192, AES256};
+    use super::*;
+
+    #[test]
+    fn kw_aes128_wrap_unwrap() {
+        // RFC 3394, 4.1
+        let kek = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let key_data = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF];
+        let expected = [
+            0x1F, 0xA6, 0x8B, 0x0A, 0x81, 0x12, 0xB4, 0x47, 0xAE, 0xF3, 0x4B, 0xD8, 0xFB, 0x5A, 0x7B, 0x82,
+            0x9D, 0x3E, 0x86, 0x23, 0x71, 0xD2, 0xCF, 0xE5,
+        ];
+
+        let wrapped = KW::<AES128>::wrap(&kek, &key_data).expect("Error during KW_AES128 execution");
+        assert_eq!(wrapped, expected);
+        let unwrapped = KW::<AES128>::unwrap(&kek, &wrapped).expect("Error during KW_AES128 execution");
+        assert_eq!(unwrapped, key_data);
+    }
+
+    #[test]
+    fn kw_aes256_wrap_unwrap() {
+        // RFC 3394, 4.6
+        let kek: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let key_data = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ];
+        let expected = [
+            0x28, 0xC9, 0xF4, 0x04, 0xC4, 0xB8, 0x10, 0xF4, 0xCB, 0xCC, 0xB3, 0x5C, 0xFB, 0x87, 0xF8, 0x26,
+            0x3F, 0x57, 0x86, 0xE2, 0xD8, 0x0E, 0xD3, 0x26, 0xCB, 0xC7, 0xF0, 0xE7, 0x1A, 0x99, 0xF4, 0x3B,
+            0xFB, 0x98, 0x8B, 0x9B, 0x7A, 0x02, 0xDD, 0x21,
+        ];
+
+        let wrapped = KW::<AES256>::wrap(&kek, &key_data).expect("Error during KW_AES256 execution");
+        assert_eq!(wrapped, expected);
+        let unwrapped = KW::<AES256>::unwrap(&kek, &wrapped).expect("Error during KW_AES256 execution");
+        assert_eq!(unwrapped, key_data);
+    }
+
+    #[test]
+    fn kw_integrity_check() {
+        let kek = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let mut wrapped = KW::<AES128>::wrap(&kek, &[0x42; 24]).expect("Error during KW_AES128 execution");
+        wrapped[20] ^= 1;
+        assert_eq!(KW::<AES128>::unwrap(&kek, &wrapped), Err(INTEGRITY_CHECK_FAILED));
+        assert!(KW::<AES128>::wrap(&kek, &[0x42; 8]).is_err());
+        assert!(KW::<AES128>::wrap(&kek, &[0x42; 20]).is_err());
+    }
+
+    #[test]
+    fn kwp_aes192_wrap_unwrap() {
+        // RFC 5649, 6
+        let kek = [
+            0x58, 0x40, 0xDF, 0x6E, 0x29, 0xB0, 0x2A, 0xF1, 0xAB, 0x49, 0x3B, 0x70, 0x5B, 0xF1, 0x6E, 0xA1,
+            0xAE, 0x83, 0x38, 0xF4, 0xDC, 0xC1, 0x76, 0xA8,
+        ];
+        let key_20 = [
+            0xC3, 0x7B, 0x7E, 0x64, 0x92, 0x58, 0x43, 0x40, 0xBE, 0xD1, 0x22, 0x07, 0x80, 0x89, 0x41, 0x15,
+            0x50, 0x68, 0xF7, 0x38,
+        ];
+        let expected_20 = [
+            0x13, 0x8B, 0xDE, 0xAA, 0x9B, 0x8F, 0xA7, 0xFC, 0x61, 0xF9, 0x77, 0x42, 0xE7, 0x22, 0x48, 0xEE,
+            0x5A, 0xE6, 0xAE, 0x53, 0x60, 0xD1, 0xAE, 0x6A, 0x5F, 0x54, 0xF3, 0x73, 0xFA, 0x54, 0x3B, 0x6A,
+        ];
+        let key_7 = [0x46, 0x6F, 0x72, 0x50, 0x61, 0x73, 0x69];
+        let expected_7 = [0xAF, 0xBE, 0xB0, 0xF0, 0x7D, 0xFB, 0xF5, 0x41, 0x92, 0x00, 0xF2, 0xCC, 0xB5, 0x0B, 0xB2, 0x4F];
+
+        let wrapped = KWP::<AES192>::wrap(&kek, &key_20).expect("Error during KWP_AES192 execution");
+        assert_eq!(wrapped, expected_20);
+        let unwrapped = KWP::<AES192>::unwrap(&kek, &wrapped).expect("Error during KWP_AES192 execution");
+        assert_eq!(unwrapped, key_20);
+
+        let wrapped = KWP::<AES192>::wrap(&kek, &key_7).expect("Error during KWP_AES192 execution");
+        assert_eq!(wrapped, expected_7);
+        let unwrapped = KWP::<AES192>::unwrap(&kek, &wrapped).expect("Error during KWP_AES192 execution");
+        assert_eq!(unwrapped, key_7);
+    }
+
+    #[test]
+    fn kwp_integrity_check() {
+        let kek = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+        let mut wrapped = KWP::<AES128>::wrap(&kek, &[0x42; 9]).expect("Error during KWP_AES128 execution");
+        wrapped[3] ^= 1;
+        assert_eq!(KWP::<AES128>::unwrap(&kek, &wrapped), Err(INTEGRITY_CHECK_FAILED));
+
+        // a KW ciphertext has a bad ICV for KWP
+        let wrapped = KW::<AES128>::wrap(&kek, &[0x42; 16]).expect("Error during KW_AES128 execution");
+        assert_eq!(KWP::<AES128>::unwrap(&kek, &wrapped), Err(INTEGRITY_CHECK_FAILED));
+        assert!(KWP::<AES128>::wrap(&kek, &[]).is_err());
+    }
+
+    #[test]
+    fn tkw_wrap_unwrap() {
+        let kek = [
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01,
+            0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23,
+        ];
+        let key_data = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB];
+        let expected = [0x21, 0xA4, 0x35, 0x66, 0x39, 0x8D, 0x98, 0xB6, 0xEA, 0xA0, 0x27, 0x73, 0xB6, 0xB9, 0x2A, 0x78];
+
+        let wrapped = TKW::wrap(&kek, &key_data).expect("Error during TKW execution");
+        assert_eq!(wrapped, expected);
+        let unwrapped = TKW::unwrap(&kek, &wrapped).expect("Error during TKW execution");
+        assert_eq!(unwrapped, key_data);
+
+        let mut wrapped = wrapped;
+        wrapped[0] ^= 1;
+        assert_eq!(TKW::unwrap(&kek, &wrapped), Err(INTEGRITY_CHECK_FAILED));
+    }
+}
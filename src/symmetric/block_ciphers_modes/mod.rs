@@ -2,12 +2,32 @@
 //!
 //! Currently, these chaining modes are implemented:
 //! - ECB
-//! - CBC
+//! - CBC, and its ciphertext stealing variants (CBC-CS1, CBC-CS2 and CBC-CS3)
+//! - PCBC
+//! - IGE (with a two-block IV)
+//! - CTR
+//! - CFB (1, 8, 64 and 128-bit segments)
+//! - OFB
+//!
+//! The GCM, CCM, OCB3 and EAX authenticated encryption modes are also available (see [gcm], [ccm], [ocb]
+//! and [eax]), as well as
+//! the nonce misuse-resistant SIV and GCM-SIV (see [siv] and [gcm_siv]).
+//! XTS, used for disk encryption, is available in [xts], as well as the wide-block HCTR2 and Adiantum
+//! (see [hctr2] and [adiantum]). Key wrapping (KW, KWP and TKW) is available in [kw], and
+//! format-preserving encryption (FF1 and FF3-1) in [fpe].
 //!
 //! Each of these is defined in a struct and exposes the `cipher` and `decipher` static methods to
 //! treat some amount of data (data length must be a multiple of block length).
+//! ECB, CBC, PCBC and IGE also expose `encrypt_padded` and `decrypt_padded`, to treat data of any length
+//! with one of the schemes of [padding]. To treat data that arrives in chunks, they can also be used
+//! through the streaming objects of [streaming].
+//! CTR, CFB and OFB are exceptions: they are streaming objects implementing
+//! [crate::symmetric::stream_ciphers::common::StreamCipher], so they treat data of any length
+//! (see [ctr], [cfb] and [ofb] for more details).
 //!
 //! Chaining modes are compatible with all block ciphers defined in this crate.
+//! When blocks can be treated independently (e.g. ECB, CBC decryption), they are given all at once
+//! to the block cipher (see [crate::symmetric::block_ciphers::common::BlockCipher::cipher_blocks]).
 //!
 //! # Example
 //!
@@ -28,4 +48,22 @@
 
 pub mod common;
 pub mod ecb;
-pub mod cbc;
\ No newline at end of file
+pub mod cbc;
+pub mod pcbc;
+pub mod ige;
+pub mod ctr;
+pub mod cfb;
+pub mod ofb;
+pub mod gcm;
+pub mod ccm;
+pub mod ocb;
+pub mod eax;
+pub mod siv;
+pub mod gcm_siv;
+pub mod xts;
+pub mod hctr2;
+pub mod adiantum;
+pub mod kw;
+pub mod fpe;
+pub mod padding;
+pub mod streaming;
\ No newline at end of file
@@ -0,0 +1,280 @@
+//! Definition of OCB3 authenticated encryption mode (cf. RFC 7253)
+//!
+//! Encryption:
+//!```text
+//!   L_* = E(0^128),  L_$ = double(L_*),  L_0 = double(L_$),  L_i = double(L_(i-1))
+//!   Offset_0 from E(nonce block)
+//!
+//!   Offset_i = Offset_(i-1) xor L_ntz(i)
+//!   C_i      = Offset_i xor E(P_i xor Offset_i)
+//!   C_*      = P_* xor E(Offset_m xor L_*)                  (last partial block)
+//!
+//!   Checksum = P_1 xor ... xor P_m xor (P_* || 1 || 0*)
+//!   T        = MSB_t(E(Checksum xor Offset xor L_$) xor HASH(A))
+//! ```
+//!
+//! Nonces are from 1 to 15 bytes long (12 bytes is the usual choice), and tags from 1 to 16 bytes long
+//! (the tag length is part of the nonce block, so a tag cannot be truncated afterwards).
+//! Decryption only releases the plaintext once the tag has been checked (in constant time).
+
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+use crate::symmetric::block_ciphers::common::BlockCipher;
+
+fn double(s: u128) -> u128 {
+    (s << 1) ^ (0x87 * (s >> 127))
+}
+
+// L_* and L_$, then L_0 ... L_(n-1)
+struct OffsetTable {
+    l_star: u128,
+    l_dollar: u128,
+    l: Vec<u128>
+}
+
+impl OffsetTable {
+    fn new(l_star: u128) -> Self {
+        let l_dollar = double(l_star);
+        OffsetTable { l_star, l_dollar, l: vec![double(l_dollar)] }
+    }
+
+    // offsets of the full blocks 1 to `count`, starting from `offset`
+    fn offsets(&mut self, mut offset: u128, count: usize) -> Vec<u128> {
+        let mut offsets = Vec::with_capacity(count);
+        for i in 1..=count {
+            let ntz = i.trailing_zeros() as usize;
+            while self.l.len() <= ntz {
+                let last = *self.l.last().unwrap();
+                self.l.push(double(last));
+            }
+            offset ^= self.l[ntz];
+            offsets.push(offset);
+        }
+        offsets
+    }
+}
+
+/// OCB3 mode over the 128-bit block cipher `T`.
+pub struct OCB<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> OCB<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    fn encipher(block: u128, key: &[u8; NK]) -> Result<u128, AeadError> {
+        let mut res = [0; 16];
+        T::cipher(&block.to_be_bytes(), &mut res, key)?;
+        Ok(u128::from_be_bytes(res))
+    }
+
+    // `blocks` are whitened with `offsets`, then all given at once to the block cipher
+    fn encipher_blocks(blocks: &mut [[u8; 16]], offsets: &[u128], key: &[u8; NK], decrypt: bool) -> Result<(), AeadError> {
+        for (block, offset) in blocks.iter_mut().zip(offsets) {
+            *block = (u128::from_be_bytes(*block) ^ offset).to_be_bytes();
+        }
+        if decrypt {
+            T::decipher_blocks(blocks, key)?;
+        } else {
+            T::cipher_blocks(blocks, key)?;
+        }
+        Ok(())
+    }
+
+    fn xor_blocks(blocks: &[[u8; 16]]) -> u128 {
+        blocks.iter().fold(0, |acc, block| acc ^ u128::from_be_bytes(*block))
+    }
+
+    // pads a partial block with 1 || 0*
+    fn pad(data: &[u8]) -> u128 {
+        let mut block = [0; 16];
+        block[..data.len()].copy_from_slice(data);
+        block[data.len()] = 0x80;
+        u128::from_be_bytes(block)
+    }
+
+    fn hash(table: &mut OffsetTable, key: &[u8; NK], aad: &[u8]) -> Result<u128, AeadError> {
+        let (blocks, last) = aad.as_chunks::<16>();
+        let offsets = table.offsets(0, blocks.len());
+        let mut blocks = blocks.to_vec();
+        Self::encipher_blocks(&mut blocks, &offsets, key, false)?;
+        let mut sum = Self::xor_blocks(&blocks);
+
+        if !last.is_empty() {
+            let offset = offsets.last().copied().unwrap_or(0) ^ table.l_star;
+            sum ^= Self::encipher(Self::pad(last) ^ offset, key)?;
+        }
+        Ok(sum)
+    }
+
+    fn initial_offset(key: &[u8; NK], nonce: &[u8], tag_len: usize) -> Result<u128, AeadError> {
+        let mut n = [0; 16];
+        n[16 - nonce.len()..].copy_from_slice(nonce);
+        let nonce_block = ((tag_len as u128 * 8 % 128) << 121) | (1 << (8 * nonce.len())) | u128::from_be_bytes(n);
+
+        let bottom = (nonce_block & 0x3F) as u32;
+        let ktop = Self::encipher(nonce_block & !0x3F, key)?;
+        let stretch = ((ktop >> 64) as u64) ^ ((ktop >> 56) as u64);
+        if bottom == 0 {
+            return Ok(ktop);
+        }
+        Ok((ktop << bottom) | (stretch >> (64 - bottom)) as u128)
+    }
+
+    fn check_parameters(nonce: &[u8], tag: &[u8]) -> Result<(), AeadError> {
+        if !(1..=15).contains(&nonce.len()) {
+            return Err(AeadError::InvalidNonceLength);
+        }
+        if !(1..=16).contains(&tag.len()) {
+            return Err(AeadError::InvalidTagLength);
+        }
+        Ok(())
+    }
+
+    // encrypts or decrypts `buffer` in place, and returns the full tag
+    fn process(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag_len: usize, decrypt: bool) -> Result<[u8; 16], AeadError> {
+        let mut table = OffsetTable::new(Self::encipher(0, key)?);
+        let (blocks, last) = buffer.as_chunks_mut::<16>();
+
+        let initial_offset = Self::initial_offset(key, nonce, tag_len)?;
+        let offsets = table.offsets(initial_offset, blocks.len());
+        let mut checksum = if decrypt { 0 } else { Self::xor_blocks(blocks) };
+        Self::encipher_blocks(blocks, &offsets, key, decrypt)?;
+        for (block, offset) in blocks.iter_mut().zip(&offsets) {
+            *block = (u128::from_be_bytes(*block) ^ offset).to_be_bytes();
+        }
+        if decrypt {
+            checksum = Self::xor_blocks(blocks);
+        }
+        let mut offset = offsets.last().copied().unwrap_or(initial_offset);
+
+        // last partial block
+        if !last.is_empty() {
+            offset ^= table.l_star;
+            let pad = Self::encipher(offset, key)?.to_be_bytes();
+            if !decrypt {
+                checksum ^= Self::pad(last);
+            }
+            for (byte, p) in last.iter_mut().zip(pad) {
+                *byte ^= p;
+            }
+            if decrypt {
+                checksum ^= Self::pad(last);
+            }
+        }
+
+        let tag = Self::encipher(checksum ^ offset ^ table.l_dollar, key)? ^ Self::hash(&mut table, key, aad)?;
+        Ok(tag.to_be_bytes())
+    }
+
+    pub fn encrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::encrypt_in_place(key, nonce, aad, ciphertext, tag)
+    }
+
+    /// `plaintext` is only written if the tag is valid.
+    pub fn decrypt(key: &[u8; NK], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut decrypted = ciphertext.to_vec();
+        Self::decrypt_in_place(key, nonce, aad, &mut decrypted, tag)?;
+        plaintext.copy_from_slice(&decrypted);
+        Ok(())
+    }
+
+    pub fn encrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        Self::check_parameters(nonce, tag)?;
+        let full_tag = Self::process(key, nonce, aad, buffer, tag.len(), false)?;
+        let tag_len = tag.len();
+        tag.copy_from_slice(&full_tag[..tag_len]);
+        Ok(())
+    }
+
+    /// The plaintext is needed to check the tag: if the tag is not valid, `buffer` is encrypted again and
+    /// still holds the ciphertext on return.
+    pub fn decrypt_in_place(key: &[u8; NK], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        Self::check_parameters(nonce, tag)?;
+        let full_tag = Self::process(key, nonce, aad, buffer, tag.len(), true)?;
+        if let Err(error) = verify_tag(&full_tag[..tag.len()], tag) {
+            Self::process(key, nonce, aad, buffer, tag.len(), false)?;
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_ocb {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use super::*;
+
+    const KEY: [u8; 16] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+
+    #[test]
+    fn ocb_aes128_empty() {
+        // RFC 7253, appendix A (first sample)
+        let nonce = [0xBB, 0xAA, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00];
+        let mut tag = [0; 16];
+        OCB::<AES128>::encrypt(&KEY, &nonce, &[], &[], &mut [], &mut tag).expect("Error during OCB_AES128 execution");
+        assert_eq!(tag, [0x78, 0x54, 0x07, 0xBF, 0xFF, 0xC8, 0xAD, 0x9E, 0xDC, 0xC5, 0x52, 0x0A, 0xC9, 0x11, 0x1E, 0xE6]);
+    }
+
+    #[test]
+    fn ocb_aes128_encrypt_decrypt() {
+        // RFC 7253, appendix A (sample with 40 bytes)
+        let nonce = [0xBB, 0xAA, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D];
+        let data: [u8; 40] = core::array::from_fn(|i| i as u8);
+        let expected_c = [
+            0xD5, 0xCA, 0x91, 0x74, 0x84, 0x10, 0xC1, 0x75, 0x1F, 0xF8, 0xA2, 0xF6, 0x18, 0x25, 0x5B, 0x68,
+            0xA0, 0xA1, 0x2E, 0x09, 0x3F, 0xF4, 0x54, 0x60, 0x6E, 0x59, 0xF9, 0xC1, 0xD0, 0xDD, 0xC5, 0x4B,
+            0x65, 0xE8, 0x62, 0x8E, 0x56, 0x8B, 0xAD, 0x7A,
+        ];
+        let expected_tag = [0xED, 0x07, 0xBA, 0x06, 0xA4, 0xA6, 0x94, 0x83, 0xA7, 0x03, 0x54, 0x90, 0xC5, 0x76, 0x9E, 0x60];
+
+        let mut ciphertext = [0; 40];
+        let mut tag = [0; 16];
+        OCB::<AES128>::encrypt(&KEY, &nonce, &data, &data, &mut ciphertext, &mut tag).expect("Error during OCB_AES128 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+
+        let mut plain = [0; 40];
+        OCB::<AES128>::decrypt(&KEY, &nonce, &data, &ciphertext, &tag, &mut plain).expect("Error during OCB_AES128 execution");
+        assert_eq!(plain, data);
+
+        let res = OCB::<AES128>::decrypt(&KEY, &nonce, &data[1..], &ciphertext, &tag, &mut plain);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn ocb_aes128_tag_96() {
+        // RFC 7253, appendix A (96-bit tag)
+        let key = [0x0F, 0x0E, 0x0D, 0x0C, 0x0B, 0x0A, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x00];
+        let nonce = [0xBB, 0xAA, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D];
+        let data: [u8; 40] = core::array::from_fn(|i| i as u8);
+        let expected_c = [
+            0x17, 0x92, 0xA4, 0xE3, 0x1E, 0x07, 0x55, 0xFB, 0x03, 0xE3, 0x1B, 0x22, 0x11, 0x6E, 0x6C, 0x2D,
+            0xDF, 0x9E, 0xFD, 0x6E, 0x33, 0xD5, 0x36, 0xF1, 0xA0, 0x12, 0x4B, 0x0A, 0x55, 0xBA, 0xE8, 0x84,
+            0xED, 0x93, 0x48, 0x15, 0x29, 0xC7, 0x6B, 0x6A,
+        ];
+        let expected_tag = [0xD0, 0xC5, 0x15, 0xF4, 0xD1, 0xCD, 0xD4, 0xFD, 0xAC, 0x4F, 0x02, 0xAA];
+
+        let mut ciphertext = [0; 40];
+        let mut tag = [0; 12];
+        OCB::<AES128>::encrypt(&key, &nonce, &data, &data, &mut ciphertext, &mut tag).expect("Error during OCB_AES128 execution");
+        assert_eq!(ciphertext, expected_c);
+        assert_eq!(tag, expected_tag);
+
+        // in place, the ciphertext is restored when the tag is not valid
+        let mut buffer = data;
+        OCB::<AES128>::encrypt_in_place(&key, &nonce, &data, &mut buffer, &mut tag).expect("Error during OCB_AES128 execution");
+        assert_eq!(buffer, expected_c);
+        assert_eq!(tag, expected_tag);
+        let res = OCB::<AES128>::decrypt_in_place(&key, &nonce, &data[1..], &mut buffer, &tag);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, expected_c);
+        OCB::<AES128>::decrypt_in_place(&key, &nonce, &data, &mut buffer, &tag).expect("Error during OCB_AES128 execution");
+        assert_eq!(buffer, data);
+    }
+}
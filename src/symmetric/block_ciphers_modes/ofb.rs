@@ -0,0 +1,155 @@
+//! Definition of OFB chaining mode (cf. NIST SP 800-38A)
+//!
+//! Encryption and decryption are the same operation:
+//!```text
+//!        IV
+//!        |
+//!       ---        ---                ---
+//!      | K | ---> | K | --> ... ---> | K |
+//!       ---   |    ---   |            ---
+//!        |    |     |    |             |
+//!  P1 ---+    P2 ---+    |       Pn ---+
+//!        |          |                  |
+//!        C1         C2                 Cn
+//! ```
+//!
+//! The keystream does not depend on the data, so [OFB] implements the [StreamCipher] trait and
+//! treats data of any length.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::common::IVInitStruct;
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+/// OFB mode over the block cipher `T`.
+pub struct OFB<T: BlockCipher> {
+    key: T::KeyType,
+    keystream: T::BlockType,  // last output block of the block cipher (IV at the beginning)
+    remaining_bytes: usize  // number of bytes that can be ciphered using `keystream`
+}
+
+impl<T, const NK: usize, const NB: usize> StreamCipher for OFB<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+{
+    const KEY_SIZE: usize = NK;
+    type KeyType = [u8; NK];
+    type InitStruct = IVInitStruct<NB>;
+    type State = [u8; NB];
+
+    fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+        OFB { key: *key, keystream: init_struct.iv, remaining_bytes: 0 }
+    }
+
+    fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        // check parameters
+        if plaintext.len() != ciphertext.len() {
+            return Err("Plaintext and ciphertext buffers must have the same length");
+        }
+
+        ciphertext.copy_from_slice(plaintext);
+        self.cipher_in_place(ciphertext)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        for byte in data.iter_mut() {
+            if self.remaining_bytes == 0 {
+                let input = self.keystream;
+                T::cipher(&input, &mut self.keystream, &self.key)?;
+                self.remaining_bytes = NB;
+            }
+            *byte ^= self.keystream[NB - self.remaining_bytes];
+            self.remaining_bytes -= 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_ofb {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use crate::symmetric::block_ciphers::triple_des::TripleDES3K;
+    use super::*;
+
+    const PLAIN: [u8; 64] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF,
+        0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17, 0xAD, 0x2B, 0x41, 0x7B, 0xE6, 0x6C, 0x37, 0x10,
+    ];
+
+    const KEY: [u8; 16] = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+
+    const INIT: IVInitStruct<16> = IVInitStruct { iv: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F] };
+
+    // SP 800-38A, F.4.1
+    const CIPHER_AES128: [u8; 64] = [
+        0x3B, 0x3F, 0xD9, 0x2E, 0xB7, 0x2D, 0xAD, 0x20, 0x33, 0x34, 0x49, 0xF8, 0xE8, 0x3C, 0xFB, 0x4A,
+        0x77, 0x89, 0x50, 0x8D, 0x16, 0x91, 0x8F, 0x03, 0xF5, 0x3C, 0x52, 0xDA, 0xC5, 0x4E, 0xD8, 0x25,
+        0x97, 0x40, 0x05, 0x1E, 0x9C, 0x5F, 0xEC, 0xF6, 0x43, 0x44, 0xF7, 0xA8, 0x22, 0x60, 0xED, 0xCC,
+        0x30, 0x4C, 0x65, 0x28, 0xF6, 0x59, 0xC7, 0x78, 0x66, 0xA5, 0x10, 0xD9, 0xC1, 0xD6, 0xAE, 0x5E,
+    ];
+
+    #[test]
+    fn ofb_aes128_encrypt() {
+        let mut ciphertext = [0; 64];
+        let mut ofb = OFB::<AES128>::new(&INIT, &KEY);
+        ofb.cipher(&PLAIN, &mut ciphertext).expect("Error during OFB_AES128 execution");
+        assert_eq!(ciphertext, CIPHER_AES128);
+    }
+
+    #[test]
+    fn ofb_aes128_decrypt_fragmented() {
+        let mut plain = [0; 64];
+        let mut ofb = OFB::<AES128>::new(&INIT, &KEY);
+        ofb.cipher(&CIPHER_AES128[0..3], &mut plain[0..3]).expect("Error during OFB_AES128 execution");
+        ofb.cipher(&CIPHER_AES128[3..35], &mut plain[3..35]).expect("Error during OFB_AES128 execution");
+        ofb.cipher(&CIPHER_AES128[35..64], &mut plain[35..64]).expect("Error during OFB_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn ofb_aes128_in_place() {
+        let mut buffer = CIPHER_AES128;
+        let mut ofb = OFB::<AES128>::new(&INIT, &KEY);
+        ofb.cipher_in_place(&mut buffer[0..3]).expect("Error during OFB_AES128 execution");
+        ofb.cipher(&CIPHER_AES128[3..35], &mut [0; 32]).expect("Error during OFB_AES128 execution");
+        ofb.cipher_in_place(&mut buffer[35..64]).expect("Error during OFB_AES128 execution");
+        assert_eq!(buffer[0..3], PLAIN[0..3]);
+        assert_eq!(buffer[35..64], PLAIN[35..64]);
+    }
+
+    #[test]
+    fn ofb_aes128_gfsbox() {
+        // CAVP OFBGFSbox128.rsp, COUNT = 0
+        let init = IVInitStruct { iv: [0xF3, 0x44, 0x81, 0xEC, 0x3C, 0xC6, 0x27, 0xBA, 0xCD, 0x5D, 0xC3, 0xFB, 0x08, 0xF2, 0x73, 0xE6] };
+        let mut ciphertext = [0; 16];
+        let expected = [0x03, 0x36, 0x76, 0x3E, 0x96, 0x6D, 0x92, 0x59, 0x5A, 0x56, 0x7C, 0xC9, 0xCE, 0x53, 0x7F, 0x5E];
+        let mut ofb = OFB::<AES128>::new(&init, &[0; 16]);
+        ofb.cipher(&[0; 16], &mut ciphertext).expect("Error during OFB_AES128 execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn ofb_triple_des_3k_partial_block() {
+        let init = IVInitStruct { iv: [0xF6, 0x9F, 0x24, 0x45, 0xDF, 0x4F, 0x9B, 0x17] };
+        let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF,
+            0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01,
+            0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x01, 0x23];
+        let expected = [
+            0x07, 0x8B, 0xB7, 0x4E, 0x59, 0xCE, 0x7E, 0xD6, 0x26, 0x7E, 0x12, 0x06, 0x92, 0x66, 0x7D, 0xA1,
+            0xA5, 0x86, 0x62, 0xD7, 0xE0, 0x4C, 0xBC, 0x64, 0x21, 0x44, 0xD5, 0x5C, 0x03,
+        ];
+        let mut ciphertext = [0; 29];
+        let mut ofb = OFB::<TripleDES3K>::new(&init, &key);
+        ofb.cipher(&PLAIN[0..29], &mut ciphertext).expect("Error during OFB_TDES execution");
+        assert_eq!(ciphertext, expected);
+    }
+
+    #[test]
+    fn ofb_bad_length() {
+        let mut ciphertext = [0; 15];
+        let mut ofb = OFB::<AES128>::new(&INIT, &KEY);
+        assert!(ofb.cipher(&PLAIN[0..16], &mut ciphertext).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Padding schemes, used to encrypt data whose length is not a multiple of the block size with ECB or CBC.
+//!
+//! Examples with 8-byte blocks and 5 bytes of data (`DD`):
+//!```text
+//!   PKCS#7          DD DD DD DD DD 03 03 03
+//!   ANSI X9.23      DD DD DD DD DD 00 00 03
+//!   ISO 10126       DD DD DD DD DD ?? ?? 03      (?? are random bytes)
+//!   ISO/IEC 7816-4  DD DD DD DD DD 80 00 00
+//!   Zero padding    DD DD DD DD DD 00 00 00
+//! ```
+//!
+//! Except for zero padding, a full block of padding is added when the data length is already a multiple
+//! of the block size, so that padding can always be removed.
+//!
+//! Unpadding only looks at the last block, and runs in constant time: its duration does not depend on the
+//! content of the block nor on the place of the first invalid byte. Only one error is returned for all
+//! invalid paddings, so that the library itself is not a padding oracle.
+
+use rand::RngExt;
+
+const INVALID_PADDING: &str = "Invalid padding";
+
+/// Trait implemented by all padding schemes.
+///
+/// - `pad`: returns a copy of `data`, padded to a multiple of `block_size`
+/// - `unpad`: returns `data` without its padding (`data` length must be a non-zero multiple of `block_size`)
+pub trait Padding {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str>;
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str>;
+}
+
+// constant time comparisons, returning 0xFF (true) or 0x00 (false)
+fn ct_eq(a: u8, b: u8) -> u8 {
+    let x = (a ^ b) as u16;
+    (x.wrapping_sub(1) >> 8) as u8
+}
+
+fn ct_le(a: u8, b: u8) -> u8 {
+    let x = (b as u16).wrapping_sub(a as u16);
+    !((x >> 8) as u8)
+}
+
+fn check_block_size(block_size: usize) -> Result<(), &'static str> {
+    if block_size == 0 || block_size > 255 {
+        return Err("Block size must be between 1 and 255 bytes");
+    }
+    Ok(())
+}
+
+// returns the last block of `data`
+fn last_block(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+    check_block_size(block_size)?;
+    if data.is_empty() || !data.len().is_multiple_of(block_size) {
+        return Err("Length of padded data is not a non-zero multiple of block size");
+    }
+    Ok(&data[data.len() - block_size..])
+}
+
+// pads with `n` bytes: `filler(i)` gives the padding byte at position i (from 0 to n - 1)
+fn pad_with(data: &[u8], block_size: usize, filler: impl Fn(usize, u8) -> u8) -> Result<Vec<u8>, &'static str> {
+    check_block_size(block_size)?;
+    let n = block_size - data.len() % block_size;
+    let mut res = Vec::with_capacity(data.len() + n);
+    res.extend_from_slice(data);
+    res.extend((0..n).map(|i| filler(i, n as u8)));
+    Ok(res)
+}
+
+// common part of the schemes ending with the padding length:
+// `check(byte, in_padding)` returns 0xFF when `byte` (not the last one) is valid
+fn unpad_with_length(data: &[u8], block_size: usize, check: impl Fn(u8, u8) -> u8) -> Result<&[u8], &'static str> {
+    let block = last_block(data, block_size)?;
+    let n = block[block_size - 1];
+    let mut valid = !ct_eq(n, 0) & ct_le(n, block_size as u8);
+    for (i, byte) in block[..block_size - 1].iter().enumerate() {
+        let in_padding = ct_le((block_size - i) as u8, n);
+        valid &= check(*byte, in_padding);
+    }
+
+    if std::hint::black_box(valid) != 0xFF {
+        return Err(INVALID_PADDING);
+    }
+    Ok(&data[..data.len() - n as usize])
+}
+
+/// PKCS#7 (cf. RFC 5652): each padding byte is the padding length.
+pub struct PKCS7 {}
+
+impl Padding for PKCS7 {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str> {
+        pad_with(data, block_size, |_, n| n)
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+        let n = data.last().copied().unwrap_or(0);
+        unpad_with_length(data, block_size, |byte, in_padding| !in_padding | ct_eq(byte, n))
+    }
+}
+
+/// ANSI X9.23: zeros, then the padding length.
+pub struct ANSIX923 {}
+
+impl Padding for ANSIX923 {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str> {
+        pad_with(data, block_size, |i, n| if i == n as usize - 1 { n } else { 0 })
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+        unpad_with_length(data, block_size, |byte, in_padding| !in_padding | ct_eq(byte, 0))
+    }
+}
+
+/// ISO 10126: random bytes, then the padding length.
+pub struct ISO10126 {}
+
+impl Padding for ISO10126 {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str> {
+        let mut res = pad_with(data, block_size, |_, n| n)?;
+        let len = res.len();
+        let n = res[len - 1] as usize;
+        rand::rng().fill(&mut res[len - n..len - 1]);
+        Ok(res)
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+        unpad_with_length(data, block_size, |_, _| 0xFF)
+    }
+}
+
+/// ISO/IEC 7816-4: a 0x80 byte, then zeros.
+pub struct ISO7816 {}
+
+impl Padding for ISO7816 {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str> {
+        pad_with(data, block_size, |i, _| if i == 0 { 0x80 } else { 0 })
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+        let block = last_block(data, block_size)?;
+
+        // from the end: zeros, then 0x80
+        let mut found = 0u8;
+        let mut valid = 0xFFu8;
+        let mut n = 0u8;
+        for (i, byte) in block.iter().enumerate().rev() {
+            let is_zero = ct_eq(*byte, 0);
+            let is_marker = ct_eq(*byte, 0x80);
+            valid &= found | is_zero | is_marker;
+            n |= !found & is_marker & (block_size - i) as u8;
+            found |= is_marker;
+        }
+        valid &= found;
+
+        if std::hint::black_box(valid) != 0xFF {
+            return Err(INVALID_PADDING);
+        }
+        Ok(&data[..data.len() - n as usize])
+    }
+}
+
+/// Zero padding: zeros are added up to the end of the block (nothing is added to full blocks).
+///
+/// Unpadding removes all the zeros at the end of the last block, so data ending with zeros is not
+/// recovered properly. It never fails.
+pub struct ZeroPadding {}
+
+impl Padding for ZeroPadding {
+    fn pad(data: &[u8], block_size: usize) -> Result<Vec<u8>, &'static str> {
+        check_block_size(block_size)?;
+        let mut res = data.to_vec();
+        res.resize(data.len().div_ceil(block_size) * block_size, 0);
+        Ok(res)
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], &'static str> {
+        if data.is_empty() {
+            check_block_size(block_size)?;
+            return Ok(data);
+        }
+        let block = last_block(data, block_size)?;
+
+        let mut in_padding = 0xFFu8;
+        let mut n = 0u8;
+        for byte in block.iter().rev() {
+            in_padding &= ct_eq(*byte, 0);
+            n += in_padding & 1;
+        }
+        Ok(&data[..data.len() - n as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests_padding {
+    use super::*;
+
+    #[test]
+    fn test_pad() {
+        let data = [0xDD; 5];
+        assert_eq!(PKCS7::pad(&data, 8).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 3, 3, 3]);
+        assert_eq!(ANSIX923::pad(&data, 8).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0, 0, 3]);
+        assert_eq!(ISO7816::pad(&data, 8).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0x80, 0, 0]);
+        assert_eq!(ZeroPadding::pad(&data, 8).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0, 0, 0]);
+        let padded = ISO10126::pad(&data, 8).unwrap();
+        assert_eq!(padded.len(), 8);
+        assert_eq!(padded[7], 3);
+
+        // full blocks
+        assert_eq!(PKCS7::pad(&[], 4).unwrap(), [4, 4, 4, 4]);
+        assert_eq!(PKCS7::pad(&[0xDD; 4], 4).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 4, 4, 4, 4]);
+        assert_eq!(ISO7816::pad(&[0xDD; 4], 4).unwrap(), [0xDD, 0xDD, 0xDD, 0xDD, 0x80, 0, 0, 0]);
+        assert_eq!(ZeroPadding::pad(&[0xDD; 4], 4).unwrap(), [0xDD; 4]);
+        assert!(PKCS7::pad(&data, 256).is_err());
+    }
+
+    #[test]
+    fn test_unpad() {
+        let data = [0xDD; 5];
+        for block_size in [1, 5, 8, 16] {
+            assert_eq!(PKCS7::unpad(&PKCS7::pad(&data, block_size).unwrap(), block_size).unwrap(), data);
+            assert_eq!(ANSIX923::unpad(&ANSIX923::pad(&data, block_size).unwrap(), block_size).unwrap(), data);
+            assert_eq!(ISO10126::unpad(&ISO10126::pad(&data, block_size).unwrap(), block_size).unwrap(), data);
+            assert_eq!(ISO7816::unpad(&ISO7816::pad(&data, block_size).unwrap(), block_size).unwrap(), data);
+            assert_eq!(ZeroPadding::unpad(&ZeroPadding::pad(&data, block_size).unwrap(), block_size).unwrap(), data);
+        }
+        assert!(PKCS7::unpad(&[8; 8], 8).unwrap().is_empty());
+        assert!(ISO7816::unpad(&[0x80, 0, 0, 0], 4).unwrap().is_empty());
+        assert_eq!(ZeroPadding::unpad(&[0xDD, 0, 0, 0, 0xDD, 0, 0, 0], 4).unwrap(), [0xDD, 0, 0, 0, 0xDD]);
+        assert!(ZeroPadding::unpad(&[], 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unpad_invalid() {
+        assert_eq!(PKCS7::unpad(&[0xDD, 0xDD, 2, 3], 4), Err(INVALID_PADDING));
+        assert_eq!(PKCS7::unpad(&[0xDD, 0xDD, 0xDD, 0], 4), Err(INVALID_PADDING));
+        assert_eq!(PKCS7::unpad(&[5, 5, 5, 5], 4), Err(INVALID_PADDING));
+        assert_eq!(ANSIX923::unpad(&[0xDD, 1, 0, 3], 4), Err(INVALID_PADDING));
+        assert_eq!(ISO10126::unpad(&[0xDD, 0xDD, 0xDD, 9], 4), Err(INVALID_PADDING));
+        assert_eq!(ISO7816::unpad(&[0xDD, 0x80, 1, 0], 4), Err(INVALID_PADDING));
+        assert_eq!(ISO7816::unpad(&[0, 0, 0, 0], 4), Err(INVALID_PADDING));
+        assert!(PKCS7::unpad(&[], 4).is_err());
+        assert!(PKCS7::unpad(&[1, 1, 1], 4).is_err());
+    }
+}
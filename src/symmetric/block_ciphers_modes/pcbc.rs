@@ -0,0 +1,153 @@
+//! Definition of PCBC (propagating CBC) chaining mode, used by Kerberos v4 and WASTE
+//!
+//! Encryption:
+//!```text
+//!          P1             P2             Pn
+//!          |----          |----          |
+//!          |    |         |    |         |
+//!   IV ----+    |    -----+    |    -----+
+//!          |    |   |     |    |   |     |
+//!         ---   |   |    ---   |   |    ---
+//!        | K |  |   |   | K |  |   |   | K |
+//!         ---   |   |    ---   |   |    ---
+//!          |    |   |     |    |   |     |
+//!          |----+---      |----+---      |
+//!          |              |              |
+//!          C1             C2             Cn
+//!```
+//!
+//! Decryption:
+//!```text
+//!          C1             C2             Cn
+//!          |----          |----          |
+//!          |    |         |    |         |
+//!         ---   |        ---   |        ---
+//!        | K |  |       | K |  |       | K |
+//!         ---   |        ---   |        ---
+//!          |    |         |    |         |
+//!   IV ----+    |    -----+    |    -----+
+//!          |    |   |     |    |   |     |
+//!          |----+---      |----+---      |
+//!          |              |              |
+//!          P1             P2             Pn
+//! ```
+//!
+//! Unlike CBC, an error in a ciphertext block propagates to all the following plaintext blocks.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::common::{BlockChainingWithIV, StreamingBlockChaining};
+use crate::utils::{check_cipher_params, slice_to_arrays, xor_arrays};
+
+pub struct PCBC{}
+
+impl<T, const NB: usize> BlockChainingWithIV<T> for PCBC
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type IVType = [u8; NB];
+
+    fn cipher(plaintext: &[u8], ciphertext: &mut [u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+        // check parameters
+        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
+
+        ciphertext.copy_from_slice(plaintext);
+        let mut state = *iv;
+        <PCBC as StreamingBlockChaining<T>>::cipher_in_place(ciphertext, key, &mut state)
+    }
+
+    fn decipher(plaintext: &mut [u8], ciphertext: &[u8], key: &T::KeyType, iv: &Self::IVType) -> Result<(), &'static str> {
+        // check parameters
+        check_cipher_params(plaintext, ciphertext, T::BLOCK_SIZE)?;
+
+        plaintext.copy_from_slice(ciphertext);
+        let mut state = *iv;
+        <PCBC as StreamingBlockChaining<T>>::decipher_in_place(plaintext, key, &mut state)
+    }
+}
+
+impl<T, const NB: usize> StreamingBlockChaining<T> for PCBC
+where
+    T: BlockCipher<BlockType = [u8; NB]>,
+{
+    type State = [u8; NB];  // last plaintext block xor last ciphertext block (IV at the beginning)
+
+    fn cipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
+
+        // do the chain
+        for chunk in data.chunks_exact_mut(NB) {
+            let plain: [u8; NB] = chunk.try_into().unwrap();
+            let mut cipher = [0; NB];
+            T::cipher(&xor_arrays(&plain, state), &mut cipher, key)?;
+            *state = xor_arrays(&plain, &cipher);
+            chunk.copy_from_slice(&cipher);
+        }
+
+        Ok(())
+    }
+
+    fn decipher_in_place(data: &mut [u8], key: &T::KeyType, state: &mut Self::State) -> Result<(), &'static str> {
+        if !data.len().is_multiple_of(NB) {
+            return Err("Length of data is not a multiple of block size");
+        }
+
+        // block decryptions do not depend on each other, so they are all done at once
+        let ciphertext = slice_to_arrays::<NB, u8>(data);
+        let mut blocks = ciphertext.clone();
+        T::decipher_blocks(&mut blocks, key)?;
+
+        // do the chain
+        for ((chunk, block), chunk_c) in data.chunks_exact_mut(NB).zip(blocks.iter()).zip(ciphertext.iter()) {
+            let plain = xor_arrays(block, state);
+            *state = xor_arrays(&plain, chunk_c);
+            chunk.copy_from_slice(&plain);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_pcbc {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use super::*;
+
+    const KEY: [u8; 16] = [0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C];
+    const IV: [u8; 16] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+    const PLAIN: [u8; 48] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11, 0xE5, 0xFB, 0xC1, 0x19, 0x1A, 0x0A, 0x52, 0xEF,
+    ];
+    // the first block is the same as with CBC (NIST SP 800-38A, F.2.1)
+    const CIPHER: [u8; 48] = [
+        0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9, 0x19, 0x7D,
+        0x9E, 0x8B, 0xAF, 0xF1, 0x2A, 0xD5, 0x27, 0x0A, 0x0D, 0x1E, 0xEF, 0x93, 0xD7, 0x03, 0x79, 0x94,
+        0x57, 0x00, 0xB3, 0x98, 0x03, 0x77, 0x9F, 0xA3, 0x5A, 0x3C, 0x60, 0x0A, 0x49, 0xA1, 0x63, 0xC0,
+    ];
+
+    #[test]
+    fn pcbc_encrypt_decrypt() {
+        let mut ciphertext = [0; 48];
+        <PCBC as BlockChainingWithIV<AES128>>::cipher(&PLAIN, &mut ciphertext, &KEY, &IV).expect("Error during PCBC_AES128 execution");
+        assert_eq!(ciphertext, CIPHER);
+
+        let mut plain = [0; 48];
+        <PCBC as BlockChainingWithIV<AES128>>::decipher(&mut plain, &CIPHER, &KEY, &IV).expect("Error during PCBC_AES128 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn pcbc_error_propagation() {
+        let mut ciphertext = CIPHER;
+        ciphertext[0] ^= 1;
+        let mut plain = [0; 48];
+        <PCBC as BlockChainingWithIV<AES128>>::decipher(&mut plain, &ciphertext, &KEY, &IV).expect("Error during PCBC_AES128 execution");
+        assert!(plain.chunks_exact(16).zip(PLAIN.chunks_exact(16)).all(|(a, b)| a != b));
+
+        let res = <PCBC as BlockChainingWithIV<AES128>>::decipher(&mut plain[..47], &CIPHER[..47], &KEY, &IV);
+        assert!(res.is_err());
+    }
+}
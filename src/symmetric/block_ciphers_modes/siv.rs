@@ -0,0 +1,248 @@
+//! Definition of SIV authenticated encryption mode (cf. RFC 5297), resistant to nonce misuse.
+//!
+//! Encryption:
+//!```text
+//!   V = S2V(K1, AD1, ..., ADn, P)
+//!   C = CTR(K2, V with bits 63 and 31 cleared, P)
+//!
+//!   S2V: D = CMAC(K1, 0^128)
+//!        D = dbl(D) xor CMAC(K1, ADi)                    for each AD component
+//!        T = P xorend D                                  if len(P) >= 128 bits
+//!        T = dbl(D) xor (P || 10*)                       otherwise
+//!        V = CMAC(K1, T)
+//! ```
+//!
+//! The key is `K1 || K2`, so AES-SIV with 256-bit keys uses `SIV<AES128>`.
+//! The associated data is a vector of up to 126 components. With a nonce, the components are
+//! `[aad, nonce]` ([SIV::encrypt] and [SIV::decrypt]). Repeating a nonce only reveals whether the same
+//! data was encrypted.
+//!
+//! Decryption only releases the plaintext once the synthetic IV `V` has been checked (in constant time).
+
+use crate::mac::cmac::{dbl, CMAC};
+use crate::mac::common::MAC;
+use crate::symmetric::aead::common::{verify_tag, AeadError};
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::ctr::{CounterEndianness, CounterSize, CTRInitStruct, CTR};
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+use crate::utils::xor_arrays;
+
+// maximum number of associated data components
+const MAX_COMPONENTS: usize = 126;
+
+/// SIV mode over the 128-bit block cipher `T`.
+pub struct SIV<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> SIV<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // returns K1 and K2
+    fn check_parameters(key: &[u8], associated_data: &[&[u8]]) -> Result<([u8; NK], [u8; NK]), AeadError> {
+        if key.len() != 2 * NK {
+            return Err(AeadError::InvalidKeyLength);
+        }
+        if associated_data.len() > MAX_COMPONENTS {
+            return Err(AeadError::Cipher("Too many associated data components"));
+        }
+        Ok((key[..NK].try_into().unwrap(), key[NK..].try_into().unwrap()))
+    }
+
+    fn s2v(key: &[u8; NK], associated_data: &[&[u8]], plaintext: &[u8]) -> [u8; 16] {
+        let mut d = CMAC::<T>::compute(&[0; 16], *key);
+        for component in associated_data {
+            d = xor_arrays(&dbl(&d), &CMAC::<T>::compute(component, *key));
+        }
+
+        if plaintext.len() >= 16 {
+            let (start, end) = plaintext.split_at(plaintext.len() - 16);
+            let end = xor_arrays(end.try_into().unwrap(), &d);
+            CMAC::<T>::compute_parts(&[start, &end], key)
+        } else {
+            let mut padded = [0; 16];
+            padded[..plaintext.len()].copy_from_slice(plaintext);
+            padded[plaintext.len()] = 0x80;
+            CMAC::<T>::compute(&xor_arrays(&dbl(&d), &padded), *key)
+        }
+    }
+
+    fn ctr(key: &[u8; NK], v: &[u8; 16], data: &mut [u8]) -> Result<(), AeadError> {
+        let mut counter_block = *v;
+        counter_block[8] &= 0x7F;
+        counter_block[12] &= 0x7F;
+        let init = CTRInitStruct { counter_block, counter_size: CounterSize::Bits128, endianness: CounterEndianness::BigEndian };
+        // the counter is incremented modulo 2^128
+        CTR::<T>::new(&init, key).wrapping_counter().cipher_in_place(data)?;
+        Ok(())
+    }
+
+    /// Encrypts `plaintext`, authenticated with all the `associated_data` components, and returns the
+    /// synthetic IV (to be sent before the ciphertext).
+    pub fn encrypt_with_components(key: &[u8], associated_data: &[&[u8]], plaintext: &[u8], ciphertext: &mut [u8]) -> Result<[u8; 16], AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        ciphertext.copy_from_slice(plaintext);
+        Self::encrypt_with_components_in_place(key, associated_data, ciphertext)
+    }
+
+    /// Decrypts `ciphertext` and checks the synthetic IV `v`. `plaintext` is only written if `v` is valid.
+    pub fn decrypt_with_components(key: &[u8], associated_data: &[&[u8]], ciphertext: &[u8], v: &[u8; 16], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        if plaintext.len() != ciphertext.len() {
+            return Err(AeadError::BadLength);
+        }
+        let mut decrypted = ciphertext.to_vec();
+        Self::decrypt_with_components_in_place(key, associated_data, &mut decrypted, v)?;
+        plaintext.copy_from_slice(&decrypted);
+        Ok(())
+    }
+
+    /// Same as [SIV::encrypt_with_components], but `buffer` is overwritten with the ciphertext.
+    pub fn encrypt_with_components_in_place(key: &[u8], associated_data: &[&[u8]], buffer: &mut [u8]) -> Result<[u8; 16], AeadError> {
+        let (k1, k2) = Self::check_parameters(key, associated_data)?;
+
+        let v = Self::s2v(&k1, associated_data, buffer);
+        Self::ctr(&k2, &v, buffer)?;
+        Ok(v)
+    }
+
+    /// Same as [SIV::decrypt_with_components], but `buffer` is overwritten with the plaintext. The plaintext is
+    /// needed to check `v`: if `v` is not valid, `buffer` is encrypted again and still holds the ciphertext on return.
+    pub fn decrypt_with_components_in_place(key: &[u8], associated_data: &[&[u8]], buffer: &mut [u8], v: &[u8; 16]) -> Result<(), AeadError> {
+        let (k1, k2) = Self::check_parameters(key, associated_data)?;
+
+        Self::ctr(&k2, v, buffer)?;
+        if let Err(error) = verify_tag(&Self::s2v(&k1, associated_data, buffer), v) {
+            Self::ctr(&k2, v, buffer)?;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Nonce-based encryption: the components are `[aad, nonce]`, and the synthetic IV is the 16-byte tag.
+    pub fn encrypt(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8], ciphertext: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if tag.len() != 16 {
+            return Err(AeadError::InvalidTagLength);
+        }
+        let v = Self::encrypt_with_components(key, &[aad, nonce], plaintext, ciphertext)?;
+        tag.copy_from_slice(&v);
+        Ok(())
+    }
+
+    /// Nonce-based decryption (see [SIV::encrypt]).
+    pub fn decrypt(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8], plaintext: &mut [u8]) -> Result<(), AeadError> {
+        let v: [u8; 16] = tag.try_into().map_err(|_| AeadError::InvalidTagLength)?;
+        Self::decrypt_with_components(key, &[aad, nonce], ciphertext, &v, plaintext)
+    }
+
+    /// Nonce-based encryption in place (see [SIV::encrypt]).
+    pub fn encrypt_in_place(key: &[u8], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &mut [u8]) -> Result<(), AeadError> {
+        if tag.len() != 16 {
+            return Err(AeadError::InvalidTagLength);
+        }
+        let v = Self::encrypt_with_components_in_place(key, &[aad, nonce], buffer)?;
+        tag.copy_from_slice(&v);
+        Ok(())
+    }
+
+    /// Nonce-based decryption in place (see [SIV::decrypt_with_components_in_place]).
+    pub fn decrypt_in_place(key: &[u8], nonce: &[u8], aad: &[u8], buffer: &mut [u8], tag: &[u8]) -> Result<(), AeadError> {
+        let v: [u8; 16] = tag.try_into().map_err(|_| AeadError::InvalidTagLength)?;
+        Self::decrypt_with_components_in_place(key, &[aad, nonce], buffer, &v)
+    }
+}
+
+#[cfg(test)]
+mod tests_siv {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use super::*;
+
+    #[test]
+    fn siv_deterministic() {
+        // RFC 5297, A.1
+        let key = [
+            0xFF, 0xFE, 0xFD, 0xFC, 0xFB, 0xFA, 0xF9, 0xF8, 0xF7, 0xF6, 0xF5, 0xF4, 0xF3, 0xF2, 0xF1, 0xF0,
+            0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF,
+        ];
+        let ad = [
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+        ];
+        let plain = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE];
+        let expected_v = [0x85, 0x63, 0x2D, 0x07, 0xC6, 0xE8, 0xF3, 0x7F, 0x95, 0x0A, 0xCD, 0x32, 0x0A, 0x2E, 0xCC, 0x93];
+        let expected_c = [0x40, 0xC0, 0x2B, 0x96, 0x90, 0xC4, 0xDC, 0x04, 0xDA, 0xEF, 0x7F, 0x6A, 0xFE, 0x5C];
+
+        let mut ciphertext = [0; 14];
+        let v = SIV::<AES128>::encrypt_with_components(&key, &[&ad], &plain, &mut ciphertext).expect("Error during SIV_AES128 execution");
+        assert_eq!(v, expected_v);
+        assert_eq!(ciphertext, expected_c);
+
+        let mut decrypted = [0; 14];
+        SIV::<AES128>::decrypt_with_components(&key, &[&ad], &ciphertext, &v, &mut decrypted).expect("Error during SIV_AES128 execution");
+        assert_eq!(decrypted, plain);
+
+        let res = SIV::<AES128>::decrypt_with_components(&key, &[&ad, &[]], &ciphertext, &v, &mut decrypted);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn siv_in_place() {
+        // RFC 5297, A.1
+        let key = [
+            0xFF, 0xFE, 0xFD, 0xFC, 0xFB, 0xFA, 0xF9, 0xF8, 0xF7, 0xF6, 0xF5, 0xF4, 0xF3, 0xF2, 0xF1, 0xF0,
+            0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF,
+        ];
+        let ad = [
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+        ];
+        let plain = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE];
+        let expected_v = [0x85, 0x63, 0x2D, 0x07, 0xC6, 0xE8, 0xF3, 0x7F, 0x95, 0x0A, 0xCD, 0x32, 0x0A, 0x2E, 0xCC, 0x93];
+        let expected_c = [0x40, 0xC0, 0x2B, 0x96, 0x90, 0xC4, 0xDC, 0x04, 0xDA, 0xEF, 0x7F, 0x6A, 0xFE, 0x5C];
+
+        let mut buffer = plain;
+        let v = SIV::<AES128>::encrypt_with_components_in_place(&key, &[&ad], &mut buffer).expect("Error during SIV_AES128 execution");
+        assert_eq!(v, expected_v);
+        assert_eq!(buffer, expected_c);
+
+        // the ciphertext is restored when the synthetic IV is not valid
+        let res = SIV::<AES128>::decrypt_with_components_in_place(&key, &[], &mut buffer, &v);
+        assert_eq!(res, Err(AeadError::AuthenticationFailed));
+        assert_eq!(buffer, expected_c);
+
+        SIV::<AES128>::decrypt_with_components_in_place(&key, &[&ad], &mut buffer, &v).expect("Error during SIV_AES128 execution");
+        assert_eq!(buffer, plain);
+    }
+
+    #[test]
+    fn siv_nonce_based() {
+        // RFC 5297, A.2
+        let key = [
+            0x7F, 0x7E, 0x7D, 0x7C, 0x7B, 0x7A, 0x79, 0x78, 0x77, 0x76, 0x75, 0x74, 0x73, 0x72, 0x71, 0x70,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F,
+        ];
+        let ad1 = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF,
+            0xDE, 0xAD, 0xDA, 0xDA, 0xDE, 0xAD, 0xDA, 0xDA, 0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA, 0x99, 0x88,
+            0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00,
+        ];
+        let ad2 = [0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0xA0];
+        let nonce = [0x09, 0xF9, 0x11, 0x02, 0x9D, 0x74, 0xE3, 0x5B, 0xD8, 0x41, 0x56, 0xC5, 0x63, 0x56, 0x88, 0xC0];
+        let plain = b"this is some plaintext to encrypt using SIV-AES";
+        let expected_v = [0x7B, 0xDB, 0x6E, 0x3B, 0x43, 0x26, 0x67, 0xEB, 0x06, 0xF4, 0xD1, 0x4B, 0xFF, 0x2F, 0xBD, 0x0F];
+        let expected_c = [
+            0xCB, 0x90, 0x0F, 0x2F, 0xDD, 0xBE, 0x40, 0x43, 0x26, 0x60, 0x19, 0x65, 0xC8, 0x89, 0xBF, 0x17,
+            0xDB, 0xA7, 0x7C, 0xEB, 0x09, 0x4F, 0xA6, 0x63, 0xB7, 0xA3, 0xF7, 0x48, 0xBA, 0x8A, 0xF8, 0x29,
+            0xEA, 0x64, 0xAD, 0x54, 0x4A, 0x27, 0x2E, 0x9C, 0x48, 0x5B, 0x62, 0xA3, 0xFD, 0x5C, 0x0D,
+        ];
+
+        let mut ciphertext = [0; 47];
+        let v = SIV::<AES128>::encrypt_with_components(&key, &[&ad1, &ad2, &nonce], plain, &mut ciphertext).expect("Error during SIV_AES128 execution");
+        assert_eq!(v, expected_v);
+        assert_eq!(ciphertext, expected_c);
+
+        let mut decrypted = [0; 47];
+        SIV::<AES128>::decrypt_with_components(&key, &[&ad1, &ad2, &nonce], &ciphertext, &v, &mut decrypted).expect("Error during SIV_AES128 execution");
+        assert_eq!(&decrypted, plain);
+    }
+}
@@ -0,0 +1,210 @@
+//! Streaming encryption and decryption with the chaining modes implementing
+//! [StreamingBlockChaining] (ECB and CBC).
+//!
+//! Data can be given in chunks of any length: partial blocks are kept in an internal buffer until
+//! the next call to `update`. `finalize` treats the remaining data, and `finalize_padded` adds (or
+//! removes) the padding (see [crate::symmetric::block_ciphers_modes::padding]).
+//!
+//! The decryptor always keeps the last full block until `finalize`, as it may contain padding.
+//!
+//! # Example
+//!
+//! ```
+//! use crypto_lib::symmetric::block_ciphers::aes::AES128;
+//! use crypto_lib::symmetric::block_ciphers_modes::cbc::CBC;
+//! use crypto_lib::symmetric::block_ciphers_modes::padding::PKCS7;
+//! use crypto_lib::symmetric::block_ciphers_modes::streaming::{BlockDecryptor, BlockEncryptor};
+//!
+//! let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+//! let iv = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+//!
+//! let mut encryptor = BlockEncryptor::<AES128, CBC>::new(&key, iv);
+//! let mut ciphertext = encryptor.update(b"Some data, ").unwrap();
+//! ciphertext.extend(encryptor.update(b"received in several chunks").unwrap());
+//! ciphertext.extend(encryptor.finalize_padded::<PKCS7>().unwrap());
+//!
+//! let mut decryptor = BlockDecryptor::<AES128, CBC>::new(&key, iv);
+//! let mut plain = decryptor.update(&ciphertext).unwrap();
+//! plain.extend(decryptor.finalize_padded::<PKCS7>().unwrap());
+//!
+//! assert_eq!(plain, b"Some data, received in several chunks");
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::symmetric::block_ciphers_modes::common::StreamingBlockChaining;
+use crate::symmetric::block_ciphers_modes::padding::Padding;
+
+/// Streaming encryptor, with the block cipher `T` and the chaining mode `M`.
+pub struct BlockEncryptor<T: BlockCipher, M: StreamingBlockChaining<T>> {
+    key: T::KeyType,
+    state: M::State,
+    buffer: Vec<u8>,  // partial block
+    mode: PhantomData<M>
+}
+
+impl<T, M, const NK: usize, const NB: usize> BlockEncryptor<T, M>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+    M: StreamingBlockChaining<T>,
+{
+    /// `state` is the initial state of the mode (e.g. the IV for CBC, `()` for ECB).
+    pub fn new(key: &[u8; NK], state: M::State) -> Self {
+        BlockEncryptor { key: *key, state, buffer: Vec::with_capacity(NB), mode: PhantomData }
+    }
+
+    /// Returns the ciphertext of all the full blocks available.
+    pub fn update(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        self.buffer.extend_from_slice(plaintext);
+        let full_len = self.buffer.len() - self.buffer.len() % NB;
+        let mut ciphertext: Vec<u8> = self.buffer.drain(..full_len).collect();
+        M::cipher_in_place(&mut ciphertext, &self.key, &mut self.state)?;
+        Ok(ciphertext)
+    }
+
+    /// Fails if the total length of the plaintext is not a multiple of the block size.
+    pub fn finalize(self) -> Result<Vec<u8>, &'static str> {
+        if !self.buffer.is_empty() {
+            return Err("Length of plaintext is not a multiple of block size");
+        }
+        Ok(Vec::new())
+    }
+
+    /// Pads the end of the plaintext with `P`, and returns the last ciphertext blocks.
+    pub fn finalize_padded<P: Padding>(mut self) -> Result<Vec<u8>, &'static str> {
+        let mut ciphertext = P::pad(&self.buffer, NB)?;
+        M::cipher_in_place(&mut ciphertext, &self.key, &mut self.state)?;
+        Ok(ciphertext)
+    }
+}
+
+/// Streaming decryptor, with the block cipher `T` and the chaining mode `M`.
+pub struct BlockDecryptor<T: BlockCipher, M: StreamingBlockChaining<T>> {
+    key: T::KeyType,
+    state: M::State,
+    buffer: Vec<u8>,  // last full block and partial block
+    mode: PhantomData<M>
+}
+
+impl<T, M, const NK: usize, const NB: usize> BlockDecryptor<T, M>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; NB]>,
+    M: StreamingBlockChaining<T>,
+{
+    /// `state` is the initial state of the mode (e.g. the IV for CBC, `()` for ECB).
+    pub fn new(key: &[u8; NK], state: M::State) -> Self {
+        BlockDecryptor { key: *key, state, buffer: Vec::with_capacity(2 * NB), mode: PhantomData }
+    }
+
+    /// Returns the plaintext of the full blocks available, except the last one.
+    pub fn update(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, &'static str> {
+        self.buffer.extend_from_slice(ciphertext);
+        let len = self.buffer.len().saturating_sub(1) / NB * NB;
+        let mut plaintext: Vec<u8> = self.buffer.drain(..len).collect();
+        M::decipher_in_place(&mut plaintext, &self.key, &mut self.state)?;
+        Ok(plaintext)
+    }
+
+    /// Returns the last plaintext block. Fails if the total length of the ciphertext is not a
+    /// multiple of the block size.
+    pub fn finalize(mut self) -> Result<Vec<u8>, &'static str> {
+        if !self.buffer.len().is_multiple_of(NB) {
+            return Err("Length of ciphertext is not a multiple of block size");
+        }
+        M::decipher_in_place(&mut self.buffer, &self.key, &mut self.state)?;
+        Ok(self.buffer)
+    }
+
+    /// Returns the last plaintext block, without the padding `P` (checked in constant time).
+    pub fn finalize_padded<P: Padding>(self) -> Result<Vec<u8>, &'static str> {
+        let padded = self.finalize()?;
+        Ok(P::unpad(&padded, NB)?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests_streaming {
+    use crate::symmetric::block_ciphers::aes::AES128;
+    use crate::symmetric::block_ciphers_modes::cbc::CBC;
+    use crate::symmetric::block_ciphers_modes::common::BlockChaining;
+    use crate::symmetric::block_ciphers_modes::ecb::ECB;
+    use crate::symmetric::block_ciphers_modes::padding::PKCS7;
+    use super::*;
+
+    const KEY: [u8; 16] = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+    const IV: [u8; 16] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+
+    // SP 800-38A, F.2.1 (the first 40 bytes)
+    const PLAIN: [u8; 40] = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+        0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF, 0x8E, 0x51,
+        0x30, 0xC8, 0x1C, 0x46, 0xA3, 0x5C, 0xE4, 0x11,
+    ];
+    const CIPHER_PKCS7: [u8; 48] = [
+        0x76, 0x49, 0xAB, 0xAC, 0x81, 0x19, 0xB2, 0x46, 0xCE, 0xE9, 0x8E, 0x9B, 0x12, 0xE9, 0x19, 0x7D,
+        0x50, 0x86, 0xCB, 0x9B, 0x50, 0x72, 0x19, 0xEE, 0x95, 0xDB, 0x11, 0x3A, 0x91, 0x76, 0x78, 0xB2,
+        0x5C, 0xAC, 0xA7, 0x61, 0x45, 0xCF, 0xFC, 0x65, 0xF7, 0x48, 0x36, 0x08, 0xC5, 0x31, 0xAE, 0xF1,
+    ];
+
+    #[test]
+    fn cbc_streaming_chunks() {
+        for chunk_size in [1, 7, 16, 17, 40] {
+            let mut encryptor = BlockEncryptor::<AES128, CBC>::new(&KEY, IV);
+            let mut ciphertext = Vec::new();
+            for chunk in PLAIN.chunks(chunk_size) {
+                ciphertext.extend(encryptor.update(chunk).expect("Error during CBC_AES128 execution"));
+            }
+            ciphertext.extend(encryptor.finalize_padded::<PKCS7>().expect("Error during CBC_AES128 execution"));
+            assert_eq!(ciphertext, CIPHER_PKCS7);
+
+            let mut decryptor = BlockDecryptor::<AES128, CBC>::new(&KEY, IV);
+            let mut plain = Vec::new();
+            for chunk in CIPHER_PKCS7.chunks(chunk_size) {
+                plain.extend(decryptor.update(chunk).expect("Error during CBC_AES128 execution"));
+            }
+            plain.extend(decryptor.finalize_padded::<PKCS7>().expect("Error during CBC_AES128 execution"));
+            assert_eq!(plain, PLAIN);
+        }
+    }
+
+    #[test]
+    fn ecb_streaming_no_padding() {
+        let mut expected = [0; 32];
+        <ECB as BlockChaining<AES128>>::cipher(&PLAIN[..32], &mut expected, &KEY).unwrap();
+
+        let mut encryptor = BlockEncryptor::<AES128, ECB>::new(&KEY, ());
+        let mut ciphertext = encryptor.update(&PLAIN[..20]).expect("Error during ECB_AES128 execution");
+        assert_eq!(ciphertext.len(), 16);
+        ciphertext.extend(encryptor.update(&PLAIN[20..32]).expect("Error during ECB_AES128 execution"));
+        ciphertext.extend(encryptor.finalize().expect("Error during ECB_AES128 execution"));
+        assert_eq!(ciphertext, expected);
+
+        let mut decryptor = BlockDecryptor::<AES128, ECB>::new(&KEY, ());
+        let mut plain = decryptor.update(&ciphertext).expect("Error during ECB_AES128 execution");
+        assert_eq!(plain.len(), 16);
+        plain.extend(decryptor.finalize().expect("Error during ECB_AES128 execution"));
+        assert_eq!(plain, PLAIN[..32]);
+
+        // partial block left
+        let mut encryptor = BlockEncryptor::<AES128, ECB>::new(&KEY, ());
+        encryptor.update(&PLAIN).expect("Error during ECB_AES128 execution");
+        assert!(encryptor.finalize().is_err());
+    }
+
+    #[test]
+    fn cbc_in_place() {
+        let mut data = CIPHER_PKCS7;
+        let mut state = IV;
+        <CBC as StreamingBlockChaining<AES128>>::decipher_in_place(&mut data[..16], &KEY, &mut state).expect("Error during CBC_AES128 execution");
+        <CBC as StreamingBlockChaining<AES128>>::decipher_in_place(&mut data[16..], &KEY, &mut state).expect("Error during CBC_AES128 execution");
+        assert_eq!(data[..40], PLAIN);
+        assert_eq!(state, CIPHER_PKCS7[32..]);
+
+        let mut state = IV;
+        <CBC as StreamingBlockChaining<AES128>>::cipher_in_place(&mut data, &KEY, &mut state).expect("Error during CBC_AES128 execution");
+        assert_eq!(data, CIPHER_PKCS7);
+
+        assert!(<CBC as StreamingBlockChaining<AES128>>::cipher_in_place(&mut data[..15], &KEY, &mut state).is_err());
+    }
+}
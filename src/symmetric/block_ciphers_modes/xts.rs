@@ -0,0 +1,237 @@
+//! Definition of XTS mode (cf. IEEE 1619 and NIST SP 800-38E), used for disk encryption.
+//!
+//! Each data unit (usually a disk sector) is encrypted on its own: its number `i` gives the tweak.
+//!
+//! Encryption of the j-th block of a data unit:
+//!```text
+//!   T0 = E_K2(i)             (i encoded on 16 bytes, little endian)
+//!   Tj = T0 * alpha^j        (in GF(2^128))
+//!
+//!          Pj
+//!          |
+//!   Tj --- +
+//!          |
+//!         ----
+//!        | K1 |
+//!         ----
+//!          |
+//!   Tj --- +
+//!          |
+//!          Cj
+//! ```
+//!
+//! The key is `K1 || K2`, the two halves must be different.
+//! When the length of a data unit is not a multiple of 16 bytes, ciphertext stealing is used for the
+//! last 2 blocks. A data unit is at least 16 bytes long.
+
+use crate::symmetric::block_ciphers::common::BlockCipher;
+use crate::utils::{constant_time_eq, slice_to_arrays, xor_arrays};
+
+// multiplication by alpha (x) in GF(2^128), little endian representation
+fn mul_alpha(tweak: &[u8; 16]) -> [u8; 16] {
+    let mut res = [0; 16];
+    let mut carry = 0;
+    for i in 0..16 {
+        res[i] = (tweak[i] << 1) | carry;
+        carry = tweak[i] >> 7;
+    }
+    res[0] ^= 0x87 * carry;
+    res
+}
+
+/// XTS mode over the 128-bit block cipher `T` (e.g. XTS-AES-128 with `XTS<AES128>`).
+pub struct XTS<T>(std::marker::PhantomData<T>);
+
+impl<T, const NK: usize> XTS<T>
+where
+    T: BlockCipher<KeyType = [u8; NK], BlockType = [u8; 16]>,
+{
+    // checks the parameters, and returns K1 and the tweaks of all the blocks (including a partial last block)
+    fn prepare(key: &[u8], data_unit: u128, input: &[u8], output: &[u8]) -> Result<([u8; NK], Vec<[u8; 16]>), &'static str> {
+        if key.len() != 2 * NK {
+            return Err("Key length must be twice the key length of the block cipher");
+        }
+        if constant_time_eq(&key[..NK], &key[NK..]) {
+            return Err("The 2 halves of the key must be different");
+        }
+        if input.len() < 16 {
+            return Err("Data unit must be at least 16 bytes long");
+        }
+        if input.len() != output.len() {
+            return Err("Input and output must have the same length");
+        }
+
+        let key1: [u8; NK] = key[..NK].try_into().unwrap();
+        let key2: [u8; NK] = key[NK..].try_into().unwrap();
+
+        let mut tweak = [0; 16];
+        T::cipher(&data_unit.to_le_bytes(), &mut tweak, &key2)?;
+        let mut tweaks = Vec::with_capacity(input.len().div_ceil(16));
+        for _ in 0..input.len().div_ceil(16) {
+            tweaks.push(tweak);
+            tweak = mul_alpha(&tweak);
+        }
+
+        Ok((key1, tweaks))
+    }
+
+    // ECB on the whitened blocks: the blocks are independent, so they are treated all at once
+    fn process_blocks(blocks: &mut [[u8; 16]], tweaks: &[[u8; 16]], key: &[u8; NK], decrypt: bool) -> Result<(), &'static str> {
+        for (block, tweak) in blocks.iter_mut().zip(tweaks) {
+            *block = xor_arrays(block, tweak);
+        }
+        if decrypt {
+            T::decipher_blocks(blocks, key)?;
+        } else {
+            T::cipher_blocks(blocks, key)?;
+        }
+        for (block, tweak) in blocks.iter_mut().zip(tweaks) {
+            *block = xor_arrays(block, tweak);
+        }
+        Ok(())
+    }
+
+    /// Encrypts the data unit number `data_unit`. `key` is `K1 || K2`.
+    pub fn encrypt(key: &[u8], data_unit: u128, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        let (key1, tweaks) = Self::prepare(key, data_unit, plaintext, ciphertext)?;
+        let full_len = plaintext.len() - plaintext.len() % 16;
+        let partial_len = plaintext.len() % 16;
+
+        let mut blocks = slice_to_arrays::<16, u8>(&plaintext[..full_len]);
+        Self::process_blocks(&mut blocks, &tweaks, &key1, false)?;
+        for (i, block) in blocks.iter().enumerate() {
+            ciphertext[16 * i..16 * (i + 1)].copy_from_slice(block);
+        }
+
+        // ciphertext stealing: the last full block is completed with the end of the previous ciphertext block
+        if partial_len > 0 {
+            let last_full = *blocks.last().unwrap();
+            let mut block = last_full;
+            block[..partial_len].copy_from_slice(&plaintext[full_len..]);
+            Self::process_blocks(std::slice::from_mut(&mut block), &tweaks[tweaks.len() - 1..], &key1, false)?;
+            ciphertext[full_len - 16..full_len].copy_from_slice(&block);
+            ciphertext[full_len..].copy_from_slice(&last_full[..partial_len]);
+        }
+
+        Ok(())
+    }
+
+    /// Decrypts the data unit number `data_unit`. `key` is `K1 || K2`.
+    pub fn decrypt(key: &[u8], data_unit: u128, ciphertext: &[u8], plaintext: &mut [u8]) -> Result<(), &'static str> {
+        let (key1, mut tweaks) = Self::prepare(key, data_unit, ciphertext, plaintext)?;
+        let full_len = ciphertext.len() - ciphertext.len() % 16;
+        let partial_len = ciphertext.len() % 16;
+
+        // with ciphertext stealing, the last full block was encrypted with the last tweak
+        if partial_len > 0 {
+            let last = tweaks.len() - 1;
+            tweaks.swap(last - 1, last);
+        }
+
+        let mut blocks = slice_to_arrays::<16, u8>(&ciphertext[..full_len]);
+        Self::process_blocks(&mut blocks, &tweaks, &key1, true)?;
+        for (i, block) in blocks.iter().enumerate() {
+            plaintext[16 * i..16 * (i + 1)].copy_from_slice(block);
+        }
+
+        if partial_len > 0 {
+            let last_full = *blocks.last().unwrap();
+            let mut block = last_full;
+            block[..partial_len].copy_from_slice(&ciphertext[full_len..]);
+            Self::process_blocks(std::slice::from_mut(&mut block), &tweaks[tweaks.len() - 1..], &key1, true)?;
+            plaintext[full_len - 16..full_len].copy_from_slice(&block);
+            plaintext[full_len..].copy_from_slice(&last_full[..partial_len]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_xts {
+    use crate::symmetric::block_ciphers::aes::{AES128, AES256};
+    use super::*;
+
+    const KEY_STEALING: [u8; 32] = [
+        0xFF, 0xFE, 0xFD, 0xFC, 0xFB, 0xFA, 0xF9, 0xF8, 0xF7, 0xF6, 0xF5, 0xF4, 0xF3, 0xF2, 0xF1, 0xF0,
+        0xBF, 0xBE, 0xBD, 0xBC, 0xBB, 0xBA, 0xB9, 0xB8, 0xB7, 0xB6, 0xB5, 0xB4, 0xB3, 0xB2, 0xB1, 0xB0,
+    ];
+
+    #[test]
+    fn xts_aes128_encrypt_decrypt() {
+        // IEEE 1619, vector 2
+        let key = [[0x11; 16], [0x22; 16]].concat();
+        let plain = [0x44; 32];
+        let expected = [
+            0xC4, 0x54, 0x18, 0x5E, 0x6A, 0x16, 0x93, 0x6E, 0x39, 0x33, 0x40, 0x38, 0xAC, 0xEF, 0x83, 0x8B,
+            0xFB, 0x18, 0x6F, 0xFF, 0x74, 0x80, 0xAD, 0xC4, 0x28, 0x93, 0x82, 0xEC, 0xD6, 0xD3, 0x94, 0xF0,
+        ];
+        let mut ciphertext = [0; 32];
+        XTS::<AES128>::encrypt(&key, 0x3333333333, &plain, &mut ciphertext).expect("Error during XTS_AES128 execution");
+        assert_eq!(ciphertext, expected);
+
+        let mut decrypted = [0; 32];
+        XTS::<AES128>::decrypt(&key, 0x3333333333, &ciphertext, &mut decrypted).expect("Error during XTS_AES128 execution");
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn xts_aes128_ciphertext_stealing() {
+        let plain: [u8; 31] = core::array::from_fn(|i| i as u8);
+        let expected_17 = [
+            0x64, 0x16, 0x10, 0x67, 0x9D, 0xCB, 0xF9, 0x2E, 0x50, 0x5C, 0x41, 0x33, 0x3F, 0xB0, 0x6C, 0x2A,
+            0x95,
+        ];
+        let expected_31 = [
+            0xC0, 0x3F, 0x4C, 0x60, 0x88, 0xFC, 0xF1, 0x4C, 0x30, 0x8A, 0xA3, 0x9F, 0x79, 0x38, 0x98, 0x09,
+            0x95, 0xC8, 0x71, 0xF6, 0x52, 0x24, 0x69, 0xCC, 0x73, 0x71, 0x09, 0x59, 0x4A, 0xB0, 0xFE,
+        ];
+
+        let mut ciphertext = [0; 17];
+        XTS::<AES128>::encrypt(&KEY_STEALING, 0x9A78563412, &plain[..17], &mut ciphertext).expect("Error during XTS_AES128 execution");
+        assert_eq!(ciphertext, expected_17);
+        let mut decrypted = [0; 17];
+        XTS::<AES128>::decrypt(&KEY_STEALING, 0x9A78563412, &ciphertext, &mut decrypted).expect("Error during XTS_AES128 execution");
+        assert_eq!(decrypted, plain[..17]);
+
+        let mut ciphertext = [0; 31];
+        XTS::<AES128>::encrypt(&KEY_STEALING, 0x9A78563412, &plain, &mut ciphertext).expect("Error during XTS_AES128 execution");
+        assert_eq!(ciphertext, expected_31);
+        let mut decrypted = [0; 31];
+        XTS::<AES128>::decrypt(&KEY_STEALING, 0x9A78563412, &ciphertext, &mut decrypted).expect("Error during XTS_AES128 execution");
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn xts_aes256_sector() {
+        // IEEE 1619, vector 10 (first 32 bytes)
+        let key = [
+            0x27, 0x18, 0x28, 0x18, 0x28, 0x45, 0x90, 0x45, 0x23, 0x53, 0x60, 0x28, 0x74, 0x71, 0x35, 0x26,
+            0x62, 0x49, 0x77, 0x57, 0x24, 0x70, 0x93, 0x69, 0x99, 0x59, 0x57, 0x49, 0x66, 0x96, 0x76, 0x27,
+            0x31, 0x41, 0x59, 0x26, 0x53, 0x58, 0x97, 0x93, 0x23, 0x84, 0x62, 0x64, 0x33, 0x83, 0x27, 0x95,
+            0x02, 0x88, 0x41, 0x97, 0x16, 0x93, 0x99, 0x37, 0x51, 0x05, 0x82, 0x09, 0x74, 0x94, 0x45, 0x92,
+        ];
+        let plain: Vec<u8> = (0..512).map(|i| i as u8).collect();
+        let expected = [
+            0x1C, 0x3B, 0x3A, 0x10, 0x2F, 0x77, 0x03, 0x86, 0xE4, 0x83, 0x6C, 0x99, 0xE3, 0x70, 0xCF, 0x9B,
+            0xEA, 0x00, 0x80, 0x3F, 0x5E, 0x48, 0x23, 0x57, 0xA4, 0xAE, 0x12, 0xD4, 0x14, 0xA3, 0xE6, 0x3B,
+        ];
+        let mut ciphertext = [0; 512];
+        XTS::<AES256>::encrypt(&key, 0xFF, &plain, &mut ciphertext).expect("Error during XTS_AES256 execution");
+        assert_eq!(ciphertext[..32], expected);
+
+        let mut decrypted = [0; 512];
+        XTS::<AES256>::decrypt(&key, 0xFF, &ciphertext, &mut decrypted).expect("Error during XTS_AES256 execution");
+        assert_eq!(decrypted.to_vec(), plain);
+    }
+
+    #[test]
+    fn xts_bad_parameters() {
+        let mut ciphertext = [0; 32];
+        let same_halves = [0x11; 32];
+        assert!(XTS::<AES128>::encrypt(&same_halves, 0, &[0; 32], &mut ciphertext).is_err());
+        assert!(XTS::<AES128>::encrypt(&KEY_STEALING[..16], 0, &[0; 32], &mut ciphertext).is_err());
+        assert!(XTS::<AES128>::encrypt(&KEY_STEALING, 0, &[0; 15], &mut ciphertext[..15]).is_err());
+        assert!(XTS::<AES128>::decrypt(&KEY_STEALING, 0, &[0; 32], &mut ciphertext[..31]).is_err());
+    }
+}
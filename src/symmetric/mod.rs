@@ -4,8 +4,10 @@
 //! - [block ciphers](block_ciphers)
 //! - [stream ciphers](stream_ciphers)
 //!
-//! Also, chaining algorithms are defined in [block_ciphers_modes].
+//! Also, chaining algorithms are defined in [block_ciphers_modes], and authenticated encryption
+//! algorithms share a common interface in [aead].
 
+pub mod aead;
 pub mod block_ciphers;
 pub mod block_ciphers_modes;
 pub mod stream_ciphers;
\ No newline at end of file
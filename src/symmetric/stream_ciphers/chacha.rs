@@ -0,0 +1,400 @@
+//! Definition of ChaCha20, 12 and 8 algorithms (32-bytes key), HChaCha20/HChaCha12 and XChaCha20/XChaCha12.
+
+// https://cr.yp.to/chacha/chacha-20080128.pdf
+// RFC 8439 (96-bit nonce, 32-bit counter)
+// draft-irtf-cfrg-xchacha (HChaCha20, XChaCha20)
+
+use crate::symmetric::stream_ciphers::common::StreamCipher;
+
+const SIG: [u32; 4] = [0x61707865, 0x3320646E, 0x79622D32, 0x6B206574];
+
+pub type ChaChaState = [u32; 16];
+
+/// Initialisation of the ChaCha variants from RFC 8439: 96-bit nonce, 32-bit block counter.
+pub struct ChaChaInitStruct {
+    pub nonce: [u8; 12],
+    pub counter: u32
+}
+
+/// Initialisation of the original ChaCha variants: 64-bit nonce, 64-bit block counter.
+pub struct ChaChaOriginalInitStruct {
+    pub nonce: [u8; 8],
+    pub counter: u64
+}
+
+/// Initialisation of XChaCha20 and XChaCha12: 192-bit nonce, 32-bit block counter.
+pub struct XChaChaInitStruct {
+    pub nonce: [u8; 24],
+    pub counter: u32
+}
+
+fn words_from_le_bytes<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    core::array::from_fn(|i| u32::from_le_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+fn initial_state(key: &[u8; 32], last_words: [u32; 4]) -> ChaChaState {
+    let k: [u32; 8] = words_from_le_bytes(key);
+    [
+        SIG[0], SIG[1], SIG[2], SIG[3],
+        k[0], k[1], k[2], k[3],
+        k[4], k[5], k[6], k[7],
+        last_words[0], last_words[1], last_words[2], last_words[3]
+    ]
+}
+
+// returns the state, the number of words used by the counter and the number of blocks left
+fn ietf_state(init_struct: &ChaChaInitStruct, key: &[u8; 32]) -> (ChaChaState, usize, u128) {
+    let n: [u32; 3] = words_from_le_bytes(&init_struct.nonce);
+    let state = initial_state(key, [init_struct.counter, n[0], n[1], n[2]]);
+    (state, 1, (1 << 32) - init_struct.counter as u128)
+}
+
+fn original_state(init_struct: &ChaChaOriginalInitStruct, key: &[u8; 32]) -> (ChaChaState, usize, u128) {
+    let n: [u32; 2] = words_from_le_bytes(&init_struct.nonce);
+    let counter = init_struct.counter;
+    let state = initial_state(key, [counter as u32, (counter >> 32) as u32, n[0], n[1]]);
+    (state, 2, (1 << 64) - counter as u128)
+}
+
+fn xchacha_state(init_struct: &XChaChaInitStruct, key: &[u8; 32]) -> (ChaChaState, usize, u128) {
+    let subkey = hchacha20(key, init_struct.nonce[..16].try_into().unwrap());
+    subkey_state(init_struct, &subkey)
+}
+
+fn xchacha12_state(init_struct: &XChaChaInitStruct, key: &[u8; 32]) -> (ChaChaState, usize, u128) {
+    let subkey = hchacha12(key, init_struct.nonce[..16].try_into().unwrap());
+    subkey_state(init_struct, &subkey)
+}
+
+// the last 8 bytes of the nonce are used with the subkey
+fn subkey_state(init_struct: &XChaChaInitStruct, subkey: &[u8; 32]) -> (ChaChaState, usize, u128) {
+    let mut nonce = [0; 12];
+    nonce[4..].copy_from_slice(&init_struct.nonce[16..]);
+    ietf_state(&ChaChaInitStruct { nonce, counter: init_struct.counter }, subkey)
+}
+
+/// HChaCha20: derives a 32-byte subkey from a key and a 16-byte nonce.
+pub fn hchacha20(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    hchacha(key, nonce, 10)
+}
+
+/// HChaCha12: same as [hchacha20] with 12 rounds (used by XChaCha12).
+pub fn hchacha12(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    hchacha(key, nonce, 6)
+}
+
+fn hchacha(key: &[u8; 32], nonce: &[u8; 16], nb_double_rounds: usize) -> [u8; 32] {
+    let state = initial_state(key, words_from_le_bytes(nonce));
+    let mixed = rounds(&state, nb_double_rounds);
+
+    let mut subkey = [0; 32];
+    for (i, word) in mixed[0..4].iter().chain(mixed[12..16].iter()).enumerate() {
+        subkey[4 * i..4 * i + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    subkey
+}
+
+// state shared by all the variants
+struct ChaChaCore {
+    state: ChaChaState,
+    nb_double_rounds: usize,
+    counter_words: usize,  // 1 (RFC 8439) or 2 (original)
+    blocks_left: u128,  // number of blocks before the counter wraps
+    keystream: [u8; 64],
+    remaining_bytes: usize  // number of bytes that can be ciphered using `keystream`
+}
+
+impl ChaChaCore {
+    fn new((state, counter_words, blocks_left): (ChaChaState, usize, u128), nb_double_rounds: usize) -> Self {
+        ChaChaCore { state, nb_double_rounds, counter_words, blocks_left, keystream: [0; 64], remaining_bytes: 0 }
+    }
+
+    fn next_block(&mut self) {
+        self.keystream = block_function(&self.state, self.nb_double_rounds);
+        self.remaining_bytes = 64;
+        self.blocks_left -= 1;
+
+        self.state[12] = self.state[12].wrapping_add(1);
+        if self.counter_words == 2 && self.state[12] == 0 {
+            self.state[13] = self.state[13].wrapping_add(1);
+        }
+    }
+
+    fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        // check parameters
+        if plaintext.len() != ciphertext.len() {
+            return Err("Plaintext and ciphertext buffers must have the same length");
+        }
+        self.check_available(plaintext.len())?;
+
+        ciphertext.copy_from_slice(plaintext);
+        self.cipher_in_place(ciphertext)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        self.check_available(data.len())?;
+
+        for byte in data.iter_mut() {
+            if self.remaining_bytes == 0 {
+                self.next_block();
+            }
+            *byte ^= self.keystream[64 - self.remaining_bytes];
+            self.remaining_bytes -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn check_available(&self, len: usize) -> Result<(), &'static str> {
+        if len as u128 > self.remaining_bytes as u128 + 64 * self.blocks_left {
+            return Err("Keystream is exhausted");
+        }
+        Ok(())
+    }
+}
+
+macro_rules! define_chacha {
+    ( $chacha_name:ident, $nb_double_rounds:expr, $init_struct:ty, $init_state:ident) => {
+        pub struct $chacha_name {
+            core: ChaChaCore
+        }
+
+        impl StreamCipher for $chacha_name {
+            const KEY_SIZE: usize = 32;
+            type KeyType = [u8; 32];
+            type InitStruct = $init_struct;
+            type State = ChaChaState;
+
+            fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+                $chacha_name { core: ChaChaCore::new($init_state(init_struct, key), $nb_double_rounds) }
+            }
+
+            fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+                self.core.cipher(plaintext, ciphertext)
+            }
+
+            fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+                self.core.cipher_in_place(data)
+            }
+        }
+    }
+}
+
+define_chacha!(ChaCha20, 10, ChaChaInitStruct, ietf_state);
+define_chacha!(ChaCha12, 6, ChaChaInitStruct, ietf_state);
+define_chacha!(ChaCha8, 4, ChaChaInitStruct, ietf_state);
+define_chacha!(ChaCha20Original, 10, ChaChaOriginalInitStruct, original_state);
+define_chacha!(ChaCha12Original, 6, ChaChaOriginalInitStruct, original_state);
+define_chacha!(ChaCha8Original, 4, ChaChaOriginalInitStruct, original_state);
+define_chacha!(XChaCha20, 10, XChaChaInitStruct, xchacha_state);
+define_chacha!(XChaCha12, 6, XChaChaInitStruct, xchacha12_state);
+
+#[inline]
+fn quarter_round(state: &mut ChaChaState, a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+#[inline]
+fn double_round(state: &mut ChaChaState) {
+    // columns
+    quarter_round(state, 0, 4, 8, 12);
+    quarter_round(state, 1, 5, 9, 13);
+    quarter_round(state, 2, 6, 10, 14);
+    quarter_round(state, 3, 7, 11, 15);
+    // diagonals
+    quarter_round(state, 0, 5, 10, 15);
+    quarter_round(state, 1, 6, 11, 12);
+    quarter_round(state, 2, 7, 8, 13);
+    quarter_round(state, 3, 4, 9, 14);
+}
+
+fn rounds(state: &ChaChaState, nb_double_rounds: usize) -> ChaChaState {
+    let mut tmp = *state;
+    for _ in 0..nb_double_rounds {
+        double_round(&mut tmp);
+    }
+    tmp
+}
+
+fn block_function(state: &ChaChaState, nb_double_rounds: usize) -> [u8; 64] {
+    let mixed = rounds(state, nb_double_rounds);
+
+    let mut block = [0; 64];
+    for (i, (m, s)) in mixed.iter().zip(state.iter()).enumerate() {
+        block[4 * i..4 * i + 4].copy_from_slice(&m.wrapping_add(*s).to_le_bytes());
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests_chacha {
+    use super::*;
+
+    const KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+    ];
+
+    // "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it."
+    const PLAIN: [u8; 114] = [
+        0x4C, 0x61, 0x64, 0x69, 0x65, 0x73, 0x20, 0x61, 0x6E, 0x64, 0x20, 0x47, 0x65, 0x6E, 0x74, 0x6C,
+        0x65, 0x6D, 0x65, 0x6E, 0x20, 0x6F, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x6C, 0x61, 0x73,
+        0x73, 0x20, 0x6F, 0x66, 0x20, 0x27, 0x39, 0x39, 0x3A, 0x20, 0x49, 0x66, 0x20, 0x49, 0x20, 0x63,
+        0x6F, 0x75, 0x6C, 0x64, 0x20, 0x6F, 0x66, 0x66, 0x65, 0x72, 0x20, 0x79, 0x6F, 0x75, 0x20, 0x6F,
+        0x6E, 0x6C, 0x79, 0x20, 0x6F, 0x6E, 0x65, 0x20, 0x74, 0x69, 0x70, 0x20, 0x66, 0x6F, 0x72, 0x20,
+        0x74, 0x68, 0x65, 0x20, 0x66, 0x75, 0x74, 0x75, 0x72, 0x65, 0x2C, 0x20, 0x73, 0x75, 0x6E, 0x73,
+        0x63, 0x72, 0x65, 0x65, 0x6E, 0x20, 0x77, 0x6F, 0x75, 0x6C, 0x64, 0x20, 0x62, 0x65, 0x20, 0x69,
+        0x74, 0x2E,
+    ];
+
+    // RFC 8439, 2.4.2
+    const CIPHER: [u8; 114] = [
+        0x6E, 0x2E, 0x35, 0x9A, 0x25, 0x68, 0xF9, 0x80, 0x41, 0xBA, 0x07, 0x28, 0xDD, 0x0D, 0x69, 0x81,
+        0xE9, 0x7E, 0x7A, 0xEC, 0x1D, 0x43, 0x60, 0xC2, 0x0A, 0x27, 0xAF, 0xCC, 0xFD, 0x9F, 0xAE, 0x0B,
+        0xF9, 0x1B, 0x65, 0xC5, 0x52, 0x47, 0x33, 0xAB, 0x8F, 0x59, 0x3D, 0xAB, 0xCD, 0x62, 0xB3, 0x57,
+        0x16, 0x39, 0xD6, 0x24, 0xE6, 0x51, 0x52, 0xAB, 0x8F, 0x53, 0x0C, 0x35, 0x9F, 0x08, 0x61, 0xD8,
+        0x07, 0xCA, 0x0D, 0xBF, 0x50, 0x0D, 0x6A, 0x61, 0x56, 0xA3, 0x8E, 0x08, 0x8A, 0x22, 0xB6, 0x5E,
+        0x52, 0xBC, 0x51, 0x4D, 0x16, 0xCC, 0xF8, 0x06, 0x81, 0x8C, 0xE9, 0x1A, 0xB7, 0x79, 0x37, 0x36,
+        0x5A, 0xF9, 0x0B, 0xBF, 0x74, 0xA3, 0x5B, 0xE6, 0xB4, 0x0B, 0x8E, 0xED, 0xF2, 0x78, 0x5E, 0x42,
+        0x87, 0x4D,
+    ];
+
+    const INIT: ChaChaInitStruct = ChaChaInitStruct { nonce: [0, 0, 0, 0, 0, 0, 0, 0x4A, 0, 0, 0, 0], counter: 1 };
+
+    #[test]
+    fn test_quarter_round() {
+        // RFC 8439, 2.1.1
+        let mut state = [0; 16];
+        state[0..4].copy_from_slice(&[0x11111111, 0x01020304, 0x9B8D6F43, 0x01234567]);
+        quarter_round(&mut state, 0, 1, 2, 3);
+        assert_eq!(state[0..4], [0xEA2A92F4, 0xCB1CF8CE, 0x4581472E, 0x5881C4BB]);
+    }
+
+    #[test]
+    fn test_chacha20_encrypt() {
+        let mut ciphertext = [0; 114];
+        let mut chacha = ChaCha20::new(&INIT, &KEY);
+        chacha.cipher(&PLAIN, &mut ciphertext).expect("Error during ChaCha20 execution");
+        assert_eq!(ciphertext, CIPHER);
+    }
+
+    #[test]
+    fn test_chacha20_decrypt_fragmented() {
+        let mut plain = [0; 114];
+        let mut chacha = ChaCha20::new(&INIT, &KEY);
+        chacha.cipher(&CIPHER[0..10], &mut plain[0..10]).expect("Error during ChaCha20 execution");
+        chacha.cipher(&CIPHER[10..74], &mut plain[10..74]).expect("Error during ChaCha20 execution");
+        chacha.cipher(&CIPHER[74..114], &mut plain[74..114]).expect("Error during ChaCha20 execution");
+        assert_eq!(plain, PLAIN);
+    }
+
+    #[test]
+    fn test_chacha20_counter_exhausted() {
+        let init = ChaChaInitStruct { nonce: [0; 12], counter: 0xFFFFFFFF };
+        let mut keystream = [0; 65];
+        let mut chacha = ChaCha20::new(&init, &KEY);
+        assert!(chacha.cipher(&[0; 65], &mut keystream).is_err());
+        chacha.cipher(&[0; 64], &mut keystream[..64]).expect("Error during ChaCha20 execution");
+        assert!(chacha.cipher(&[0; 1], &mut keystream[..1]).is_err());
+    }
+
+    #[test]
+    fn test_chacha_original_reduced_rounds() {
+        // draft-strombergson-chacha-test-vectors, TC1 (all-zero key and IV)
+        let init = ChaChaOriginalInitStruct { nonce: [0; 8], counter: 0 };
+        let expected8 = [
+            0x3E, 0x00, 0xEF, 0x2F, 0x89, 0x5F, 0x40, 0xD6, 0x7F, 0x5B, 0xB8, 0xE8, 0x1F, 0x09, 0xA5, 0xA1,
+            0x2C, 0x84, 0x0E, 0xC3, 0xCE, 0x9A, 0x7F, 0x3B, 0x18, 0x1B, 0xE1, 0x88, 0xEF, 0x71, 0x1A, 0x1E,
+        ];
+        let expected12 = [
+            0x9B, 0xF4, 0x9A, 0x6A, 0x07, 0x55, 0xF9, 0x53, 0x81, 0x1F, 0xCE, 0x12, 0x5F, 0x26, 0x83, 0xD5,
+            0x04, 0x29, 0xC3, 0xBB, 0x49, 0xE0, 0x74, 0x14, 0x7E, 0x00, 0x89, 0xA5, 0x2E, 0xAE, 0x15, 0x5F,
+        ];
+
+        let mut keystream = [0; 32];
+        ChaCha8Original::new(&init, &[0; 32]).cipher(&[0; 32], &mut keystream).expect("Error during ChaCha8 execution");
+        assert_eq!(keystream, expected8);
+        ChaCha12Original::new(&init, &[0; 32]).cipher(&[0; 32], &mut keystream).expect("Error during ChaCha12 execution");
+        assert_eq!(keystream, expected12);
+
+        // with a 96-bit nonce, the counter word is the same for the first block
+        let init = ChaChaInitStruct { nonce: [0; 12], counter: 0 };
+        ChaCha8::new(&init, &[0; 32]).cipher(&[0; 32], &mut keystream).expect("Error during ChaCha8 execution");
+        assert_eq!(keystream, expected8);
+        ChaCha12::new(&init, &[0; 32]).cipher(&[0; 32], &mut keystream).expect("Error during ChaCha12 execution");
+        assert_eq!(keystream, expected12);
+    }
+
+    #[test]
+    fn test_chacha20_original_counter() {
+        let init = ChaChaOriginalInitStruct { nonce: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07], counter: 5 };
+        let expected = [
+            0x52, 0x0F, 0x48, 0x9B, 0x7E, 0xD8, 0xD2, 0x0A, 0xE3, 0xFD, 0x49, 0xE9, 0xE2, 0x59, 0xE4, 0x43,
+            0x97, 0x51, 0x4D, 0x61, 0x8C, 0x96, 0xC4, 0x84, 0x6B, 0xE3, 0xC6, 0x80, 0xBD, 0xC1, 0x1C, 0x71,
+            0xDC, 0xBB, 0xE2, 0x9C, 0xCF, 0x80, 0xD6, 0x2A, 0x09, 0x38, 0xFA, 0x54, 0x93, 0x91, 0xE6, 0xEA,
+            0x57, 0xEC, 0xBE, 0x26, 0x06, 0x79, 0x0E, 0xC1, 0x5D, 0x22, 0x24, 0xAE, 0x30, 0x7C, 0x14, 0x42,
+            0x26, 0xB7, 0xC4, 0xE8, 0xC2, 0xF9, 0x7D, 0x2A, 0x1D, 0x67, 0x85, 0x2D, 0x29, 0xBE, 0xBA, 0x11,
+            0x0E, 0xDD, 0x44, 0x51, 0x97, 0x01, 0x20, 0x62, 0xA3, 0x93, 0xA9, 0xC9, 0x28, 0x03, 0xAD, 0x3B,
+            0x4F, 0x31, 0xD7, 0xBC,
+        ];
+
+        let mut keystream = [0; 100];
+        let mut chacha = ChaCha20Original::new(&init, &KEY);
+        chacha.cipher(&[0; 30], &mut keystream[0..30]).expect("Error during ChaCha20 execution");
+        chacha.cipher(&[0; 70], &mut keystream[30..100]).expect("Error during ChaCha20 execution");
+        assert_eq!(keystream, expected);
+    }
+
+    #[test]
+    fn test_hchacha20() {
+        // draft-irtf-cfrg-xchacha, 2.2.1
+        let nonce = [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4A, 0x00, 0x00, 0x00, 0x00, 0x31, 0x41, 0x59, 0x27];
+        let expected = [
+            0x82, 0x41, 0x3B, 0x42, 0x27, 0xB2, 0x7B, 0xFE, 0xD3, 0x0E, 0x42, 0x50, 0x8A, 0x87, 0x7D, 0x73,
+            0xA0, 0xF9, 0xE4, 0xD5, 0x8A, 0x74, 0xA8, 0x53, 0xC1, 0x2E, 0xC4, 0x13, 0x26, 0xD3, 0xEC, 0xDC,
+        ];
+        assert_eq!(hchacha20(&KEY, &nonce), expected);
+    }
+
+    #[test]
+    fn test_xchacha20() {
+        // first bytes of the keystream of draft-irtf-cfrg-xchacha, A.3.1
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce: [u8; 24] = core::array::from_fn(|i| 0x40 + i as u8);
+        let expected = [0x7B, 0x19, 0x1F, 0x80, 0xF3, 0x61, 0xF0, 0x99, 0x09, 0x4F, 0x6F, 0x4B, 0x8F, 0xB9, 0x7D, 0xF8];
+
+        let mut keystream = [0; 16];
+        let mut xchacha = XChaCha20::new(&XChaChaInitStruct { nonce, counter: 0 }, &key);
+        xchacha.cipher(&[0; 16], &mut keystream).expect("Error during XChaCha20 execution");
+        assert_eq!(keystream, expected);
+    }
+
+    #[test]
+    fn test_xchacha12() {
+        // reference implementation of Adiantum
+        let nonce: [u8; 24] = core::array::from_fn(|i| i as u8);
+        let expected = [
+            0x60, 0xBD, 0xBC, 0xFB, 0x13, 0x47, 0x14, 0x92, 0xE5, 0x73, 0xE1, 0xEC, 0x83, 0x7F, 0x73, 0x9A,
+            0x6F, 0x5E, 0x41, 0xCF, 0x91, 0x6B, 0xDC, 0xD8, 0xE8, 0xA6, 0x82, 0x16, 0x6B, 0x51, 0xD4, 0xB2,
+        ];
+
+        let mut keystream = [0; 32];
+        let mut xchacha = XChaCha12::new(&XChaChaInitStruct { nonce, counter: 0 }, &KEY);
+        xchacha.cipher(&[0; 32], &mut keystream).expect("Error during XChaCha12 execution");
+        assert_eq!(keystream, expected);
+    }
+
+    #[test]
+    fn test_chacha20_bad_length() {
+        let mut ciphertext = [0; 10];
+        let mut chacha = ChaCha20::new(&INIT, &KEY);
+        assert!(chacha.cipher(&PLAIN[0..11], &mut ciphertext).is_err());
+    }
+}
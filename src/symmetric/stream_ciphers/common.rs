@@ -8,13 +8,14 @@
 /// - 2 types:
 ///     - `InitStruct`: structure used to initialise the state
 ///     - `State`: type of the cipher's state
-/// - 4 methods:
+/// - 3 methods:
 ///     - `new`: static method that returns an initialised instance of the stream cipher
 ///     - `cipher`: encrypts the `plaintext` and put the result in `ciphertext`
+///     - `cipher_in_place`: encrypts `data` and overwrites it with the result
 ///
 /// Inputs can be of any length (but the two `plaintext` and `ciphertext` buffers must have the same length).
 /// Multiple calls to `cipher` can be done to treat the data as it was a single continuous block
-/// (i.e. conceptually, `cipher(a, b) == cipher(a), cipher(b)`).
+/// (i.e. conceptually, `cipher(a, b) == cipher(a), cipher(b)`). Calls to `cipher` and `cipher_in_place` can be mixed.
 pub trait StreamCipher {
     const KEY_SIZE: usize;
     
@@ -24,4 +25,5 @@ pub trait StreamCipher {
 
     fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self;
     fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str>;
-}
\ No newline at end of file
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str>;
+}
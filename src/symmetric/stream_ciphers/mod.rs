@@ -1,7 +1,12 @@
 //! Stream ciphers.
 //!
 //! Currently, these ciphers are implemented:
-//! - Salsa20/Salsa12/Salsa8 (32 and 16 bytes key)
+//! - Salsa20/Salsa12/Salsa8 (32 and 16 bytes key) and XSalsa20
+//! - ChaCha20/ChaCha12/ChaCha8 (96-bit nonce as in RFC 8439, or original 64-bit nonce), XChaCha20 and
+//!   XChaCha12
+//!
+//! Block ciphers used in CTR mode also implement the stream cipher interface
+//! (see [crate::symmetric::block_ciphers_modes::ctr]).
 //!
 //! Each cipher exposes the `cipher` method to treat some data (see [common::StreamCipher] for more details).
 //!
@@ -74,4 +79,5 @@
 //! ```
 
 pub mod common;
-pub mod salsa;
\ No newline at end of file
+pub mod salsa;
+pub mod chacha;
\ No newline at end of file
@@ -1,9 +1,9 @@
-//! Definition of Salsa20, 12 and 8 algorithms (32 and 16-bytes key).
+//! Definition of Salsa20, 12 and 8 algorithms (32 and 16-bytes key), HSalsa20 and XSalsa20.
 
 // https://cr.yp.to/snuffle/spec.pdf
+// https://cr.yp.to/snuffle/xsalsa-20110204.pdf (HSalsa20, XSalsa20)
 
 use crate::symmetric::stream_ciphers::common::StreamCipher;
-use crate::utils::xor_arrays;
 
 const SIG: [u32; 4] = [0x61707865, 0x3320646E, 0x79622D32, 0x6B206574];
 const TAU: [u32; 4] = [0x61707865, 0x3120646E, 0x79622D36, 0x6B206574];
@@ -23,13 +23,92 @@ macro_rules! define_salsa {
 
         impl $salsa_name {
             fn increment_counter(self: &mut Self) {
-                // we don't do little-endian conversions here because the counter's bytes are already
-                // swapped in the spec
-                let mut counter = (self.state[9] as u64) << 32 | (self.state[8] as u64);
-                counter = counter.wrapping_add(1);
+                let counter = self.counter().wrapping_add(1);
+                self.set_counter(counter);
+            }
+
+            // we don't do little-endian conversions here because the counter's bytes are already
+            // swapped in the spec
+            fn counter(&self) -> u64 {
+                (self.state[9] as u64) << 32 | (self.state[8] as u64)
+            }
+
+            fn set_counter(&mut self, counter: u64) {
                 self.state[8] = counter as u32;
                 self.state[9] = (counter >> 32) as u32;
             }
+
+            /// Same as `new`, but the keystream starts at block `counter` instead of block 0.
+            pub fn new_with_counter(init_struct: &SalsaInitStruct, key: &[u8; $key_length], counter: u64) -> Self {
+                let mut salsa = Self::new(init_struct, key);
+                salsa.set_counter(counter);
+                salsa
+            }
+
+            /// Moves to the byte `byte_offset` of the keystream (counted from block 0), so that the next call
+            /// to `cipher` treats the data as if it started at this offset.
+            pub fn seek(&mut self, byte_offset: u128) -> Result<(), &'static str> {
+                if byte_offset >= 1 << 70 {
+                    return Err("Offset is beyond the end of the keystream");
+                }
+
+                self.set_counter((byte_offset / 64) as u64);
+                self.remaining_bytes = match (byte_offset % 64) as usize {
+                    0 => 0,
+                    offset_in_block => 64 - offset_in_block,
+                };
+                Ok(())
+            }
+
+            /// Returns the offset of the next keystream byte (counted from block 0).
+            pub fn current_position(&self) -> u128 {
+                let block_start = self.counter() as u128 * 64;
+                match self.remaining_bytes {
+                    0 => block_start,
+                    remaining => block_start + 64 - remaining as u128,
+                }
+            }
+
+            /// XORs the keystream into `data`, in place.
+            pub fn apply_keystream(&mut self, data: &mut [u8]) {
+                let mut offset = 0;
+
+                // finish the current block (`state` still holds its counter)
+                if self.remaining_bytes > 0 {
+                    let hashed = hash_function(&self.state, $nb_double_rounds);
+                    let length = self.remaining_bytes.min(data.len());
+                    for (d, k) in data[..length].iter_mut().zip(&hashed[64 - self.remaining_bytes..]) {
+                        *d ^= k;
+                    }
+                    offset += length;
+                    self.remaining_bytes -= length;
+
+                    if self.remaining_bytes == 0 {
+                        self.increment_counter();
+                    }
+                }
+
+                while offset < data.len() {
+                    let hashed = hash_function(&self.state, $nb_double_rounds);
+                    let length = (data.len() - offset).min(64);
+                    for (d, k) in data[offset..offset + length].iter_mut().zip(hashed.iter()) {
+                        *d ^= k;
+                    }
+                    offset += length;
+
+                    if length == 64 { // if an entire block was used
+                        self.increment_counter();
+                    } else { // if only a partial block was used
+                        self.remaining_bytes = 64 - length;
+                    }
+                }
+            }
+
+            /// Writes the raw keystream into `keystream`.
+            pub fn fill_keystream(&mut self, keystream: &mut [u8]) {
+                keystream.fill(0);
+                self.apply_keystream(keystream);
+            }
         }
 
         impl StreamCipher for $salsa_name {
@@ -64,40 +143,14 @@ macro_rules! define_salsa {
                     return Err("Plaintext and ciphertext buffers must have the same length");
                 }
 
-                // do the thing
-                let mut offset = 0;
-                let max = plaintext.len();
-
-                // process remaining bytes in state
-                if self.remaining_bytes > 0 {
-                    let hashed = hash_function(&self.state, $nb_double_rounds);
-                    for j in 0..self.remaining_bytes {
-                        ciphertext[j] = hashed[64 - self.remaining_bytes + j] ^ plaintext[j]
-                    }
-                    offset += self.remaining_bytes;
-
-                    self.increment_counter();
-                }
-
-                while offset < max {
-                    let hashed = hash_function(&self.state, $nb_double_rounds);
+                ciphertext.copy_from_slice(plaintext);
+                self.apply_keystream(ciphertext);
 
-                    if max - offset >= 64 { // if an entire block can be ciphered
-                        let res = xor_arrays(&hashed, &plaintext[offset..offset +64].try_into().unwrap());
-                        for j in 0..64 {
-                            ciphertext[offset +j] = res[j];
-                        }
-                        offset += 64;
-                        self.increment_counter();
-                    } else { // if only a partial block can be ciphered
-                        for j in 0..max - offset {
-                            ciphertext[offset +j] = hashed[j] ^ plaintext[offset +j];
-                        }
-                        self.remaining_bytes = 64 - (max - offset);
-                        offset += max - offset;
-                    }
-                }
+                Ok(())
+            }
 
+            fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+                self.apply_keystream(data);
                 Ok(())
             }
         }
@@ -111,6 +164,60 @@ define_salsa!(Salsa12K16, 6, 16, TAU);
 define_salsa!(Salsa8K32, 4, 32, SIG);
 define_salsa!(Salsa8K16, 4, 16, TAU);
 
+/// Initialisation of XSalsa20: 192-bit nonce.
+pub struct XSalsaInitStruct {
+    pub nonce: [u8; 24]
+}
+
+/// HSalsa20: derives a 32-byte subkey from a key and a 16-byte nonce.
+pub fn hsalsa20(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    let k: [u32; 8] = core::array::from_fn(|i| u32::from_le_bytes(key[4*i..4*i+4].try_into().unwrap()));
+    let n: [u32; 4] = core::array::from_fn(|i| u32::from_le_bytes(nonce[4*i..4*i+4].try_into().unwrap()));
+
+    let mut state = [
+        SIG[0], k[0], k[1], k[2],
+        k[3], SIG[1], n[0], n[1],
+        n[2], n[3], SIG[2], k[4],
+        k[5], k[6], k[7], SIG[3]
+    ];
+    for _ in 0..10 {
+        state = double_round(&state);
+    }
+
+    // no final addition: the output words are the diagonal and the nonce positions
+    let mut subkey = [0; 32];
+    for (i, index) in [0, 5, 10, 15, 6, 7, 8, 9].iter().enumerate() {
+        subkey[4*i..4*i+4].copy_from_slice(&state[*index].to_le_bytes());
+    }
+    subkey
+}
+
+/// XSalsa20: Salsa20 keyed with `HSalsa20(key, nonce[0..16])`, using `nonce[16..24]` as nonce.
+pub struct XSalsa20 {
+    salsa: Salsa20K32
+}
+
+impl StreamCipher for XSalsa20 {
+    const KEY_SIZE: usize = 32;
+    type KeyType = [u8; 32];
+    type InitStruct = XSalsaInitStruct;
+    type State = SalsaState;
+
+    fn new(init_struct: &Self::InitStruct, key: &Self::KeyType) -> Self {
+        let subkey = hsalsa20(key, init_struct.nonce[..16].try_into().unwrap());
+        let nonce = u64::from_be_bytes(init_struct.nonce[16..].try_into().unwrap());
+        XSalsa20 { salsa: Salsa20K32::new(&SalsaInitStruct { nonce }, &subkey) }
+    }
+
+    fn cipher(&mut self, plaintext: &[u8], ciphertext: &mut [u8]) -> Result<(), &'static str> {
+        self.salsa.cipher(plaintext, ciphertext)
+    }
+
+    fn cipher_in_place(&mut self, data: &mut [u8]) -> Result<(), &'static str> {
+        self.salsa.cipher_in_place(data)
+    }
+}
+
 #[inline]
 fn quarter_round(y0: u32, y1: u32, y2: u32, y3: u32) -> (u32, u32, u32, u32) {
     let z1 = y1 ^ ((y0.wrapping_add(y3)).rotate_left(7));
@@ -889,4 +996,103 @@ mod tests_chacha {
         assert_eq!(ciphertext1, expected1);
         assert_eq!(ciphertext2, expected2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hsalsa20() {
+        // "Cryptography in NaCl", section 7: first key derived from the X25519 shared secret
+        let shared = [
+            0x4A, 0x5D, 0x9D, 0x5B, 0xA4, 0xCE, 0x2D, 0xE1, 0x72, 0x8E, 0x3B, 0xF4, 0x80, 0x35, 0x0F, 0x25,
+            0xE0, 0x7E, 0x21, 0xC9, 0x47, 0xD1, 0x9E, 0x33, 0x76, 0xF0, 0x9B, 0x3C, 0x1E, 0x16, 0x17, 0x42,
+        ];
+        let expected = [
+            0x1B, 0x27, 0x55, 0x64, 0x73, 0xE9, 0x85, 0xD4, 0x62, 0xCD, 0x51, 0x19, 0x7A, 0x9A, 0x46, 0xC7,
+            0x60, 0x09, 0x54, 0x9E, 0xAC, 0x64, 0x74, 0xF2, 0x06, 0xC4, 0xEE, 0x08, 0x44, 0xF6, 0x83, 0x89,
+        ];
+        assert_eq!(hsalsa20(&shared, &[0; 16]), expected);
+    }
+
+    #[test]
+    fn test_xsalsa20() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 24] = core::array::from_fn(|i| i as u8);
+        let expected = [0x7C, 0xB6, 0x60, 0xAF, 0xDD, 0x9E, 0xC6, 0x46, 0x8F, 0x57, 0xDD, 0x6D, 0x24, 0x33, 0xF9, 0x34];
+
+        let mut keystream = [0; 16];
+        let mut xsalsa = XSalsa20::new(&XSalsaInitStruct { nonce }, &key);
+        xsalsa.cipher(&[0; 16], &mut keystream).expect("Error during XSalsa20 test!");
+        assert_eq!(keystream, expected);
+    }
+
+    // keystream of Salsa20K32 for the key and nonce of the tests above (ciphertext xor plaintext)
+    fn keystream_k32(length: usize) -> Vec<u8> {
+        let key = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ];
+        let mut keystream = vec![0; length];
+        Salsa20K32::new(&SalsaInitStruct { nonce: 0x0011223344556677 }, &key).fill_keystream(&mut keystream);
+        keystream
+    }
+
+    #[test]
+    fn test_fill_keystream() {
+        let expected_start = [0x13, 0xFD, 0x01, 0x62, 0xB7, 0xCC, 0x38, 0xFD];
+        let expected_block_2 = [0x61, 0xA3, 0x4D, 0xB9, 0x8D, 0x89, 0x34, 0xE8];
+        let keystream = keystream_k32(128);
+        assert_eq!(keystream[0..8], expected_start);
+        assert_eq!(keystream[64..72], expected_block_2);
+    }
+
+    #[test]
+    fn test_fragmented_cipher() {
+        let reference = keystream_k32(200);
+        let mut keystream = vec![0; 200];
+        let key = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ];
+        let mut salsa = Salsa20K32::new(&SalsaInitStruct { nonce: 0x0011223344556677 }, &key);
+        for (start, end) in [(0, 10), (10, 15), (15, 64), (64, 70), (70, 200)] {
+            let plaintext = vec![0; end - start];
+            salsa.cipher(&plaintext, &mut keystream[start..end]).expect("Error during Salsa20K32 test!");
+        }
+        assert_eq!(keystream, reference);
+    }
+
+    #[test]
+    fn test_seek() {
+        let key = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ];
+        let reference = keystream_k32(300);
+        let mut salsa = Salsa20K32::new(&SalsaInitStruct { nonce: 0x0011223344556677 }, &key);
+
+        for offset in [0, 1, 63, 64, 65, 130, 200] {
+            salsa.seek(offset as u128).expect("Error during Salsa20K32 test!");
+            assert_eq!(salsa.current_position(), offset as u128);
+
+            let mut keystream = vec![0; 300 - offset];
+            salsa.fill_keystream(&mut keystream);
+            assert_eq!(keystream, reference[offset..]);
+            assert_eq!(salsa.current_position(), 300);
+        }
+
+        assert!(salsa.seek(1 << 70).is_err());
+    }
+
+    #[test]
+    fn test_new_with_counter() {
+        let key = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ];
+        let reference = keystream_k32(192);
+        let mut salsa = Salsa20K32::new_with_counter(&SalsaInitStruct { nonce: 0x0011223344556677 }, &key, 2);
+        assert_eq!(salsa.current_position(), 128);
+
+        let mut data = reference[128..].to_vec();
+        salsa.apply_keystream(&mut data);
+        assert_eq!(data, [0; 64]);
+    }
+}
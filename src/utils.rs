@@ -15,6 +15,12 @@ pub fn extract_array_from_slice<const LENGTH: usize, T: Copy+Default>(s: &[T], s
     Ok(ret)
 }
 
+/// Splits a slice into arrays of `LENGTH` elements. The slice length must be a multiple of `LENGTH`.
+pub fn slice_to_arrays<const LENGTH: usize, T: Copy>(s: &[T]) -> Vec<[T; LENGTH]>
+{
+    s.chunks_exact(LENGTH).map(|c| c.try_into().unwrap()).collect()
+}
+
 pub fn check_cipher_params(p: &[u8], c: &[u8], block_size: usize) -> Result<(), &'static str>
 {
     if p.len() != c.len() {
@@ -23,4 +29,14 @@ pub fn check_cipher_params(p: &[u8], c: &[u8], block_size: usize) -> Result<(),
         return Err("Length of plain/ciphertext is not a multiple of block size")
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Compares two slices in constant time (for a given length), e.g. to check authentication tags
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool
+{
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0, |acc, (x, y)| acc | std::hint::black_box(x ^ y));
+    diff == 0
+}